@@ -0,0 +1,105 @@
+use syn::{Attribute, Expr, LitInt, LitStr, Path};
+
+/// Attributes of the derived struct
+#[derive(Default)]
+pub struct ContainerAttributes {
+    /// The struct is encoded as SET instead of SEQUENCE
+    pub set: bool,
+}
+
+impl ContainerAttributes {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut attributes = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("asn1")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("set") {
+                    attributes.set = true;
+                    Ok(())
+                } else if meta.path.is_ident("sequence") {
+                    attributes.set = false;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported container attribute"))
+                }
+            })?;
+        }
+
+        Ok(attributes)
+    }
+}
+
+/// Context-specific tag of the field
+#[derive(Clone, Copy)]
+pub enum Tagging {
+    None,
+    Explicit(u32),
+    Implicit(u32),
+}
+
+/// Default value of the DEFAULT field
+pub enum DefaultValue {
+    /// `Default::default()`
+    Trait,
+    /// Path to the function that returns the default value
+    Function(Path),
+}
+
+/// Attributes of the struct field or the CHOICE variant
+pub struct FieldAttributes {
+    pub tagging: Tagging,
+    pub optional: bool,
+    pub default: Option<DefaultValue>,
+}
+
+impl FieldAttributes {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut attributes = Self {
+            tagging: Tagging::None,
+            optional: false,
+            default: None,
+        };
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("asn1")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("explicit") || meta.path.is_ident("implicit") {
+                    if !matches!(attributes.tagging, Tagging::None) {
+                        return Err(meta.error("field can have only one context-specific tag"));
+                    }
+
+                    let tag_number = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+                    attributes.tagging = if meta.path.is_ident("explicit") {
+                        Tagging::Explicit(tag_number)
+                    } else {
+                        Tagging::Implicit(tag_number)
+                    };
+
+                    Ok(())
+                } else if meta.path.is_ident("optional") {
+                    attributes.optional = true;
+                    Ok(())
+                } else if meta.path.is_ident("default") {
+                    attributes.default = Some(if meta.input.peek(syn::Token![=]) {
+                        DefaultValue::Function(meta.value()?.parse::<LitStr>()?.parse()?)
+                    } else {
+                        DefaultValue::Trait
+                    });
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported field attribute"))
+                }
+            })?;
+        }
+
+        Ok(attributes)
+    }
+}
+
+impl DefaultValue {
+    pub fn to_expr(&self) -> Expr {
+        match self {
+            DefaultValue::Trait => syn::parse_quote!(::core::default::Default::default()),
+            DefaultValue::Function(path) => syn::parse_quote!(#path()),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DataEnum, DeriveInput, Fields};
+
+use crate::decoder_generics;
+use crate::field::{Field, Presence};
+
+/// Derives CHOICE decoding and encoding for the enum. The alternative is selected by the tag of the next entity.
+pub fn expand(input: &DeriveInput, data: &DataEnum) -> syn::Result<TokenStream> {
+    if data.variants.is_empty() {
+        return Err(syn::Error::new_spanned(
+            input,
+            "CHOICE must have at least one alternative",
+        ));
+    }
+
+    let variants = data
+        .variants
+        .iter()
+        .enumerate()
+        .map(|(index, variant)| {
+            let Fields::Unnamed(fields) = &variant.fields else {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "CHOICE alternative must have exactly one unnamed field",
+                ));
+            };
+            if fields.unnamed.len() != 1 {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "CHOICE alternative must have exactly one unnamed field",
+                ));
+            }
+
+            let field = Field::new(index, variant.ident.to_string(), &fields.unnamed[0].ty, &variant.attrs)?;
+            if !matches!(field.presence, Presence::Required) {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "CHOICE alternative can not be OPTIONAL or DEFAULT",
+                ));
+            }
+
+            Ok((&variant.ident, field))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (decoder_generics, lifetime) = decoder_generics(&input.generics);
+    let (decoder_impl_generics, _, _) = decoder_generics.split_for_impl();
+
+    let idents = variants.iter().map(|(ident, _)| ident).collect::<Vec<_>>();
+    let matches_tag = variants
+        .iter()
+        .map(|(_, field)| field.matches_tag(&lifetime))
+        .collect::<Vec<_>>();
+    let decode = variants.iter().map(|(_, field)| field.decode(&lifetime));
+
+    let value = quote! { value };
+    let len = variants.iter().map(|(_, field)| field.len(&value));
+    let encode = variants.iter().map(|(_, field)| field.encode(&value));
+
+    Ok(quote! {
+        impl #decoder_impl_generics ::asn1_parser::Asn1Decoder<#lifetime> for #name #ty_generics #where_clause {
+            fn compare_tags(tag: ::asn1_parser::Tag) -> bool {
+                #(#matches_tag)||*
+            }
+
+            fn decode(reader: &mut ::asn1_parser::Reader<#lifetime>) -> ::asn1_parser::Asn1Result<Self> {
+                let offset = reader.full_offset();
+                let tag = ::asn1_parser::peek_tag(reader)?;
+
+                #(if #matches_tag {
+                    return Ok(Self::#idents(#decode));
+                })*
+
+                Err(::asn1_parser::Error::unexpected_tag(None, tag).with_offset(offset))
+            }
+        }
+
+        impl #impl_generics ::asn1_parser::Asn1Encoder for #name #ty_generics #where_clause {
+            fn needed_buf_size(&self) -> usize {
+                match self {
+                    #(Self::#idents(value) => #len,)*
+                }
+            }
+
+            fn encode(&self, writer: &mut ::asn1_parser::Writer) -> ::asn1_parser::Asn1Result<()> {
+                match self {
+                    #(Self::#idents(value) => {
+                        #encode;
+                    })*
+                }
+
+                Ok(())
+            }
+        }
+    })
+}
@@ -0,0 +1,160 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Attribute, Expr, GenericArgument, Lifetime, PathArguments, Type};
+
+use crate::attributes::{FieldAttributes, Tagging};
+
+/// Presence of the field in the encoding
+pub enum Presence {
+    Required,
+    /// OPTIONAL field. The field type is `Option<T>`
+    Optional,
+    /// DEFAULT field. The expression returns the default value
+    Default(Expr),
+}
+
+/// Struct field or CHOICE variant
+pub struct Field<'a> {
+    /// Field or variant name used in error messages
+    pub name: String,
+    /// Name of the local variable that holds the decoded value
+    pub var: Ident,
+    /// Type of the field value. For OPTIONAL fields it's the `T` from the `Option<T>`
+    pub ty: &'a Type,
+    pub tagging: Tagging,
+    pub presence: Presence,
+}
+
+impl<'a> Field<'a> {
+    pub fn new(index: usize, name: String, ty: &'a Type, attrs: &[Attribute]) -> syn::Result<Self> {
+        let attributes = FieldAttributes::parse(attrs)?;
+
+        let (ty, presence) = match (attributes.optional, attributes.default) {
+            (true, Some(_)) => {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "field can not be OPTIONAL and DEFAULT at the same time",
+                ))
+            }
+            (true, None) => (
+                option_inner_type(ty)
+                    .ok_or_else(|| syn::Error::new_spanned(ty, "OPTIONAL field type must be Option<T>"))?,
+                Presence::Optional,
+            ),
+            (false, Some(default)) => (ty, Presence::Default(default.to_expr())),
+            (false, None) => (ty, Presence::Required),
+        };
+
+        Ok(Self {
+            name,
+            var: format_ident!("field_{}", index),
+            ty,
+            tagging: attributes.tagging,
+            presence,
+        })
+    }
+
+    /// Returns the expression that checks if the `tag` variable belongs to this field
+    pub fn matches_tag(&self, lifetime: &Lifetime) -> TokenStream {
+        let ty = self.ty;
+
+        match self.tagging {
+            Tagging::None => quote! { <#ty as ::asn1_parser::Asn1Decoder<#lifetime>>::compare_tags(tag) },
+            Tagging::Explicit(number) => quote! { tag == ::asn1_parser::explicit_tag(#number) },
+            Tagging::Implicit(number) => quote! { ::asn1_parser::is_implicit_tag(tag, #number) },
+        }
+    }
+
+    /// Returns the expression that decodes the field value from the `reader` variable
+    pub fn decode(&self, lifetime: &Lifetime) -> TokenStream {
+        let ty = self.ty;
+
+        match self.tagging {
+            Tagging::None => quote! { <#ty as ::asn1_parser::Asn1Decoder<#lifetime>>::decode(reader)? },
+            Tagging::Explicit(number) => quote! { ::asn1_parser::decode_explicit::<#ty>(reader, #number)? },
+            Tagging::Implicit(number) => {
+                quote! { { let value: #ty = ::asn1_parser::decode_implicit(reader, #number)?; value } }
+            }
+        }
+    }
+
+    /// Returns the expression that calculates the encoded length of the field value
+    pub fn len(&self, value: &TokenStream) -> TokenStream {
+        match self.tagging {
+            Tagging::None => quote! { ::asn1_parser::Asn1Encoder::needed_buf_size(#value) },
+            Tagging::Explicit(number) => quote! { ::asn1_parser::explicit_len(#number, #value) },
+            Tagging::Implicit(number) => quote! { ::asn1_parser::implicit_len(#number, #value) },
+        }
+    }
+
+    /// Returns the expression that encodes the field value into the `writer` variable
+    pub fn encode(&self, value: &TokenStream) -> TokenStream {
+        match self.tagging {
+            Tagging::None => quote! { ::asn1_parser::Asn1Encoder::encode(#value, writer)? },
+            Tagging::Explicit(number) => quote! { ::asn1_parser::encode_explicit(#number, #value, writer)? },
+            Tagging::Implicit(number) => quote! { ::asn1_parser::encode_implicit(#number, #value, writer)? },
+        }
+    }
+
+    /// Returns the expression that calculates the encoded length of the struct field taking into account its presence
+    pub fn field_len(&self, field: &TokenStream) -> TokenStream {
+        let value = quote! { value };
+        let len = self.len(&value);
+
+        match &self.presence {
+            Presence::Required => self.len(&quote! { &#field }),
+            Presence::Optional => quote! { #field.as_ref().map(|value| #len).unwrap_or_default() },
+            Presence::Default(default) => quote! {
+                {
+                    let value = &#field;
+                    if ::asn1_parser::encodes_equal(value, &#default) { 0 } else { #len }
+                }
+            },
+        }
+    }
+
+    /// Returns the statement that encodes the struct field taking into account its presence
+    pub fn encode_field(&self, field: &TokenStream) -> TokenStream {
+        let value = quote! { value };
+        let encode = self.encode(&value);
+
+        match &self.presence {
+            Presence::Required => {
+                let encode = self.encode(&quote! { &#field });
+                quote! { #encode; }
+            }
+            Presence::Optional => quote! {
+                if let Some(value) = #field.as_ref() {
+                    #encode;
+                }
+            },
+            Presence::Default(default) => quote! {
+                {
+                    let value = &#field;
+                    if !::asn1_parser::encodes_equal(value, &#default) {
+                        #encode;
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// Returns `T` if the type is `Option<T>`
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+
+    match arguments.args.first()? {
+        GenericArgument::Type(ty) if arguments.args.len() == 1 => Some(ty),
+        _ => None,
+    }
+}
@@ -0,0 +1,46 @@
+//! Derive macros for typed asn1 structures on top of the `asn1-parser` crate.
+//!
+//! See the crate README for the supported attributes.
+
+mod attributes;
+mod choice;
+mod field;
+mod structure;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, GenericParam, Generics, Lifetime, LifetimeParam};
+
+/// Derives asn1 decoding and encoding.
+///
+/// Structs are encoded as SEQUENCE (or SET with `#[asn1(set)]`) and implement `Asn1ValueDecoder` and `Asn1ValueEncoder`,
+/// so `Tlv<'data, Struct>` implements `Asn1Decoder` and `Asn1Encoder`.
+/// Enums are encoded as CHOICE and implement `Asn1Decoder` and `Asn1Encoder` directly.
+#[proc_macro_derive(Asn1, attributes(asn1))]
+pub fn derive_asn1(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let expanded = match &input.data {
+        Data::Struct(data) => structure::expand(&input, data),
+        Data::Enum(data) => choice::expand(&input, data),
+        Data::Union(_) => Err(syn::Error::new_spanned(&input, "Asn1 can not be derived for unions")),
+    };
+
+    expanded.unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Returns generics for the decoder impl and the lifetime of the decoded data.
+///
+/// The first lifetime of the type is used as the data lifetime. If the type has no lifetimes, a new one is added.
+fn decoder_generics(generics: &Generics) -> (Generics, Lifetime) {
+    if let Some(lifetime) = generics.lifetimes().next() {
+        return (generics.clone(), lifetime.lifetime.clone());
+    }
+
+    let lifetime: Lifetime = parse_quote!('data);
+    let mut generics = generics.clone();
+    generics
+        .params
+        .insert(0, GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())));
+
+    (generics, lifetime)
+}
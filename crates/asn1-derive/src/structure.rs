@@ -0,0 +1,185 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DataStruct, DeriveInput, Member};
+
+use crate::attributes::ContainerAttributes;
+use crate::decoder_generics;
+use crate::field::{Field, Presence};
+
+/// Derives SEQUENCE or SET decoding and encoding for the struct
+pub fn expand(input: &DeriveInput, data: &DataStruct) -> syn::Result<TokenStream> {
+    let attributes = ContainerAttributes::parse(&input.attrs)?;
+
+    let members = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        })
+        .collect::<Vec<_>>();
+    let fields = data
+        .fields
+        .iter()
+        .zip(&members)
+        .enumerate()
+        .map(|(index, (field, member))| {
+            let name = match member {
+                Member::Named(ident) => ident.to_string(),
+                Member::Unnamed(index) => index.index.to_string(),
+            };
+
+            Field::new(index, name, &field.ty, &field.attrs)
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (decoder_generics, lifetime) = decoder_generics(&input.generics);
+    let (decoder_impl_generics, _, _) = decoder_generics.split_for_impl();
+
+    let tag = if attributes.set {
+        quote! { ::asn1_parser::Set::TAG }
+    } else {
+        quote! { ::asn1_parser::Sequence::TAG }
+    };
+
+    let decode_fields = if attributes.set {
+        decode_set(&fields, &lifetime)
+    } else {
+        decode_sequence(&fields, &lifetime)
+    };
+    let vars = fields.iter().map(|field| &field.var);
+
+    let self_fields = members.iter().map(|member| quote! { self.#member }).collect::<Vec<_>>();
+    let fields_len = fields
+        .iter()
+        .zip(&self_fields)
+        .map(|(field, value)| field.field_len(value));
+    let encode_fields = fields
+        .iter()
+        .zip(&self_fields)
+        .map(|(field, value)| field.encode_field(value));
+
+    Ok(quote! {
+        impl #impl_generics ::asn1_parser::Taggable for #name #ty_generics #where_clause {
+            fn tag(&self) -> ::asn1_parser::Tag {
+                #tag
+            }
+        }
+
+        impl #decoder_impl_generics ::asn1_parser::Asn1ValueDecoder<#lifetime> for #name #ty_generics #where_clause {
+            fn decode(
+                _: ::asn1_parser::Tag,
+                reader: &mut ::asn1_parser::Reader<#lifetime>,
+            ) -> ::asn1_parser::Asn1Result<Self> {
+                #decode_fields
+
+                Ok(Self { #(#members: #vars),* })
+            }
+
+            fn compare_tags(tag: ::asn1_parser::Tag) -> bool {
+                #tag == tag
+            }
+
+            fn expected_tag() -> Option<::asn1_parser::Tag> {
+                Some(#tag)
+            }
+        }
+
+        impl #impl_generics ::asn1_parser::Asn1ValueEncoder for #name #ty_generics #where_clause {
+            fn value_len(&self) -> usize {
+                0 #(+ #fields_len)*
+            }
+
+            fn encode_value(&self, writer: &mut ::asn1_parser::Writer) -> ::asn1_parser::Asn1Result<()> {
+                #(#encode_fields)*
+
+                Ok(())
+            }
+        }
+    })
+}
+
+/// SEQUENCE fields are decoded one by one. Absent OPTIONAL and DEFAULT fields are detected by the next tag.
+fn decode_sequence(fields: &[Field], lifetime: &syn::Lifetime) -> TokenStream {
+    let decode_fields = fields.iter().map(|field| {
+        let var = &field.var;
+        let decode = field.decode(lifetime);
+        let matches_tag = field.matches_tag(lifetime);
+
+        match &field.presence {
+            Presence::Required => quote! { let #var = #decode; },
+            Presence::Optional => quote! {
+                let #var = if matches!(::asn1_parser::peek_tag(reader), Ok(tag) if #matches_tag) {
+                    Some(#decode)
+                } else {
+                    None
+                };
+            },
+            Presence::Default(default) => quote! {
+                let #var = if matches!(::asn1_parser::peek_tag(reader), Ok(tag) if #matches_tag) {
+                    #decode
+                } else {
+                    #default
+                };
+            },
+        }
+    });
+
+    quote! {
+        #(#decode_fields)*
+
+        if !reader.empty() {
+            return Err(::asn1_parser::Error::new(
+                ::asn1_parser::ErrorKind::TrailingData,
+                "Data after the last SEQUENCE field",
+            )
+            .with_offset(reader.full_offset()));
+        }
+    }
+}
+
+/// SET fields can be encoded in any order, so every entity is matched against not yet decoded fields by its tag.
+fn decode_set(fields: &[Field], lifetime: &syn::Lifetime) -> TokenStream {
+    let vars = fields.iter().map(|field| &field.var).collect::<Vec<_>>();
+    let types = fields.iter().map(|field| field.ty);
+    let matches_tag = fields.iter().map(|field| field.matches_tag(lifetime));
+    let decode = fields.iter().map(|field| field.decode(lifetime));
+
+    let finish_fields = fields.iter().map(|field| {
+        let var = &field.var;
+        let message = format!("Missing SET field: {}", field.name);
+
+        match &field.presence {
+            Presence::Required => quote! {
+                let #var = #var.ok_or_else(|| {
+                    ::asn1_parser::Error::new(::asn1_parser::ErrorKind::InvalidValue, #message)
+                        .with_offset(reader.full_offset())
+                })?;
+            },
+            Presence::Optional => quote! {},
+            Presence::Default(default) => quote! {
+                let #var = #var.unwrap_or_else(|| #default);
+            },
+        }
+    });
+
+    quote! {
+        #(let mut #vars: Option<#types> = None;)*
+
+        while !reader.empty() {
+            let offset = reader.full_offset();
+            let tag = ::asn1_parser::peek_tag(reader)?;
+
+            #(if #vars.is_none() && #matches_tag {
+                #vars = Some(#decode);
+            } else)* {
+                return Err(::asn1_parser::Error::unexpected_tag(None, tag).with_offset(offset));
+            }
+        }
+
+        #(#finish_fields)*
+    }
+}
@@ -0,0 +1,148 @@
+use asn1_derive::Asn1;
+use asn1_parser::{
+    Asn1Decoder, Asn1Encoder, Bool, ErrorKind, Integer, OctetString, RawAsn1EntityData, Tlv, Utf8String,
+};
+
+/// EncryptedData ::= SEQUENCE {
+///     etype   [0] Int32,
+///     kvno    [1] UInt32 OPTIONAL,
+///     cipher  [2] OCTET STRING
+/// }
+#[derive(Debug, Asn1)]
+struct EncryptedData<'data> {
+    #[asn1(explicit = 0)]
+    etype: Tlv<'data, Integer<'data>>,
+    #[asn1(explicit = 1, optional)]
+    kvno: Option<Tlv<'data, Integer<'data>>>,
+    #[asn1(explicit = 2)]
+    cipher: Tlv<'data, OctetString<'data>>,
+}
+
+#[derive(Debug, Asn1)]
+#[asn1(set)]
+struct Extension<'data> {
+    #[asn1(implicit = 0)]
+    name: Tlv<'data, Utf8String<'data>>,
+    #[asn1(implicit = 1, default = "not_critical")]
+    critical: Tlv<'data, Bool>,
+    #[asn1(explicit = 2, optional)]
+    id: Option<Tlv<'data, Integer<'data>>>,
+}
+
+fn not_critical<'data>() -> Tlv<'data, Bool> {
+    Tlv::new(0, RawAsn1EntityData::default(), Bool::from(false))
+}
+
+#[derive(Debug, Asn1)]
+enum Principal<'data> {
+    #[asn1(explicit = 0)]
+    Name(Tlv<'data, Utf8String<'data>>),
+    Id(Tlv<'data, Integer<'data>>),
+}
+
+#[derive(Debug, Asn1)]
+struct Message<'data> {
+    principal: Principal<'data>,
+    data: Tlv<'data, EncryptedData<'data>>,
+}
+
+fn encode<T: Asn1Encoder>(value: &T) -> Vec<u8> {
+    let mut buff = vec![0; value.needed_buf_size()];
+    value.encode_buff(&mut buff).unwrap();
+
+    buff
+}
+
+#[test]
+fn sequence() {
+    let raw = [
+        0x30, 0x12, 0xa0, 0x03, 0x02, 0x01, 0x12, 0xa1, 0x03, 0x02, 0x01, 0x02, 0xa2, 0x06, 0x04, 0x04, 0xde, 0xad,
+        0xbe, 0xef,
+    ];
+    let encrypted_data = Tlv::<EncryptedData>::decode_buff(&raw).unwrap();
+    let value = encrypted_data.inner_asn1();
+
+    assert_eq!(i32::try_from(value.etype.inner_asn1()).unwrap(), 18);
+    assert_eq!(value.etype.meta().tag_position(), 4);
+    let kvno = value.kvno.as_ref().unwrap();
+    assert_eq!(i32::try_from(kvno.inner_asn1()).unwrap(), 2);
+    assert_eq!(kvno.meta().tag_position(), 9);
+    assert_eq!(value.cipher.meta().tag_position(), 14);
+    assert_eq!(value.cipher.meta().data_bytes(), &[0xde, 0xad, 0xbe, 0xef]);
+
+    assert_eq!(encode(&encrypted_data), raw);
+
+    let raw = [
+        0x30, 0x0d, 0xa0, 0x03, 0x02, 0x01, 0x12, 0xa2, 0x06, 0x04, 0x04, 0xde, 0xad, 0xbe, 0xef,
+    ];
+    let encrypted_data = Tlv::<EncryptedData>::decode_buff(&raw).unwrap();
+    assert!(encrypted_data.inner_asn1().kvno.is_none());
+    assert_eq!(encode(&encrypted_data), raw);
+
+    // missing etype
+    let raw = [
+        0x30, 0x0d, 0xa1, 0x03, 0x02, 0x01, 0x02, 0xa2, 0x06, 0x04, 0x04, 0xde, 0xad, 0xbe, 0xef,
+    ];
+    let error = Tlv::<EncryptedData>::decode_buff(&raw).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedTag);
+    assert_eq!(error.offset(), Some(2));
+}
+
+#[test]
+fn set() {
+    let raw = [0x31, 0x09, 0xa2, 0x03, 0x02, 0x01, 0x05, 0x80, 0x02, 0x68, 0x69];
+    let extension = Tlv::<Extension>::decode_buff(&raw).unwrap();
+    let value = extension.inner_asn1();
+
+    assert_eq!(value.name.inner_asn1().string(), "hi");
+    assert_eq!(value.name.meta().tag_position(), 7);
+    assert!(!value.critical.inner_asn1().value());
+    assert_eq!(i32::try_from(value.id.as_ref().unwrap().inner_asn1()).unwrap(), 5);
+
+    // fields are encoded in the declaration order and the DEFAULT field is omitted
+    assert_eq!(
+        encode(&extension),
+        [0x31, 0x09, 0x80, 0x02, 0x68, 0x69, 0xa2, 0x03, 0x02, 0x01, 0x05]
+    );
+
+    let raw = [0x31, 0x07, 0x81, 0x01, 0xff, 0x80, 0x02, 0x68, 0x69];
+    let extension = Tlv::<Extension>::decode_buff(&raw).unwrap();
+    assert!(extension.inner_asn1().critical.inner_asn1().value());
+    assert!(extension.inner_asn1().id.is_none());
+    assert_eq!(
+        encode(&extension),
+        [0x31, 0x07, 0x80, 0x02, 0x68, 0x69, 0x81, 0x01, 0xff]
+    );
+
+    let error = Tlv::<Extension>::decode_buff(&[0x31, 0x03, 0x81, 0x01, 0xff]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidValue);
+
+    let error = Tlv::<Extension>::decode_buff(&[0x31, 0x06, 0x80, 0x01, 0x61, 0x80, 0x01, 0x62]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedTag);
+    assert_eq!(error.offset(), Some(5));
+}
+
+#[test]
+fn choice() {
+    let principal = Principal::decode_buff(&[0x02, 0x01, 0x07]).unwrap();
+    assert!(matches!(&principal, Principal::Id(id) if i32::try_from(id.inner_asn1()).unwrap() == 7));
+    assert_eq!(encode(&principal), [0x02, 0x01, 0x07]);
+
+    let raw = [
+        0x30, 0x18, 0xa0, 0x04, 0x0c, 0x02, 0x68, 0x69, 0x30, 0x10, 0xa0, 0x03, 0x02, 0x01, 0x12, 0xa2, 0x09, 0x04,
+        0x07, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06,
+    ];
+    let message = Tlv::<Message>::decode_buff(&raw).unwrap();
+    let value = message.inner_asn1();
+
+    let Principal::Name(name) = &value.principal else {
+        panic!("expected principal name");
+    };
+    assert_eq!(name.inner_asn1().string(), "hi");
+    assert_eq!(value.data.meta().tag_position(), 8);
+    assert_eq!(value.data.inner_asn1().cipher.meta().tag_position(), 17);
+    assert_eq!(encode(&message), raw);
+
+    let error = Principal::decode_buff(&[0x01, 0x01, 0xff]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedTag);
+}
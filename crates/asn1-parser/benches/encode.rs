@@ -0,0 +1,80 @@
+//! Compares the encoding into the pre-sized buffer with the growable writer.
+//!
+//! Run it using `cargo bench -p asn1-parser`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use asn1_parser::{Asn1Encoder, Asn1Type, ExplicitTag, OctetString, OwnedAsn1, OwnedInteger, Sequence};
+
+const ITERATIONS: u32 = 50;
+
+fn node(asn1: Asn1Type<'static>) -> OwnedAsn1 {
+    OwnedAsn1::new(0, Default::default(), asn1)
+}
+
+/// Deeply nested structure (like certificates chains wrapped into CMS)
+fn deep(depth: usize) -> OwnedAsn1 {
+    let mut asn1 = node(Asn1Type::OctetString(OctetString::new_owned(vec![0xab; 1024])));
+
+    for i in 0..depth {
+        asn1 = if i % 2 == 0 {
+            node(Asn1Type::Sequence(Sequence::new(vec![asn1])))
+        } else {
+            node(Asn1Type::ExplicitTag(ExplicitTag::new(0, vec![asn1])))
+        };
+    }
+
+    asn1
+}
+
+/// Wide structure with a lot of small fields (like CRLs)
+fn wide(width: i64) -> OwnedAsn1 {
+    node(Asn1Type::Sequence(Sequence::new(
+        (0..width)
+            .map(|i| {
+                node(Asn1Type::Sequence(Sequence::new(vec![
+                    node(Asn1Type::Integer(OwnedInteger::from(i * 1_000_003))),
+                    node(Asn1Type::OctetString(OctetString::new_owned(vec![0xcd; 20]))),
+                ])))
+            })
+            .collect(),
+    )))
+}
+
+fn measure(f: impl Fn() -> Vec<u8>) -> Duration {
+    // warm up
+    black_box(f());
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn bench(name: &str, asn1: &OwnedAsn1) {
+    let encode_buff = measure(|| {
+        let mut buff = vec![0; asn1.needed_buf_size()];
+        asn1.encode_buff(&mut buff).unwrap();
+        buff
+    });
+    let encode_to_vec = measure(|| asn1.encode_to_vec().unwrap());
+
+    println!(
+        "{:<12} {:>10} bytes   encode_buff: {:>12?}   encode_to_vec: {:>12?}",
+        name,
+        asn1.needed_buf_size(),
+        encode_buff,
+        encode_to_vec
+    );
+}
+
+fn main() {
+    bench("deep 8", &deep(8));
+    bench("deep 16", &deep(16));
+    bench("deep 20", &deep(20));
+    bench("wide 1000", &wide(1_000));
+    bench("wide 100000", &wide(100_000));
+}
@@ -0,0 +1,19 @@
+#![no_main]
+
+use asn1_parser::{Asn1, Asn1Decoder, Asn1Encoder};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(asn1) = Asn1::decode_buff(data) else {
+        return;
+    };
+    assert!(data.starts_with(asn1.meta().raw_bytes()));
+
+    let encoded = asn1.encode_to_vec().expect("decoded asn1 entity should be encodable");
+    assert_eq!(asn1.needed_buf_size(), encoded.len());
+    assert_eq!(asn1.meta().raw_bytes(), encoded, "decoded asn1 entity should be re-encoded exactly");
+
+    let decoded = Asn1::decode_buff(&encoded).expect("re-encoded asn1 entity should be decodable");
+    assert_eq!(decoded.meta().raw_bytes(), encoded);
+    assert_eq!(decoded.encode_to_vec().unwrap(), encoded);
+});
@@ -1,12 +1,19 @@
-use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
 use core::ops::Range;
 
-use crate::reader::Reader;
+use crate::length::{is_minimal_length, len_size, write_len, write_len_and_value, INDEFINITE_LENGTH};
+use crate::reader::{DecodeOptions, Reader};
+use crate::shared::{CopiedRawData, RawBytes, RawDataStorage};
+use crate::tag::{tag_size, write_tag};
+use crate::unparsed::unparsed_len;
 use crate::writer::Writer;
 use crate::{
-    ApplicationTag, Asn1Encoder, Asn1Result, Asn1ValueDecoder, BitString, BmpString, Bool, Error, ExplicitTag,
-    GeneralString, GeneralizedTime, IA5String, ImplicitTag, Integer, MetaInfo, Null, NumericString, ObjectIdentifier,
-    OctetString, PrintableString, Sequence, Set, Tag, Taggable, Tlv, UtcTime, Utf8String, VisibleString,
+    ApplicationTag, Asn1Decoder, Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, BitString, BmpString, Bool,
+    Enumerated, Error, ErrorKind, ExplicitTag, GeneralString, GeneralizedTime, GraphicString, IA5String, ImplicitTag,
+    Integer, MetaInfo, Null, NumericString, ObjectDescriptor, ObjectIdentifier, OctetString, PrintableString, Real,
+    RelativeOid, Sequence, Set, Tag, Taggable, TeletexString, Tlv, UniversalString, Unknown, Unparsed, UtcTime,
+    Utf8String, VideotexString, VisibleString,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,6 +30,11 @@ pub enum Asn1Type<'data> {
     GeneralString(GeneralString<'data>),
     NumericString(NumericString<'data>),
     VisibleString(VisibleString<'data>),
+    UniversalString(UniversalString<'data>),
+    TeletexString(TeletexString<'data>),
+    VideotexString(VideotexString<'data>),
+    GraphicString(GraphicString<'data>),
+    ObjectDescriptor(ObjectDescriptor<'data>),
 
     UtcTime(UtcTime),
     GeneralizedTime(GeneralizedTime),
@@ -30,11 +42,17 @@ pub enum Asn1Type<'data> {
     Bool(Bool),
     Null(Null),
     Integer(Integer<'data>),
+    Enumerated(Enumerated<'data>),
+    Real(Real<'data>),
     ObjectIdentifier(ObjectIdentifier),
+    RelativeOid(RelativeOid),
 
     ExplicitTag(ExplicitTag<'data>),
     ImplicitTag(ImplicitTag<'data>),
     ApplicationTag(ApplicationTag<'data>),
+
+    Unknown(Unknown<'data>),
+    Unparsed(Unparsed<'data>),
 }
 
 pub type Asn1<'data> = Tlv<'data, Asn1Type<'data>>;
@@ -42,31 +60,163 @@ pub type OwnedAsn1 = Tlv<'static, Asn1Type<'static>>;
 
 pub type OwnedAsn1Type = Asn1Type<'static>;
 
+impl<'data> Asn1<'data> {
+    /// Decodes the asn1 entity using provided buffer in the lenient mode.
+    ///
+    /// Malformed data does not fail the decoding. Instead, undecodable regions become [Unparsed] nodes,
+    /// truncated lengths are clamped, and all encountered errors are returned as diagnostics.
+    pub fn decode_buff_lenient(buff: &'data [u8]) -> (Self, Vec<Error>) {
+        Self::decode_buff_lenient_with_options(buff, DecodeOptions::default())
+    }
+
+    /// Decodes the asn1 entity using provided buffer and decoding limits in the lenient mode.
+    ///
+    /// When a limit is hit, the rest of the data of the current constructed entity becomes [Unparsed].
+    pub fn decode_buff_lenient_with_options(buff: &'data [u8], options: DecodeOptions) -> (Self, Vec<Error>) {
+        let mut reader = Reader::with_options(buff, options);
+        reader.set_lenient(true);
+
+        let asn1 = Self::decode_node_lenient(&mut reader);
+
+        if !reader.empty() {
+            let error =
+                Error::new(ErrorKind::TrailingData, "Data after the asn1 entity").with_offset(reader.full_offset());
+            reader.push_diagnostic(error);
+        }
+
+        (asn1, reader.take_diagnostics())
+    }
+
+    /// Decodes the asn1 entity. In the lenient mode, the entity that can not be decoded becomes [Unparsed] node.
+    pub(crate) fn decode_node(reader: &mut Reader<'data>) -> Asn1Result<Self> {
+        if !reader.is_lenient() {
+            return Self::decode(reader);
+        }
+
+        Ok(Self::decode_node_lenient(reader))
+    }
+
+    /// Decodes the asn1 entity encapsulated into the contents octets of the entity that is being decoded.
+    ///
+    /// Returns `None` if the octets are not exactly one valid asn1 entity.
+    pub(crate) fn decode_encapsulated(
+        reader: &mut Reader<'data>,
+        data: &'data [u8],
+        offset: usize,
+    ) -> Asn1Result<Option<Box<Self>>> {
+        let mut inner_reader = reader.encapsulated(data, offset);
+        let inner = Self::decode(&mut inner_reader);
+        reader.finish_nested(&inner_reader);
+
+        match inner {
+            Ok(inner) if inner_reader.empty() => Ok(Some(Box::new(inner))),
+            Ok(_) => Ok(None),
+            Err(error) => {
+                skip_encapsulated_error(reader, error)?;
+
+                Ok(None)
+            }
+        }
+    }
+
+    fn decode_node_lenient(reader: &mut Reader<'data>) -> Self {
+        let position = reader.position();
+        let offset = reader.full_offset();
+        let data = reader.remaining();
+
+        match Self::decode(reader) {
+            Ok(asn1) => asn1,
+            Err(error) => {
+                let unparsed_len = if error.kind() == ErrorKind::LimitExceeded {
+                    data.len()
+                } else {
+                    unparsed_len(data)
+                };
+                reader.push_diagnostic(error);
+
+                let unparsed = &data[0..unparsed_len];
+                reader.set_position(position + unparsed.len());
+
+                Tlv::new(
+                    reader.next_id(),
+                    RawAsn1EntityData {
+                        raw_data: RawBytes::from(unparsed),
+                        tag: offset,
+                        length: 0..0,
+                        data: 0..unparsed.len(),
+                    },
+                    Asn1Type::Unparsed(Unparsed::new(unparsed)),
+                )
+            }
+        }
+    }
+}
+
+/// Skips the error of the encapsulated entities decoding, so they are kept as raw octets.
+///
+/// Exceeded decoding limits are not skipped: they fail the decoding or become diagnostics in the lenient mode.
+pub(crate) fn skip_encapsulated_error(reader: &mut Reader<'_>, error: Error) -> Asn1Result<()> {
+    if error.kind() != ErrorKind::LimitExceeded {
+        return Ok(());
+    }
+
+    if !reader.is_lenient() {
+        return Err(error);
+    }
+    reader.push_diagnostic(error);
+
+    Ok(())
+}
+
 impl Asn1Type<'_> {
     pub fn to_owned(&self) -> OwnedAsn1Type {
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    /// Returns owned version of the asn1 type. Raw data of the nested nodes is kept in the provided storage
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedAsn1Type {
         match self {
-            Asn1Type::Sequence(s) => Asn1Type::Sequence(s.to_owned()),
-            Asn1Type::Set(s) => Asn1Type::Set(s.to_owned()),
-            Asn1Type::OctetString(o) => Asn1Type::OctetString(o.to_owned()),
-            Asn1Type::Utf8String(u) => Asn1Type::Utf8String(u.to_owned()),
-            Asn1Type::BitString(b) => Asn1Type::BitString(b.to_owned()),
-            Asn1Type::IA5String(i) => Asn1Type::IA5String(i.to_owned()),
-            Asn1Type::PrintableString(p) => Asn1Type::PrintableString(p.to_owned()),
-            Asn1Type::GeneralString(g) => Asn1Type::GeneralString(g.to_owned()),
-            Asn1Type::NumericString(n) => Asn1Type::NumericString(n.to_owned()),
-            Asn1Type::VisibleString(n) => Asn1Type::VisibleString(n.to_owned()),
+            Asn1Type::Sequence(s) => Asn1Type::Sequence(s.to_owned_in(storage)),
+            Asn1Type::Set(s) => Asn1Type::Set(s.to_owned_in(storage)),
+            Asn1Type::OctetString(o) => Asn1Type::OctetString(o.to_owned_in(storage)),
+            Asn1Type::Utf8String(u) => Asn1Type::Utf8String(u.to_owned_in(storage)),
+            Asn1Type::BitString(b) => Asn1Type::BitString(b.to_owned_in(storage)),
+            Asn1Type::IA5String(i) => Asn1Type::IA5String(i.to_owned_in(storage)),
+            Asn1Type::PrintableString(p) => Asn1Type::PrintableString(p.to_owned_in(storage)),
+            Asn1Type::GeneralString(g) => Asn1Type::GeneralString(g.to_owned_in(storage)),
+            Asn1Type::NumericString(n) => Asn1Type::NumericString(n.to_owned_in(storage)),
+            Asn1Type::VisibleString(n) => Asn1Type::VisibleString(n.to_owned_in(storage)),
+            Asn1Type::UniversalString(u) => Asn1Type::UniversalString(u.to_owned_in(storage)),
+            Asn1Type::TeletexString(t) => Asn1Type::TeletexString(t.to_owned_in(storage)),
+            Asn1Type::VideotexString(v) => Asn1Type::VideotexString(v.to_owned_in(storage)),
+            Asn1Type::GraphicString(g) => Asn1Type::GraphicString(g.to_owned_in(storage)),
+            Asn1Type::ObjectDescriptor(o) => Asn1Type::ObjectDescriptor(o.to_owned_in(storage)),
             Asn1Type::Bool(b) => Asn1Type::Bool(b.clone()),
             Asn1Type::Null(n) => Asn1Type::Null(n.clone()),
-            Asn1Type::Integer(i) => Asn1Type::Integer(i.to_owned()),
+            Asn1Type::Integer(i) => Asn1Type::Integer(i.to_owned_in(storage)),
+            Asn1Type::Enumerated(e) => Asn1Type::Enumerated(e.to_owned_in(storage)),
+            Asn1Type::Real(r) => Asn1Type::Real(r.to_owned_in(storage)),
             Asn1Type::ObjectIdentifier(o) => Asn1Type::ObjectIdentifier(o.clone()),
-            Asn1Type::ExplicitTag(e) => Asn1Type::ExplicitTag(e.to_owned()),
-            Asn1Type::ImplicitTag(i) => Asn1Type::ImplicitTag(i.to_owned()),
-            Asn1Type::ApplicationTag(a) => Asn1Type::ApplicationTag(a.to_owned()),
-            Asn1Type::BmpString(b) => Asn1Type::BmpString(b.to_owned()),
+            Asn1Type::RelativeOid(r) => Asn1Type::RelativeOid(r.clone()),
+            Asn1Type::ExplicitTag(e) => Asn1Type::ExplicitTag(e.to_owned_in(storage)),
+            Asn1Type::ImplicitTag(i) => Asn1Type::ImplicitTag(i.to_owned_in(storage)),
+            Asn1Type::ApplicationTag(a) => Asn1Type::ApplicationTag(a.to_owned_in(storage)),
+            Asn1Type::Unknown(u) => Asn1Type::Unknown(u.to_owned_in(storage)),
+            Asn1Type::BmpString(b) => Asn1Type::BmpString(b.to_owned_in(storage)),
             Asn1Type::UtcTime(u) => Asn1Type::UtcTime(u.clone()),
             Asn1Type::GeneralizedTime(u) => Asn1Type::GeneralizedTime(u.clone()),
+            Asn1Type::Unparsed(u) => Asn1Type::Unparsed(u.to_owned_in(storage)),
         }
     }
+
+    /// Returns encoded contents octets of the asn1 entity
+    pub(crate) fn contents(&self) -> Vec<u8> {
+        let mut writer = Writer::growable();
+        // the growable writer can not run out of space, so the encoding does not fail
+        self.encode_value(&mut writer)
+            .and_then(|_| writer.into_vec())
+            .unwrap_or_default()
+    }
 }
 
 impl Taggable for Asn1Type<'_> {
@@ -83,15 +233,25 @@ impl Taggable for Asn1Type<'_> {
             Asn1Type::GeneralString(g) => g.tag(),
             Asn1Type::NumericString(g) => g.tag(),
             Asn1Type::VisibleString(g) => g.tag(),
+            Asn1Type::UniversalString(u) => u.tag(),
+            Asn1Type::TeletexString(t) => t.tag(),
+            Asn1Type::VideotexString(v) => v.tag(),
+            Asn1Type::GraphicString(g) => g.tag(),
+            Asn1Type::ObjectDescriptor(o) => o.tag(),
             Asn1Type::Bool(b) => b.tag(),
             Asn1Type::Null(n) => n.tag(),
             Asn1Type::Integer(i) => i.tag(),
+            Asn1Type::Enumerated(e) => e.tag(),
+            Asn1Type::Real(r) => r.tag(),
             Asn1Type::ObjectIdentifier(o) => o.tag(),
+            Asn1Type::RelativeOid(r) => r.tag(),
             Asn1Type::ExplicitTag(e) => e.tag(),
             Asn1Type::ImplicitTag(i) => i.tag(),
             Asn1Type::ApplicationTag(a) => a.tag(),
+            Asn1Type::Unknown(u) => u.tag(),
             Asn1Type::UtcTime(u) => u.tag(),
             Asn1Type::GeneralizedTime(u) => u.tag(),
+            Asn1Type::Unparsed(u) => u.tag(),
         }
     }
 }
@@ -110,9 +270,17 @@ impl<'data> Asn1ValueDecoder<'data> for Asn1Type<'data> {
             GeneralString,
             NumericString,
             VisibleString,
+            UniversalString,
+            TeletexString,
+            VideotexString,
+            GraphicString,
+            ObjectDescriptor,
             Bool,
             Integer,
+            Enumerated,
+            Real,
             ObjectIdentifier,
+            RelativeOid,
             ExplicitTag,
             ImplicitTag,
             ApplicationTag,
@@ -122,62 +290,144 @@ impl<'data> Asn1ValueDecoder<'data> for Asn1Type<'data> {
             in tag, reader
         );
 
-        Err(Error::from("Invalid asn1 data"))
+        // the tag is not supported by any specific type, so the entity is kept as is
+        Ok(Asn1Type::Unknown(Unknown::decode(tag, reader)?))
     }
 
-    fn compare_tags(_tag: Tag) -> bool {
-        true
+    fn compare_tags(tag: Tag) -> bool {
+        Self::is_supported_tag(tag) || Unknown::compare_tags(tag)
     }
 }
 
-impl Asn1Encoder for Asn1Type<'_> {
-    fn needed_buf_size(&self) -> usize {
+impl Asn1Type<'_> {
+    /// Checks if the tag is decoded by any specific asn1 type rather than [Unknown]
+    pub(crate) fn is_supported_tag(tag: Tag) -> bool {
+        OctetString::compare_tags(tag)
+            || Utf8String::compare_tags(tag)
+            || Sequence::compare_tags(tag)
+            || Set::compare_tags(tag)
+            || BitString::compare_tags(tag)
+            || BmpString::compare_tags(tag)
+            || IA5String::compare_tags(tag)
+            || PrintableString::compare_tags(tag)
+            || GeneralString::compare_tags(tag)
+            || NumericString::compare_tags(tag)
+            || VisibleString::compare_tags(tag)
+            || UniversalString::compare_tags(tag)
+            || TeletexString::compare_tags(tag)
+            || VideotexString::compare_tags(tag)
+            || GraphicString::compare_tags(tag)
+            || ObjectDescriptor::compare_tags(tag)
+            || Bool::compare_tags(tag)
+            || Integer::compare_tags(tag)
+            || Enumerated::compare_tags(tag)
+            || Real::compare_tags(tag)
+            || ObjectIdentifier::compare_tags(tag)
+            || RelativeOid::compare_tags(tag)
+            || ExplicitTag::compare_tags(tag)
+            || ImplicitTag::compare_tags(tag)
+            || ApplicationTag::compare_tags(tag)
+            || Null::compare_tags(tag)
+            || UtcTime::compare_tags(tag)
+            || GeneralizedTime::compare_tags(tag)
+    }
+}
+
+impl Asn1ValueEncoder for Asn1Type<'_> {
+    fn value_len(&self) -> usize {
+        match self {
+            Asn1Type::OctetString(octet) => octet.value_len(),
+            Asn1Type::Utf8String(utf8) => utf8.value_len(),
+            Asn1Type::Sequence(sequence) => sequence.value_len(),
+            Asn1Type::Set(set) => set.value_len(),
+            Asn1Type::BitString(bit) => bit.value_len(),
+            Asn1Type::BmpString(bmp) => bmp.value_len(),
+            Asn1Type::IA5String(i) => i.value_len(),
+            Asn1Type::PrintableString(p) => p.value_len(),
+            Asn1Type::GeneralString(g) => g.value_len(),
+            Asn1Type::NumericString(g) => g.value_len(),
+            Asn1Type::VisibleString(g) => g.value_len(),
+            Asn1Type::UniversalString(u) => u.value_len(),
+            Asn1Type::TeletexString(t) => t.value_len(),
+            Asn1Type::VideotexString(v) => v.value_len(),
+            Asn1Type::GraphicString(g) => g.value_len(),
+            Asn1Type::ObjectDescriptor(o) => o.value_len(),
+            Asn1Type::Bool(boolean) => boolean.value_len(),
+            Asn1Type::Integer(integer) => integer.value_len(),
+            Asn1Type::Enumerated(enumerated) => enumerated.value_len(),
+            Asn1Type::Real(real) => real.value_len(),
+            Asn1Type::ObjectIdentifier(object_identifier) => object_identifier.value_len(),
+            Asn1Type::RelativeOid(relative_oid) => relative_oid.value_len(),
+            Asn1Type::ExplicitTag(e) => e.value_len(),
+            Asn1Type::ImplicitTag(i) => i.value_len(),
+            Asn1Type::ApplicationTag(a) => a.value_len(),
+            Asn1Type::Unknown(u) => u.value_len(),
+            Asn1Type::Null(n) => n.value_len(),
+            Asn1Type::UtcTime(u) => u.value_len(),
+            Asn1Type::GeneralizedTime(u) => u.value_len(),
+            Asn1Type::Unparsed(u) => u.value_len(),
+        }
+    }
+
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        match self {
+            Asn1Type::OctetString(octet) => octet.encode_value(writer),
+            Asn1Type::Utf8String(utf8) => utf8.encode_value(writer),
+            Asn1Type::Sequence(sequence) => sequence.encode_value(writer),
+            Asn1Type::Set(set) => set.encode_value(writer),
+            Asn1Type::BitString(bit) => bit.encode_value(writer),
+            Asn1Type::BmpString(bmp) => bmp.encode_value(writer),
+            Asn1Type::IA5String(ia5) => ia5.encode_value(writer),
+            Asn1Type::PrintableString(printable) => printable.encode_value(writer),
+            Asn1Type::GeneralString(general) => general.encode_value(writer),
+            Asn1Type::NumericString(numeric) => numeric.encode_value(writer),
+            Asn1Type::VisibleString(numeric) => numeric.encode_value(writer),
+            Asn1Type::UniversalString(universal) => universal.encode_value(writer),
+            Asn1Type::TeletexString(teletex) => teletex.encode_value(writer),
+            Asn1Type::VideotexString(videotex) => videotex.encode_value(writer),
+            Asn1Type::GraphicString(graphic) => graphic.encode_value(writer),
+            Asn1Type::ObjectDescriptor(descriptor) => descriptor.encode_value(writer),
+            Asn1Type::Bool(boolean) => boolean.encode_value(writer),
+            Asn1Type::Integer(integer) => integer.encode_value(writer),
+            Asn1Type::Enumerated(enumerated) => enumerated.encode_value(writer),
+            Asn1Type::Real(real) => real.encode_value(writer),
+            Asn1Type::ObjectIdentifier(object_identifier) => object_identifier.encode_value(writer),
+            Asn1Type::RelativeOid(relative_oid) => relative_oid.encode_value(writer),
+            Asn1Type::ExplicitTag(e) => e.encode_value(writer),
+            Asn1Type::ImplicitTag(i) => i.encode_value(writer),
+            Asn1Type::ApplicationTag(a) => a.encode_value(writer),
+            Asn1Type::Unknown(unknown) => unknown.encode_value(writer),
+            Asn1Type::Null(n) => n.encode_value(writer),
+            Asn1Type::UtcTime(utc_time) => utc_time.encode_value(writer),
+            Asn1Type::GeneralizedTime(generalized_time) => generalized_time.encode_value(writer),
+            Asn1Type::Unparsed(unparsed) => unparsed.encode_value(writer),
+        }
+    }
+
+    fn header_len_for(&self, value_len: usize) -> usize {
+        match self {
+            Asn1Type::Unparsed(unparsed) => unparsed.header_len_for(value_len),
+            _ => tag_size(self.tag()) + len_size(value_len),
+        }
+    }
+
+    fn encode_header(&self, writer: &mut Writer) -> Asn1Result<()> {
         match self {
-            Asn1Type::OctetString(octet) => octet.needed_buf_size(),
-            Asn1Type::Utf8String(utf8) => utf8.needed_buf_size(),
-            Asn1Type::Sequence(sequence) => sequence.needed_buf_size(),
-            Asn1Type::Set(set) => set.needed_buf_size(),
-            Asn1Type::BitString(bit) => bit.needed_buf_size(),
-            Asn1Type::BmpString(bmp) => bmp.needed_buf_size(),
-            Asn1Type::IA5String(i) => i.needed_buf_size(),
-            Asn1Type::PrintableString(p) => p.needed_buf_size(),
-            Asn1Type::GeneralString(g) => g.needed_buf_size(),
-            Asn1Type::NumericString(g) => g.needed_buf_size(),
-            Asn1Type::VisibleString(g) => g.needed_buf_size(),
-            Asn1Type::Bool(boolean) => boolean.needed_buf_size(),
-            Asn1Type::Integer(integer) => integer.needed_buf_size(),
-            Asn1Type::ObjectIdentifier(object_identifier) => object_identifier.needed_buf_size(),
-            Asn1Type::ExplicitTag(e) => e.needed_buf_size(),
-            Asn1Type::ImplicitTag(i) => i.needed_buf_size(),
-            Asn1Type::ApplicationTag(a) => a.needed_buf_size(),
-            Asn1Type::Null(n) => n.needed_buf_size(),
-            Asn1Type::UtcTime(u) => u.needed_buf_size(),
-            Asn1Type::GeneralizedTime(u) => u.needed_buf_size(),
+            Asn1Type::Unparsed(unparsed) => unparsed.encode_header(writer),
+            _ => {
+                write_tag(self.tag(), writer)?;
+                write_len(self.value_len(), writer)
+            }
         }
     }
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
+    fn encode_growable(&self, writer: &mut Writer) -> Asn1Result<()> {
         match self {
-            Asn1Type::OctetString(octet) => octet.encode(writer),
-            Asn1Type::Utf8String(utf8) => utf8.encode(writer),
-            Asn1Type::Sequence(sequence) => sequence.encode(writer),
-            Asn1Type::Set(set) => set.encode(writer),
-            Asn1Type::BitString(bit) => bit.encode(writer),
-            Asn1Type::BmpString(bmp) => bmp.encode(writer),
-            Asn1Type::IA5String(ia5) => ia5.encode(writer),
-            Asn1Type::PrintableString(printable) => printable.encode(writer),
-            Asn1Type::GeneralString(general) => general.encode(writer),
-            Asn1Type::NumericString(numeric) => numeric.encode(writer),
-            Asn1Type::VisibleString(numeric) => numeric.encode(writer),
-            Asn1Type::Bool(boolean) => boolean.encode(writer),
-            Asn1Type::Integer(integer) => integer.encode(writer),
-            Asn1Type::ObjectIdentifier(object_identifier) => object_identifier.encode(writer),
-            Asn1Type::ExplicitTag(e) => e.encode(writer),
-            Asn1Type::ImplicitTag(i) => i.encode(writer),
-            Asn1Type::ApplicationTag(a) => a.encode(writer),
-            Asn1Type::Null(n) => n.encode(writer),
-            Asn1Type::UtcTime(utc_time) => utc_time.encode(writer),
-            Asn1Type::GeneralizedTime(generalized_time) => generalized_time.encode(writer),
+            Asn1Type::Unparsed(unparsed) => unparsed.encode_growable(writer),
+            _ => {
+                write_tag(self.tag(), writer)?;
+                write_len_and_value(writer, || self.value_len(), |writer| self.encode_value(writer))
+            }
         }
     }
 }
@@ -189,22 +439,32 @@ impl MetaInfo for Asn1Type<'_> {
             Asn1Type::Utf8String(_) => {}
             Asn1Type::Sequence(sequence) => sequence.clear_meta(),
             Asn1Type::Set(set) => set.clear_meta(),
-            Asn1Type::BitString(_) => {}
+            Asn1Type::BitString(bit_string) => bit_string.clear_meta(),
             Asn1Type::BmpString(_) => {}
             Asn1Type::IA5String(_) => {}
             Asn1Type::PrintableString(_) => {}
             Asn1Type::GeneralString(_) => {}
             Asn1Type::NumericString(_) => {}
             Asn1Type::VisibleString(_) => {}
+            Asn1Type::UniversalString(_) => {}
+            Asn1Type::TeletexString(_) => {}
+            Asn1Type::VideotexString(_) => {}
+            Asn1Type::GraphicString(_) => {}
+            Asn1Type::ObjectDescriptor(_) => {}
             Asn1Type::Bool(_) => {}
             Asn1Type::Integer(_) => {}
+            Asn1Type::Enumerated(_) => {}
+            Asn1Type::Real(_) => {}
             Asn1Type::ObjectIdentifier(_) => {}
+            Asn1Type::RelativeOid(_) => {}
             Asn1Type::ExplicitTag(explicit_tag) => explicit_tag.clear_meta(),
             Asn1Type::ImplicitTag(implicit_tag) => implicit_tag.clear_meta(),
             Asn1Type::ApplicationTag(application_tag) => application_tag.clear_meta(),
+            Asn1Type::Unknown(unknown) => unknown.clear_meta(),
             Asn1Type::Null(_) => {}
             Asn1Type::UtcTime(_) => {}
-            Asn1Type::GeneralizedTime(_) => {}
+            Asn1Type::GeneralizedTime(generalized_time) => generalized_time.clear_meta(),
+            Asn1Type::Unparsed(_) => {}
         }
     }
 }
@@ -213,7 +473,7 @@ impl MetaInfo for Asn1Type<'_> {
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct RawAsn1EntityData<'data> {
     /// Raw input bytes for the *current* asn1 node
-    pub raw_data: Cow<'data, [u8]>,
+    pub raw_data: RawBytes<'data>,
 
     /// Position of the tag in the input data
     pub tag: usize,
@@ -244,6 +504,27 @@ impl RawAsn1EntityData<'_> {
         self.raw_data.as_ref()
     }
 
+    /// Returns `true` if the length of the asn1 entity is encoded using the indefinite form.
+    ///
+    /// In such a case, the `raw_data` ends with the end-of-contents octets that are not included in the `data` range.
+    pub fn is_indefinite_length(&self) -> bool {
+        self.length_bytes() == [INDEFINITE_LENGTH]
+    }
+
+    /// Returns the amount of the subsequent length octets if the length is encoded using the non-minimal long form.
+    ///
+    /// Such length form is kept on re-encoding, so the decoded data is reproduced exactly.
+    pub fn non_minimal_length_octets(&self) -> Option<usize> {
+        match self.length_bytes() {
+            length @ [_, _, ..] if !is_minimal_length(length) => Some(length.len() - 1),
+            _ => None,
+        }
+    }
+
+    pub fn tag_bytes(&self) -> &[u8] {
+        &self.raw_data[0..self.length.start]
+    }
+
     pub fn length_bytes(&self) -> &[u8] {
         &self.raw_data[self.length.clone()]
     }
@@ -252,9 +533,14 @@ impl RawAsn1EntityData<'_> {
         &self.raw_data[self.data.clone()]
     }
 
+    /// Returns end-of-contents octets. The slice is empty if the definite length form is used.
+    pub fn end_of_contents_bytes(&self) -> &[u8] {
+        &self.raw_data[self.data.end..]
+    }
+
     pub fn to_owned(&self) -> OwnedRawAsn1EntityData {
         RawAsn1EntityData {
-            raw_data: self.raw_data.to_vec().into(),
+            raw_data: self.raw_data.to_owned(),
             tag: self.tag,
             length: self.length.clone(),
             data: self.data.clone(),
@@ -0,0 +1,259 @@
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{
+    ApplicationTag, Asn1Result, Asn1Type, Asn1ValueEncoder, Bool, Error, ErrorKind, ExplicitTag, GeneralizedTime,
+    ImplicitTag, Null, ObjectIdentifier, OwnedApplicationTag, OwnedAsn1, OwnedAsn1Type, OwnedBitString, OwnedBmpString,
+    OwnedEnumerated, OwnedExplicitTag, OwnedGeneralString, OwnedGraphicString, OwnedIA5String, OwnedImplicitTag,
+    OwnedInteger, OwnedNumericString, OwnedObjectDescriptor, OwnedOctetString, OwnedPrintableString, OwnedReal,
+    OwnedSequence, OwnedSet, OwnedTeletexString, OwnedUniversalString, OwnedUnknown, OwnedUnparsed, OwnedUtf8String,
+    OwnedVideotexString, OwnedVisibleString, RelativeOid, Sequence, Set, Tag, TagClass, Taggable, Unknown, UtcTime,
+    Writer,
+};
+
+/// Builder for asn1 trees constructed from scratch.
+///
+/// Every asn1 value converts into the builder, so it can be pushed into SEQUENCE, SET, and tagged nodes directly.
+/// Node ids are assigned when the tree is built.
+///
+/// ```
+/// use asn1_parser::{Asn1Builder, Asn1Encoder, Bool, ObjectIdentifier, OwnedInteger};
+///
+/// let asn1 = Asn1Builder::sequence()
+///     .push(OwnedInteger::from(5))?
+///     .push(ObjectIdentifier::try_from("1.2.840.113549.1.1.11")?)?
+///     .push(Asn1Builder::from(Bool::from(true)).explicit(0))?
+///     .build();
+///
+/// assert_eq!(asn1.needed_buf_size(), 21);
+/// # Ok::<(), asn1_parser::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asn1Builder(Node);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Value(OwnedAsn1Type),
+    Sequence(Vec<Asn1Builder>),
+    Set(Vec<Asn1Builder>),
+    ExplicitTag(u32, Vec<Asn1Builder>),
+    ApplicationTag(u32, Vec<Asn1Builder>),
+    ImplicitTag(u32, Box<Asn1Builder>),
+}
+
+impl Asn1Builder {
+    /// Creates an empty SEQUENCE
+    pub fn sequence() -> Self {
+        Self(Node::Sequence(Vec::new()))
+    }
+
+    /// Creates an empty SET
+    pub fn set() -> Self {
+        Self(Node::Set(Vec::new()))
+    }
+
+    /// Creates an empty context-specific constructed node
+    pub fn explicit_tag(tag_number: u32) -> Self {
+        Self(Node::ExplicitTag(tag_number, Vec::new()))
+    }
+
+    /// Creates an empty application constructed node
+    pub fn application_tag(tag_number: u32) -> Self {
+        Self(Node::ApplicationTag(tag_number, Vec::new()))
+    }
+
+    /// Creates the time node from the timestamp: UTCTime for the years from 1950 to 2049 and GeneralizedTime
+    /// otherwise, as RFC 5280 requires for the certificate validity. The timestamp is normalised to UTC.
+    #[cfg(feature = "time")]
+    pub fn time(timestamp: ::time::OffsetDateTime) -> Asn1Result<Self> {
+        Ok(
+            if (1950..2050).contains(&timestamp.to_offset(::time::UtcOffset::UTC).year()) {
+                UtcTime::try_from(timestamp)?.into()
+            } else {
+                GeneralizedTime::try_from(timestamp)?.into()
+            },
+        )
+    }
+
+    /// Appends the field to the constructed node.
+    ///
+    /// Fails if the node is not created by [Asn1Builder::sequence], [Asn1Builder::set],
+    /// [Asn1Builder::explicit_tag], or [Asn1Builder::application_tag].
+    pub fn push(mut self, field: impl Into<Asn1Builder>) -> Asn1Result<Self> {
+        match &mut self.0 {
+            Node::Sequence(fields)
+            | Node::Set(fields)
+            | Node::ExplicitTag(_, fields)
+            | Node::ApplicationTag(_, fields) => fields.push(field.into()),
+            Node::Value(_) | Node::ImplicitTag(..) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidValue,
+                    "Fields can be pushed only into the constructed node",
+                ))
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Appends all fields to the constructed node. It fails in the same cases as [Asn1Builder::push].
+    pub fn extend<F: Into<Asn1Builder>>(self, fields: impl IntoIterator<Item = F>) -> Asn1Result<Self> {
+        fields.into_iter().try_fold(self, |builder, field| builder.push(field))
+    }
+
+    /// Wraps the node into the EXPLICIT context-specific tag: `[tag_number] EXPLICIT`
+    pub fn explicit(self, tag_number: u32) -> Self {
+        Self(Node::ExplicitTag(tag_number, vec![self]))
+    }
+
+    /// Replaces the node tag with the context-specific one: `[tag_number] IMPLICIT`.
+    ///
+    /// The constructed bit of the original tag is preserved. The node is built in the same way as it is decoded:
+    /// constructed nodes become [ExplicitTag] nodes, and primitive ones become [ImplicitTag] nodes.
+    pub fn implicit(self, tag_number: u32) -> Self {
+        Self(Node::ImplicitTag(tag_number, Box::new(self)))
+    }
+
+    /// Wraps the node into the EXPLICIT application tag: `[APPLICATION tag_number] EXPLICIT`
+    pub fn application(self, tag_number: u32) -> Self {
+        Self(Node::ApplicationTag(tag_number, vec![self]))
+    }
+
+    /// Builds the asn1 tree. Every node (including the nodes of the pushed values and the encapsulated entities)
+    /// gets a fresh unique id.
+    pub fn build(self) -> OwnedAsn1 {
+        let mut asn1 = self.build_node();
+        assign_ids(&mut asn1, &mut 0);
+
+        asn1
+    }
+
+    fn build_node(self) -> OwnedAsn1 {
+        let asn1 = match self.0 {
+            Node::Value(asn1) => asn1,
+            Node::Sequence(fields) => Asn1Type::Sequence(Sequence::new(build_fields(fields))),
+            Node::Set(fields) => Asn1Type::Set(Set::new(build_fields(fields))),
+            Node::ExplicitTag(tag_number, fields) => {
+                Asn1Type::ExplicitTag(ExplicitTag::new(tag_number, build_fields(fields)))
+            }
+            Node::ApplicationTag(tag_number, fields) => {
+                Asn1Type::ApplicationTag(ApplicationTag::new(tag_number, build_fields(fields)))
+            }
+            Node::ImplicitTag(tag_number, inner) => match inner.0 {
+                // context-specific constructed nodes are decoded as explicit tags, so we build them in the same way
+                Node::Sequence(fields)
+                | Node::Set(fields)
+                | Node::ExplicitTag(_, fields)
+                | Node::ApplicationTag(_, fields) => {
+                    Asn1Type::ExplicitTag(ExplicitTag::new(tag_number, build_fields(fields)))
+                }
+                inner => implicit(tag_number, Asn1Builder(inner).build_node().inner_asn1()),
+            },
+        };
+
+        OwnedAsn1::new(0, Default::default(), asn1)
+    }
+}
+
+fn build_fields(fields: Vec<Asn1Builder>) -> Vec<OwnedAsn1> {
+    fields.into_iter().map(Asn1Builder::build_node).collect()
+}
+
+fn implicit(tag_number: u32, asn1: &OwnedAsn1Type) -> OwnedAsn1Type {
+    let mut writer = Writer::growable();
+    let octets = asn1
+        .encode_value(&mut writer)
+        .and_then(|_| writer.into_vec())
+        .expect("the growable writer can not run out of space");
+
+    if !asn1.tag().is_constructed() {
+        return Asn1Type::ImplicitTag(ImplicitTag::new_owned(tag_number, octets));
+    }
+
+    let unknown = Unknown::new_owned(Tag::new(TagClass::ContextSpecific, true, tag_number), octets);
+    match unknown.fields() {
+        // context-specific constructed nodes are decoded as explicit tags, so we build them in the same way
+        Some(fields) => Asn1Type::ExplicitTag(ExplicitTag::new(tag_number, fields.to_vec())),
+        None => Asn1Type::Unknown(unknown),
+    }
+}
+
+/// Assigns sequential ids to the node and all its children in the pre-order
+fn assign_ids(asn1: &mut OwnedAsn1, next_id: &mut u64) {
+    *next_id += 1;
+    asn1.set_id(*next_id);
+
+    match asn1.inner_asn1_mut() {
+        Asn1Type::Sequence(sequence) => assign_fields_ids(sequence.fields_mut(), next_id),
+        Asn1Type::Set(set) => assign_fields_ids(set.fields_mut(), next_id),
+        Asn1Type::ExplicitTag(explicit) => assign_fields_ids(explicit.inner_mut(), next_id),
+        Asn1Type::ApplicationTag(application) => assign_fields_ids(application.inner_mut(), next_id),
+        Asn1Type::OctetString(octet_string) => assign_fields_ids(octet_string.inner_mut(), next_id),
+        Asn1Type::BitString(bit_string) => assign_fields_ids(bit_string.inner_mut(), next_id),
+        Asn1Type::ImplicitTag(implicit) => assign_fields_ids(implicit.inner_mut(), next_id),
+        Asn1Type::Unknown(unknown) => assign_fields_ids(unknown.fields_mut().into_iter().flatten(), next_id),
+        _ => {}
+    }
+}
+
+fn assign_fields_ids<'a>(fields: impl IntoIterator<Item = &'a mut OwnedAsn1>, next_id: &mut u64) {
+    fields.into_iter().for_each(|field| assign_ids(field, next_id));
+}
+
+impl From<OwnedAsn1Type> for Asn1Builder {
+    fn from(asn1: OwnedAsn1Type) -> Self {
+        Self(Node::Value(asn1))
+    }
+}
+
+impl From<OwnedAsn1> for Asn1Builder {
+    fn from(asn1: OwnedAsn1) -> Self {
+        Self(Node::Value(asn1.into_inner_asn1()))
+    }
+}
+
+macro_rules! impl_from_asn1_value {
+    ($($variant:ident($ty:ty)),* $(,)?) => {
+        $(
+            impl From<$ty> for Asn1Builder {
+                fn from(value: $ty) -> Self {
+                    Self(Node::Value(Asn1Type::$variant(value)))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_asn1_value!(
+    Sequence(OwnedSequence),
+    Set(OwnedSet),
+    OctetString(OwnedOctetString),
+    Utf8String(OwnedUtf8String),
+    BitString(OwnedBitString),
+    BmpString(OwnedBmpString),
+    IA5String(OwnedIA5String),
+    PrintableString(OwnedPrintableString),
+    GeneralString(OwnedGeneralString),
+    NumericString(OwnedNumericString),
+    VisibleString(OwnedVisibleString),
+    UniversalString(OwnedUniversalString),
+    TeletexString(OwnedTeletexString),
+    VideotexString(OwnedVideotexString),
+    GraphicString(OwnedGraphicString),
+    ObjectDescriptor(OwnedObjectDescriptor),
+    UtcTime(UtcTime),
+    GeneralizedTime(GeneralizedTime),
+    Bool(Bool),
+    Null(Null),
+    Integer(OwnedInteger),
+    Enumerated(OwnedEnumerated),
+    Real(OwnedReal),
+    ObjectIdentifier(ObjectIdentifier),
+    RelativeOid(RelativeOid),
+    ExplicitTag(OwnedExplicitTag),
+    ImplicitTag(OwnedImplicitTag),
+    ApplicationTag(OwnedApplicationTag),
+    Unknown(OwnedUnknown),
+    Unparsed(OwnedUnparsed),
+);
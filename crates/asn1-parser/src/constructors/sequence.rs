@@ -1,10 +1,10 @@
 use alloc::vec::Vec;
 
 use crate::asn1::Asn1;
-use crate::length::{len_size, write_len};
 use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawDataStorage};
 use crate::writer::Writer;
-use crate::{Asn1Decoder, Asn1Encoder, Asn1Result, Asn1ValueDecoder, MetaInfo, Tag, Taggable};
+use crate::{Asn1Encoder, Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, MetaInfo, Tag, Taggable};
 
 /// [ASN.1 SEQUENCE](https://www.oss.com/asn1/resources/asn1-made-simple/asn1-quick-reference/sequence.html)
 ///
@@ -14,9 +14,9 @@ pub struct Sequence<'data>(Vec<Asn1<'data>>);
 
 pub type OwnedSequence = Sequence<'static>;
 
-impl Sequence<'_> {
+impl<'data> Sequence<'data> {
     /// Tag value of the [SEQUENCE] type
-    pub const TAG: Tag = Tag(0x30);
+    pub const TAG: Tag = Tag::from_byte(0x30);
 
     /// Creates a new [Sequence] from passed fields
     pub fn new(fields: Vec<Asn1>) -> Sequence {
@@ -28,12 +28,21 @@ impl Sequence<'_> {
         &self.0
     }
 
+    /// Returns mutable [Sequence] fields
+    pub fn fields_mut(&mut self) -> &mut [Asn1<'data>] {
+        &mut self.0
+    }
+
     /// Returns owned version of the [Sequence]
     pub fn to_owned(&self) -> OwnedSequence {
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedSequence {
         Sequence(
             self.0
                 .iter()
-                .map(|f| f.to_owned_with_asn1(f.inner_asn1().to_owned()))
+                .map(|f| f.to_owned_in(storage, f.inner_asn1().to_owned_in(storage)))
                 .collect(),
         )
     }
@@ -51,19 +60,12 @@ impl Taggable for Sequence<'_> {
     }
 }
 
-impl Asn1Encoder for Sequence<'_> {
-    fn needed_buf_size(&self) -> usize {
-        let data_len = self.0.iter().map(|f| f.needed_buf_size()).sum();
-
-        1 /* tag */ + len_size(data_len) + data_len
+impl Asn1ValueEncoder for Sequence<'_> {
+    fn value_len(&self) -> usize {
+        self.0.iter().map(|f| f.needed_buf_size()).sum()
     }
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        writer.write_byte(Self::TAG.into())?;
-
-        let data_len = self.0.iter().map(|f| f.needed_buf_size()).sum();
-        write_len(data_len, writer)?;
-
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
         self.0.iter().try_for_each(|f| f.encode(writer))
     }
 }
@@ -73,7 +75,7 @@ impl<'data> Asn1ValueDecoder<'data> for Sequence<'data> {
         let mut fields = Vec::new();
 
         while !reader.empty() {
-            fields.push(Asn1::decode(reader)?);
+            fields.push(Asn1::decode_node(reader)?);
         }
 
         Ok(Self(fields))
@@ -82,6 +84,10 @@ impl<'data> Asn1ValueDecoder<'data> for Sequence<'data> {
     fn compare_tags(tag: Tag) -> bool {
         Self::TAG == tag
     }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
+    }
 }
 
 impl MetaInfo for Sequence<'_> {
@@ -1,10 +1,10 @@
 use alloc::vec::Vec;
 
 use crate::asn1::Asn1;
-use crate::length::write_len;
 use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawDataStorage};
 use crate::writer::Writer;
-use crate::{Asn1Encoder, Asn1Result, Asn1ValueDecoder, MetaInfo, Sequence, Tag, Taggable};
+use crate::{Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, MetaInfo, Sequence, Tag, Taggable};
 
 /// [ASN.1 SET](https://www.oss.com/asn1/resources/asn1-made-simple/asn1-quick-reference/set.html)
 ///
@@ -16,9 +16,9 @@ pub struct Set<'data>(Sequence<'data>);
 
 pub type OwnedSet = Set<'static>;
 
-impl Set<'_> {
+impl<'data> Set<'data> {
     /// Tag value of the [SET] type
-    pub const TAG: Tag = Tag(0x31);
+    pub const TAG: Tag = Tag::from_byte(0x31);
 
     /// Creates a new [Set] from passed fields
     pub fn new(fields: Vec<Asn1>) -> Set {
@@ -30,13 +30,22 @@ impl Set<'_> {
         self.0.fields()
     }
 
+    /// Returns mutable [Set] fields
+    pub fn fields_mut(&mut self) -> &mut [Asn1<'data>] {
+        self.0.fields_mut()
+    }
+
     /// Returns owned version of the [Set]
     pub fn to_owned(&self) -> OwnedSet {
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedSet {
         Set(Sequence::from(
             self.0
                 .fields()
                 .iter()
-                .map(|f| f.to_owned_with_asn1(f.inner_asn1().to_owned()))
+                .map(|f| f.to_owned_in(storage, f.inner_asn1().to_owned_in(storage)))
                 .collect::<Vec<_>>(),
         ))
     }
@@ -54,18 +63,13 @@ impl Taggable for Set<'_> {
     }
 }
 
-impl Asn1Encoder for Set<'_> {
-    fn needed_buf_size(&self) -> usize {
-        self.0.needed_buf_size()
+impl Asn1ValueEncoder for Set<'_> {
+    fn value_len(&self) -> usize {
+        self.0.value_len()
     }
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        writer.write_byte(Self::TAG.into())?;
-
-        let data_len = self.0.fields().iter().map(|f| f.needed_buf_size()).sum();
-        write_len(data_len, writer)?;
-
-        self.0.fields().iter().try_for_each(|f| f.encode(writer))
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        self.0.encode_value(writer)
     }
 }
 
@@ -77,6 +81,10 @@ impl<'data> Asn1ValueDecoder<'data> for Set<'data> {
     fn compare_tags(tag: Tag) -> bool {
         Self::TAG == tag
     }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
+    }
 }
 
 impl MetaInfo for Set<'_> {
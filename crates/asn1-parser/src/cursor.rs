@@ -0,0 +1,176 @@
+//! Lazy decoding of the asn1 entities.
+
+use core::ops::Range;
+
+use crate::length::{indefinite_len, read_len, Length, END_OF_CONTENTS};
+use crate::reader::{read_data, Reader};
+use crate::tag::read_tag;
+use crate::{Asn1Decoder, Asn1Result, Error, ErrorKind, Tag};
+
+/// Lazy zero-copy reader of the encoded asn1 entities.
+///
+/// Unlike [Asn1Decoder], the cursor does not build the tree. It reads only the tag and length octets of the next
+/// entity and skips its contents octets, so large blobs can be inspected without decoding or copying them.
+/// Nested entities are read on demand using the [Asn1Header::children] cursor.
+#[derive(Debug, Clone)]
+pub struct Asn1Cursor<'data> {
+    data: &'data [u8],
+    position: usize,
+    // position of the cursor data in the input buffer
+    offset: usize,
+}
+
+impl<'data> Asn1Cursor<'data> {
+    pub fn new(data: &'data [u8]) -> Self {
+        Self {
+            data,
+            position: 0,
+            offset: 0,
+        }
+    }
+
+    /// Returns the position of the next entity in the input buffer
+    pub fn offset(&self) -> usize {
+        self.offset + self.position
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position == self.data.len()
+    }
+
+    /// Returns the data that is not read yet
+    pub fn remaining(&self) -> &'data [u8] {
+        &self.data[self.position..]
+    }
+
+    /// Reads the header of the next entity and moves the cursor after its contents octets.
+    ///
+    /// Returns `None` if there is no more data. The cursor is not moved if the header can not be read.
+    pub fn next_header(&mut self) -> Asn1Result<Option<Asn1Header<'data>>> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+
+        let mut reader = Reader::new(self.remaining());
+        reader.set_offset(self.offset());
+
+        let tag = read_tag(&mut reader)?;
+
+        let length_offset = reader.full_offset();
+        let (length, length_range) = read_len(&mut reader)?;
+
+        let (_, data) = match length {
+            Length::Definite(len) => read_data(&mut reader, len)?,
+            Length::Indefinite => {
+                if tag.is_primitive() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidLength,
+                        "Indefinite length is not allowed for primitive types",
+                    )
+                    .with_offset(length_offset));
+                }
+
+                let len = indefinite_len(&reader)?;
+                let data = read_data(&mut reader, len)?;
+                // `indefinite_len` stops right before the end-of-contents octets
+                reader.read(END_OF_CONTENTS.len())?;
+
+                data
+            }
+        };
+
+        let header = Asn1Header {
+            tag,
+            length,
+            tag_position: self.offset(),
+            raw_data: &self.remaining()[0..reader.position()],
+            length_range,
+            data,
+        };
+        self.position += reader.position();
+
+        Ok(Some(header))
+    }
+}
+
+impl<'data> Iterator for Asn1Cursor<'data> {
+    type Item = Asn1Result<Asn1Header<'data>>;
+
+    /// Returns the next entity header. The iteration stops after the first error
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_header() {
+            Ok(header) => header.map(Ok),
+            Err(error) => {
+                self.position = self.data.len();
+
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+/// Tag and length of the asn1 entity read by the [Asn1Cursor].
+///
+/// All data is borrowed from the input buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asn1Header<'data> {
+    tag: Tag,
+    length: Length,
+    tag_position: usize,
+    raw_data: &'data [u8],
+    length_range: Range<usize>,
+    data: Range<usize>,
+}
+
+impl<'data> Asn1Header<'data> {
+    pub fn tag(&self) -> Tag {
+        self.tag
+    }
+
+    pub fn length(&self) -> Length {
+        self.length
+    }
+
+    /// Position of the tag in the input data
+    pub fn tag_position(&self) -> usize {
+        self.tag_position
+    }
+
+    /// Returns the amount of the tag and length octets
+    pub fn header_len(&self) -> usize {
+        self.data.start
+    }
+
+    /// Returns tag, length, contents, and end-of-contents octets of the entity
+    pub fn raw_bytes(&self) -> &'data [u8] {
+        self.raw_data
+    }
+
+    pub fn length_bytes(&self) -> &'data [u8] {
+        &self.raw_data[self.length_range.clone()]
+    }
+
+    pub fn data_bytes(&self) -> &'data [u8] {
+        &self.raw_data[self.data.clone()]
+    }
+
+    /// Returns the cursor over the contents octets.
+    ///
+    /// Contents octets of the constructed entities are the encoded nested entities. The cursor can also be used for
+    /// the asn1 entities encapsulated into the primitive ones, e.g. OCTET STRING.
+    pub fn children(&self) -> Asn1Cursor<'data> {
+        Asn1Cursor {
+            data: self.data_bytes(),
+            position: 0,
+            offset: self.tag_position + self.data.start,
+        }
+    }
+
+    /// Decodes the entity. Offsets of the decoded nodes are positions in the input data of the cursor
+    pub fn decode<T: Asn1Decoder<'data>>(&self) -> Asn1Result<T> {
+        let mut reader = Reader::new(self.raw_data);
+        reader.set_offset(self.tag_position);
+
+        T::decode(&mut reader)
+    }
+}
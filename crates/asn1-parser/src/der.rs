@@ -0,0 +1,316 @@
+//! [DER](https://www.itu.int/rec/T-REC-X.690) conformance checking.
+//!
+//! The parser accepts BER encodings. The checker walks the decoded tree and reports everything that is not allowed in DER.
+//! The tree can also be rewritten to satisfy DER using [canonicalize_der].
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+
+use crate::length::is_minimal_length;
+use crate::primitives::minimal_signed_bytes;
+use crate::reader::Reader;
+use crate::{
+    Asn1, Asn1Encoder, Asn1Entity, Asn1Type, Asn1ValueDecoder, Asn1Visitor, Asn1VisitorMut, BitString, Bool, Integer,
+    MetaInfo, OctetString, RawAsn1EntityData, Set, Tag, TagClass, Taggable, Unknown, VisitContext, VisitFlow,
+};
+
+/// Kind of the DER rule violation
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DerViolationKind {
+    /// Length is encoded using the indefinite form
+    IndefiniteLength,
+    /// Length is not encoded using the minimal number of octets
+    NonMinimalLength,
+    /// BOOLEAN value is not 0x00 or 0xFF
+    InvalidBoolean,
+    /// INTEGER value contains redundant leading 0x00 or 0xFF octet
+    NonMinimalInteger,
+    /// SET elements are not sorted by tag, or SET OF elements are not sorted by encoding
+    UnsortedSet,
+    /// String is encoded using the constructed form
+    ConstructedString,
+    /// BIT STRING unused (padding) bits are not zero
+    NonZeroPaddingBits,
+    /// UTCTime or GeneralizedTime value does not end with 'Z'
+    NonUtcTime,
+    /// GeneralizedTime fraction of a second has trailing zeros
+    TrailingZeroFraction,
+}
+
+impl fmt::Display for DerViolationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DerViolationKind::IndefiniteLength => "indefinite length",
+            DerViolationKind::NonMinimalLength => "non-minimal length encoding",
+            DerViolationKind::InvalidBoolean => "BOOLEAN value is not 0x00 or 0xFF",
+            DerViolationKind::NonMinimalInteger => "INTEGER has redundant leading octets",
+            DerViolationKind::UnsortedSet => "SET elements are not sorted",
+            DerViolationKind::ConstructedString => "constructed string encoding",
+            DerViolationKind::NonZeroPaddingBits => "BIT STRING padding bits are not zero",
+            DerViolationKind::NonUtcTime => "time value does not end with 'Z'",
+            DerViolationKind::TrailingZeroFraction => "time fraction has trailing zeros",
+        })
+    }
+}
+
+/// DER rule violation found in the asn1 tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerViolation {
+    pub kind: DerViolationKind,
+    /// Id of the violating node
+    pub node_id: u64,
+    /// Absolute position of the violating node in the input data
+    pub offset: usize,
+}
+
+/// Checks the decoded asn1 tree against DER rules and returns all found violations.
+///
+/// The check relies on the raw asn1 entities data, so the tree should be decoded from the buffer.
+pub fn check_der(asn1: &Asn1<'_>) -> Vec<DerViolation> {
+    let mut checker = DerChecker::default();
+    checker.visit(asn1);
+
+    checker.violations
+}
+
+#[derive(Default)]
+struct DerChecker {
+    violations: Vec<DerViolation>,
+}
+
+impl<'a> Asn1Visitor<'a> for DerChecker {
+    fn enter_node(&mut self, node: &'a Asn1<'a>, _context: &VisitContext<'_>) -> VisitFlow {
+        check_node(node, &mut self.violations);
+
+        VisitFlow::Continue
+    }
+}
+
+/// Rewrites the asn1 tree in place, so it is encoded according to DER rules.
+///
+/// All lengths are re-encoded in the minimal definite form, BOOLEAN and INTEGER values are normalized, constructed
+/// strings of every string type are merged into the primitive form, BIT STRING padding bits are cleared, SET elements are sorted, and GeneralizedTime fractions
+/// lose their trailing zeros. Fields of unknown constructed tags are canonicalized too, and their content octets are
+/// re-encoded from them. Time values with the local time difference are left as is because converting
+/// them to UTC changes the value. The encapsulated entities of OCTET STRING and BIT STRING are kept as is too.
+///
+/// Meta info of the tree is cleared, so node ids and raw entity data are reset.
+pub fn canonicalize_der(asn1: &mut Asn1<'_>) {
+    asn1.clear_meta();
+    DerCanonicalizer.visit_mut(asn1);
+}
+
+struct DerCanonicalizer;
+
+impl Asn1VisitorMut for DerCanonicalizer {
+    fn leave_bool(&mut self, boolean: &mut Bool, _context: &VisitContext<'_>) {
+        if !matches!(boolean.as_byte(), 0x00 | 0xff) {
+            *boolean = Bool::from(boolean.value());
+        }
+    }
+
+    fn leave_integer(&mut self, integer: &mut Integer<'_>, _context: &VisitContext<'_>) {
+        let minimal = minimal_signed_bytes(integer.raw_data());
+
+        if minimal.len() != integer.raw_data().len() {
+            *integer = Integer::from(minimal.to_vec());
+        }
+    }
+
+    fn leave_octet_string(&mut self, octet_string: &mut OctetString<'_>, _context: &VisitContext<'_>) {
+        if octet_string.segments().is_some() {
+            *octet_string = OctetString::new_owned(octet_string.octets().to_vec());
+        }
+    }
+
+    fn leave_bit_string(&mut self, bit_string: &mut BitString<'_>, _context: &VisitContext<'_>) {
+        if bit_string.segments().is_none() && has_zero_padding_bits(bit_string.raw_bits()) {
+            return;
+        }
+
+        if let [unused_bits, bits @ ..] = bit_string.raw_bits() {
+            let mut bits = bits.to_vec();
+            if let Some(last) = bits.last_mut() {
+                *last &= 0xff_u8.checked_shl(u32::from(*unused_bits)).unwrap_or(0);
+            }
+
+            // the amount of unused bits greater than 7 can not be represented in DER
+            let canonical = (bits.len() * 8)
+                .checked_sub(usize::from(*unused_bits))
+                .and_then(|bits_amount| BitString::from_raw_vec(bits_amount, bits).ok());
+            if let Some(canonical) = canonical {
+                *bit_string = canonical;
+            }
+        }
+    }
+
+    fn leave_unknown(&mut self, unknown: &mut Unknown<'_>, _context: &VisitContext<'_>) {
+        // the entity is encoded from the fields anyway, so the error fails the encoding of the whole tree
+        let _ = unknown.encode_fields();
+
+        // constructed strings other than OCTET STRING and BIT STRING are decoded as unknown entities.
+        // Nested constructed segments are already merged, so all segments are primitive
+        let tag = unknown.tag();
+        if !is_constructed_string(tag) {
+            return;
+        }
+        let merged = unknown
+            .fields()
+            .filter(|segments| segments.iter().all(|segment| segment.tag().is_primitive()))
+            .map(|segments| {
+                segments
+                    .iter()
+                    .flat_map(|segment| segment.inner_asn1().contents())
+                    .collect::<Vec<_>>()
+            });
+        if let Some(octets) = merged {
+            *unknown = Unknown::new_owned(Tag::new(TagClass::Universal, false, tag.number()), octets);
+        }
+    }
+
+    fn leave_node(&mut self, node: &mut Asn1<'_>, _context: &VisitContext<'_>) {
+        // merged strings get their own types, the same as after decoding of the DER encoded data
+        let Asn1Type::Unknown(unknown) = node.inner_asn1() else {
+            return;
+        };
+        if !Asn1Type::is_supported_tag(unknown.tag()) {
+            return;
+        }
+
+        let decoded = Asn1Type::decode(unknown.tag(), &mut Reader::new(unknown.octets())).map(|asn1| asn1.to_owned());
+        if let Ok(decoded) = decoded {
+            *node.inner_asn1_mut() = decoded;
+        }
+    }
+
+    fn leave_set(&mut self, set: &mut Set<'_>, _context: &VisitContext<'_>) {
+        let fields = set.fields_mut();
+
+        match fields.first().map(|first| first.tag()) {
+            Some(tag) if fields.iter().all(|f| f.tag() == tag) => {
+                // the lexicographic order of encodings also satisfies X.690 11.6: a prefix is always less or equal.
+                // entities that can not be encoded fail the encoding of the whole tree anyway
+                fields.sort_by_cached_key(|field| field.encode_to_vec().unwrap_or_default())
+            }
+            _ => fields.sort_by_key(|field| {
+                let tag = field.tag();
+
+                (tag.class() as u8, tag.number())
+            }),
+        }
+    }
+}
+
+fn check_node(asn1: &Asn1<'_>, violations: &mut Vec<DerViolation>) {
+    let meta = asn1.meta();
+    let mut report = |kind| {
+        violations.push(DerViolation {
+            kind,
+            node_id: asn1.id(),
+            offset: meta.tag_position(),
+        })
+    };
+
+    if meta.is_indefinite_length() {
+        report(DerViolationKind::IndefiniteLength);
+    } else if !is_minimal_length(meta.length_bytes()) {
+        report(DerViolationKind::NonMinimalLength);
+    }
+
+    // constructed strings other than OCTET STRING and BIT STRING are decoded as unknown entities
+    if is_constructed_string(asn1.tag()) {
+        report(DerViolationKind::ConstructedString);
+    }
+
+    match asn1.inner_asn1() {
+        Asn1Type::Bool(_) => {
+            if !matches!(meta.data_bytes(), [0x00] | [0xff]) {
+                report(DerViolationKind::InvalidBoolean);
+            }
+        }
+        Asn1Type::Integer(integer) => {
+            if let [first, second, ..] = integer.raw_data() {
+                if (*first == 0x00 && second & 0x80 == 0) || (*first == 0xff && second & 0x80 != 0) {
+                    report(DerViolationKind::NonMinimalInteger);
+                }
+            }
+        }
+        Asn1Type::Set(set) => {
+            if !is_sorted_set(set) {
+                report(DerViolationKind::UnsortedSet);
+            }
+        }
+        Asn1Type::BitString(bit_string) => {
+            if bit_string.segments().is_none() && !has_zero_padding_bits(bit_string.raw_bits()) {
+                report(DerViolationKind::NonZeroPaddingBits);
+            }
+        }
+        Asn1Type::UtcTime(_) => {
+            if meta.data_bytes().last() != Some(&b'Z') {
+                report(DerViolationKind::NonUtcTime);
+            }
+        }
+        Asn1Type::GeneralizedTime(_) => {
+            let data = meta.data_bytes();
+
+            match data.split_last() {
+                Some((b'Z', time)) => {
+                    if time.contains(&b'.') && matches!(time.last(), Some(b'0') | Some(b'.')) {
+                        report(DerViolationKind::TrailingZeroFraction);
+                    }
+                }
+                _ => report(DerViolationKind::NonUtcTime),
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Tag numbers of the universal string types: BIT STRING, OCTET STRING, ObjectDescriptor, and the restricted
+/// character strings. DER allows only the primitive form of them (X.690 10.2)
+const STRING_TAG_NUMBERS: [u32; 14] = [3, 4, 7, 12, 18, 19, 20, 21, 22, 25, 26, 27, 28, 30];
+
+fn is_constructed_string(tag: Tag) -> bool {
+    tag.is_universal() && tag.is_constructed() && STRING_TAG_NUMBERS.contains(&tag.number())
+}
+
+fn has_zero_padding_bits(bits: &[u8]) -> bool {
+    match bits {
+        [] => true,
+        [unused_bits] => *unused_bits == 0,
+        [unused_bits, .., last] => {
+            let mask = 1_u16.checked_shl(u32::from(*unused_bits)).unwrap_or(0x100) - 1;
+
+            u16::from(*last) & mask == 0
+        }
+    }
+}
+
+/// SET elements should be sorted by tags. SET OF elements (all of the same type) should be sorted by their encodings.
+fn is_sorted_set(set: &Set<'_>) -> bool {
+    let fields = set.fields();
+
+    match fields.first() {
+        Some(first) if fields.iter().all(|f| f.tag() == first.tag()) => fields
+            .windows(2)
+            .all(|pair| compare_encodings(pair[0].meta(), pair[1].meta()) != Ordering::Greater),
+        _ => fields.windows(2).all(|pair| {
+            let (left, right) = (pair[0].tag(), pair[1].tag());
+
+            (left.class() as u8, left.number()) <= (right.class() as u8, right.number())
+        }),
+    }
+}
+
+/// Compares encodings as octet strings with the shorter one padded at its trailing end with 0-octets (X.690 11.6)
+fn compare_encodings(left: &RawAsn1EntityData<'_>, right: &RawAsn1EntityData<'_>) -> Ordering {
+    let (left, right) = (left.raw_bytes(), right.raw_bytes());
+    let len = left.len().max(right.len());
+
+    (0..len)
+        .map(|i| (left.get(i).copied().unwrap_or(0), right.get(i).copied().unwrap_or(0)))
+        .map(|(l, r)| l.cmp(&r))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
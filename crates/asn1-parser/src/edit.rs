@@ -0,0 +1,173 @@
+//! In-place editing of the decoded asn1 tree.
+//!
+//! Lengths are not stored in the tree: they are calculated from the values during the encoding, so all
+//! enclosing lengths are updated automatically after the value is changed. The asn1 entities encapsulated into
+//! OCTET STRING, BIT STRING, IMPLICIT tags, and unknown constructed entities are encoded from the raw octets,
+//! so [edit_node] re-encodes these octets after the encapsulated entity is changed.
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use num_bigint_dig::BigInt;
+use num_traits::Num;
+
+use crate::reader::Reader;
+use crate::writer::Writer;
+use crate::{
+    Asn1, Asn1Entity, Asn1Result, Asn1Type, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, GeneralizedTime,
+    ObjectIdentifier, RelativeOid, Tag, UtcTime,
+};
+
+/// Finds the node with the given id and changes it using the `edit` function.
+///
+/// The search includes the encapsulated asn1 entities. Returns `false` if the node is not found.
+/// The meta info of the tree is not updated, so the tree should be encoded and decoded again
+/// to get valid offsets and raw entities data.
+pub fn edit_node<F>(root: &mut Asn1<'_>, node_id: u64, mut edit: F) -> Asn1Result<bool>
+where
+    F: FnMut(&mut Asn1<'_>) -> Asn1Result<()>,
+{
+    edit_node_recursive(root, node_id, &mut edit)
+}
+
+fn edit_node_recursive<F>(asn1: &mut Asn1<'_>, node_id: u64, edit: &mut F) -> Asn1Result<bool>
+where
+    F: FnMut(&mut Asn1<'_>) -> Asn1Result<()>,
+{
+    if asn1.id() == node_id {
+        edit(asn1)?;
+
+        return Ok(true);
+    }
+
+    match asn1.inner_asn1_mut() {
+        Asn1Type::Sequence(sequence) => edit_fields(sequence.fields_mut(), node_id, edit),
+        Asn1Type::Set(set) => edit_fields(set.fields_mut(), node_id, edit),
+        Asn1Type::ExplicitTag(explicit) => edit_fields(explicit.inner_mut(), node_id, edit),
+        Asn1Type::ApplicationTag(application) => edit_fields(application.inner_mut(), node_id, edit),
+        Asn1Type::OctetString(octet_string) => {
+            let edited = match octet_string.inner_mut() {
+                Some(inner) => edit_node_recursive(inner, node_id, edit)?,
+                None => false,
+            };
+            if edited {
+                octet_string.encode_inner()?;
+            }
+
+            Ok(edited)
+        }
+        Asn1Type::BitString(bit_string) => {
+            let edited = match bit_string.inner_mut() {
+                Some(inner) => edit_node_recursive(inner, node_id, edit)?,
+                None => false,
+            };
+            if edited {
+                bit_string.encode_inner()?;
+            }
+
+            Ok(edited)
+        }
+        Asn1Type::ImplicitTag(implicit) => {
+            let edited = match implicit.inner_mut() {
+                Some(inner) => edit_node_recursive(inner, node_id, edit)?,
+                None => false,
+            };
+            if edited {
+                implicit.encode_inner()?;
+            }
+
+            Ok(edited)
+        }
+        Asn1Type::Unknown(unknown) => {
+            let edited = match unknown.fields_mut() {
+                Some(fields) => edit_fields(fields, node_id, edit)?,
+                None => false,
+            };
+            if edited {
+                unknown.encode_fields()?;
+            }
+
+            Ok(edited)
+        }
+        _ => Ok(false),
+    }
+}
+
+fn edit_fields<F>(fields: &mut [Asn1<'_>], node_id: u64, edit: &mut F) -> Asn1Result<bool>
+where
+    F: FnMut(&mut Asn1<'_>) -> Asn1Result<()>,
+{
+    for field in fields {
+        if edit_node_recursive(field, node_id, edit)? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Parses the text value and sets it to the node.
+///
+/// The value format is the same as [node_value](crate::node_value) returns. UTCTime and GeneralizedTime
+/// values are parsed from their string encoding, e.g. `20240101000000Z`.
+pub fn set_node_value(asn1: &mut Asn1<'_>, value: &str) -> Asn1Result<()> {
+    match asn1.inner_asn1_mut() {
+        Asn1Type::Bool(boolean) => boolean.set_value(match value {
+            "true" => true,
+            "false" => false,
+            _ => return Err(Error::new(ErrorKind::InvalidValue, "Invalid BOOLEAN value")),
+        }),
+        Asn1Type::Integer(integer) => integer.set_value(&parse_big_int(value)?),
+        Asn1Type::Enumerated(enumerated) => enumerated.set_value(&parse_big_int(value)?),
+        Asn1Type::ObjectIdentifier(oid) => *oid = ObjectIdentifier::try_from(value)?,
+        Asn1Type::RelativeOid(oid) => {
+            *oid = RelativeOid::from(
+                value
+                    .split('.')
+                    .map(|arc| arc.parse::<u64>())
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+        }
+        Asn1Type::Utf8String(string) => string.set_string(value.to_string())?,
+        Asn1Type::NumericString(string) => string.set_string(value.to_string())?,
+        Asn1Type::PrintableString(string) => string.set_string(value.to_string())?,
+        Asn1Type::IA5String(string) => string.set_string(value.to_string())?,
+        Asn1Type::VisibleString(string) => string.set_string(value.to_string())?,
+        Asn1Type::GeneralString(string) => string.set_string(value.to_string())?,
+        Asn1Type::UniversalString(string) => string.set_string(value),
+        Asn1Type::TeletexString(string) => string.set_string(value)?,
+        Asn1Type::VideotexString(string) => string.set_string(value)?,
+        Asn1Type::GraphicString(string) => string.set_string(value)?,
+        Asn1Type::ObjectDescriptor(string) => string.set_string(value)?,
+        Asn1Type::UtcTime(utc_time) => *utc_time = decode_time(UtcTime::TAG, value)?,
+        Asn1Type::GeneralizedTime(generalized_time) => *generalized_time = decode_time(GeneralizedTime::TAG, value)?,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidValue,
+                "Value of this asn1 type can not be set from text",
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+/// Decodes the time value. Decoders stop after the time zone, so the value is encoded again to reject trailing data
+fn decode_time<'data, T>(tag: Tag, value: &'data str) -> Asn1Result<T>
+where
+    T: Asn1ValueDecoder<'data> + Asn1ValueEncoder,
+{
+    let time = T::decode(tag, &mut Reader::new(value.as_bytes()))?;
+
+    let mut writer = Writer::growable();
+    time.encode_value(&mut writer)?;
+    if writer.into_vec()? != value.as_bytes() {
+        return Err(Error::new(ErrorKind::InvalidValue, "Invalid time value"));
+    }
+
+    Ok(time)
+}
+
+pub(crate) fn parse_big_int(value: &str) -> Asn1Result<BigInt> {
+    BigInt::from_str_radix(value, 10).map_err(|_| Error::new(ErrorKind::InvalidValue, "Invalid INTEGER value"))
+}
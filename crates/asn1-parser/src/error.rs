@@ -1,68 +1,237 @@
-use alloc::string::FromUtf16Error;
+use alloc::string::{FromUtf16Error, String};
+use alloc::vec::Vec;
+use core::fmt;
 use core::num::{ParseFloatError, ParseIntError, TryFromIntError};
 use core::str::Utf8Error;
 
 use oid::ObjectIdentifierError;
 
-#[derive(Debug)]
+use crate::Tag;
+
+/// Kind of the asn1 decoding/encoding error
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// Input data ended before the asn1 entity was fully read
+    UnexpectedEof,
+    /// Invalid tag (identifier octets)
+    InvalidTag,
+    /// Invalid length octets or end-of-contents octets
+    InvalidLength,
+    /// The tag is not supported or not expected at this place
+    UnexpectedTag,
+    /// Invalid asn1 entity value (contents octets)
+    InvalidValue,
+    /// Output buffer is too small to encode the asn1 entity
+    BufferTooSmall,
+    /// Input data contains bytes after the decoded asn1 entity
+    TrailingData,
+    /// Invalid ASN.1 module (schema) notation
+    InvalidSchema,
+    /// Invalid asn1 path notation
+    InvalidPath,
+    /// The input exceeds limits set by the decoding options
+    LimitExceeded,
+    /// Reading from the input stream failed
+    Io,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ErrorKind::UnexpectedEof => "unexpected end of data",
+            ErrorKind::InvalidTag => "invalid tag",
+            ErrorKind::InvalidLength => "invalid length",
+            ErrorKind::UnexpectedTag => "unexpected tag",
+            ErrorKind::InvalidValue => "invalid value",
+            ErrorKind::BufferTooSmall => "buffer is too small",
+            ErrorKind::TrailingData => "trailing data",
+            ErrorKind::InvalidSchema => "invalid schema",
+            ErrorKind::InvalidPath => "invalid path",
+            ErrorKind::LimitExceeded => "decoding limit exceeded",
+            ErrorKind::Io => "input/output error",
+        })
+    }
+}
+
+/// Node in which the error occurred
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ErrorPathNode {
+    /// Tag of the node
+    pub tag: Tag,
+    /// Absolute position of the node tag in the input data
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Error {
+    kind: ErrorKind,
     message: &'static str,
+    // description of the underlying error, e.g. the I/O error of the stream
+    detail: Option<String>,
+    offset: Option<usize>,
+    expected_tag: Option<Tag>,
+    actual_tag: Option<Tag>,
+    path: Vec<ErrorPathNode>,
 }
 
 impl Error {
+    pub fn new(kind: ErrorKind, message: &'static str) -> Self {
+        Self {
+            kind,
+            message,
+            detail: None,
+            offset: None,
+            expected_tag: None,
+            actual_tag: None,
+            path: Vec::new(),
+        }
+    }
+
+    /// Creates a new [ErrorKind::UnexpectedTag] error
+    pub fn unexpected_tag(expected: Option<Tag>, actual: Tag) -> Self {
+        Self {
+            expected_tag: expected,
+            actual_tag: Some(actual),
+            ..Self::new(ErrorKind::UnexpectedTag, "Unexpected asn1 tag")
+        }
+    }
+
+    /// Sets the absolute byte offset in the input data at which the error occurred
+    pub fn with_offset(self, offset: usize) -> Self {
+        Self {
+            offset: Some(offset),
+            ..self
+        }
+    }
+
+    /// Sets the description of the underlying error that caused this one
+    pub fn with_detail(self, detail: String) -> Self {
+        Self {
+            detail: Some(detail),
+            ..self
+        }
+    }
+
+    /// Adds the node to the beginning of the error path.
+    ///
+    /// If the error has no offset yet, then the offset of the node data is used.
+    pub(crate) fn in_node(mut self, node: ErrorPathNode, data_offset: usize) -> Self {
+        self.offset.get_or_insert(data_offset);
+        self.path.insert(0, node);
+
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
     pub fn message(&self) -> &str {
         self.message
     }
+
+    /// Returns the description of the underlying error, e.g. the kind and the text of the stream I/O error
+    pub fn detail(&self) -> Option<&str> {
+        self.detail.as_deref()
+    }
+
+    /// Returns the absolute byte offset in the input data at which the error occurred
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    pub fn expected_tag(&self) -> Option<Tag> {
+        self.expected_tag
+    }
+
+    pub fn actual_tag(&self) -> Option<Tag> {
+        self.actual_tag
+    }
+
+    /// Returns nodes from the root to the one in which the error occurred
+    pub fn path(&self) -> &[ErrorPathNode] {
+        &self.path
+    }
 }
 
-impl From<&'static str> for Error {
-    fn from(message: &'static str) -> Self {
-        Self { message }
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)?;
+
+        if let Some(detail) = &self.detail {
+            write!(f, " ({})", detail)?;
+        }
+
+        if let Some(offset) = self.offset {
+            write!(f, " at offset {}", offset)?;
+        }
+
+        if let Some(expected) = self.expected_tag {
+            write!(f, ", expected tag {}", expected)?;
+        }
+
+        if let Some(actual) = self.actual_tag {
+            write!(f, ", actual tag {}", actual)?;
+        }
+
+        if !self.path.is_empty() {
+            f.write_str(", path: ")?;
+
+            for (i, node) in self.path.iter().enumerate() {
+                if i != 0 {
+                    f.write_str(" / ")?;
+                }
+
+                write!(f, "{}@{}", node.tag, node.offset)?;
+            }
+        }
+
+        Ok(())
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
 impl From<Utf8Error> for Error {
     fn from(_value: Utf8Error) -> Self {
-        Self { message: "Utf8error" }
+        Self::new(ErrorKind::InvalidValue, "Utf8error")
     }
 }
 
 impl From<FromUtf16Error> for Error {
     fn from(_value: FromUtf16Error) -> Self {
-        Self {
-            message: "FromUtf16Error",
-        }
+        Self::new(ErrorKind::InvalidValue, "FromUtf16Error")
     }
 }
 
 impl From<TryFromIntError> for Error {
     fn from(_value: TryFromIntError) -> Self {
-        Self {
-            message: "Numbers conversion error",
-        }
+        Self::new(ErrorKind::InvalidValue, "Numbers conversion error")
     }
 }
 
 impl From<ObjectIdentifierError> for Error {
     fn from(_value: ObjectIdentifierError) -> Self {
-        Self {
-            message: "ObjectIdentifierError",
-        }
+        Self::new(ErrorKind::InvalidValue, "ObjectIdentifierError")
     }
 }
 
 impl From<ParseFloatError> for Error {
     fn from(_value: ParseFloatError) -> Self {
-        Self {
-            message: "Float parse error",
-        }
+        Self::new(ErrorKind::InvalidValue, "Float parse error")
     }
 }
 
 impl From<ParseIntError> for Error {
     fn from(_value: ParseIntError) -> Self {
-        Self {
-            message: "Int parse error",
-        }
+        Self::new(ErrorKind::InvalidValue, "Int parse error")
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<::time::error::ComponentRange> for Error {
+    fn from(_value: ::time::error::ComponentRange) -> Self {
+        Self::new(ErrorKind::InvalidValue, "Date or time component is out of range")
     }
 }
@@ -2,28 +2,46 @@ use core::mem::size_of;
 use core::ops::Range;
 
 use crate::reader::Reader;
+use crate::tag::read_tag;
 use crate::writer::Writer;
-use crate::{Asn1Result, Error};
+use crate::{Asn1Result, Error, ErrorKind};
 
-const USIZE_LEN: usize = size_of::<usize>();
+pub(crate) const USIZE_LEN: usize = size_of::<usize>();
+
+/// Length octets value of the indefinite form
+pub const INDEFINITE_LENGTH: u8 = 0x80;
+
+/// End-of-contents octets. They terminate contents encoded using the indefinite length form
+pub const END_OF_CONTENTS: [u8; 2] = [0x00, 0x00];
+
+/// Decoded asn1 length
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Length {
+    /// Length of the contents octets is encoded in the length octets
+    Definite(usize),
+    /// Contents octets are terminated by the end-of-contents octets
+    Indefinite,
+}
 
 /// Reads length from the reader
-pub fn read_len(reader: &mut Reader) -> Asn1Result<(usize, Range<usize>)> {
+pub fn read_len(reader: &mut Reader) -> Asn1Result<(Length, Range<usize>)> {
     let before = reader.position();
+    let offset = reader.full_offset();
 
     let length = match reader.read_byte()? {
-        n @ 128..=255 => {
+        INDEFINITE_LENGTH => Length::Indefinite,
+        n @ 129..=255 => {
             let len = n as usize & 127;
             if len > USIZE_LEN {
-                return Err(Error::from("Invalid length bytes"));
+                return Err(Error::new(ErrorKind::InvalidLength, "Invalid length bytes").with_offset(offset));
             }
 
             let mut num = [0; USIZE_LEN];
             reader.read_exact(&mut num[USIZE_LEN - len..])?;
 
-            usize::from_be_bytes(num)
+            Length::Definite(usize::from_be_bytes(num))
         }
-        n => n as usize,
+        n => Length::Definite(n as usize),
     };
 
     let after = reader.position();
@@ -31,6 +49,48 @@ pub fn read_len(reader: &mut Reader) -> Asn1Result<(usize, Range<usize>)> {
     Ok((length, before..after))
 }
 
+/// Checks if the length octets use the minimal form required by DER
+pub(crate) fn is_minimal_length(length: &[u8]) -> bool {
+    match length {
+        [] => true,
+        [len] => *len != INDEFINITE_LENGTH,
+        // long form: the first octet contains the amount of the subsequent octets
+        [_, first, rest @ ..] => *first != 0 && !(rest.is_empty() && *first < 0x80),
+    }
+}
+
+fn fits_into_octets(length: usize, octets: usize) -> bool {
+    octets >= USIZE_LEN || length >> (octets * 8) == 0
+}
+
+/// Returns how many bytes encoded length will take in the long form with provided amount of subsequent octets.
+///
+/// The minimal form is used if the length does not fit into these octets.
+pub fn len_size_with_octets(length: usize, octets: usize) -> usize {
+    if fits_into_octets(length, octets) {
+        1 + octets
+    } else {
+        len_size(length)
+    }
+}
+
+/// Writes asn1 length in the long form with provided amount of subsequent octets.
+///
+/// The minimal form is used if the length does not fit into these octets.
+pub fn write_len_with_octets(length: usize, octets: usize, writer: &mut Writer) -> Asn1Result<()> {
+    if !fits_into_octets(length, octets) {
+        return write_len(length, writer);
+    }
+
+    writer.write_byte(0x80 | u8::try_from(octets)?)?;
+
+    let padding = octets.saturating_sub(USIZE_LEN);
+    for _ in 0..padding {
+        writer.write_byte(0)?;
+    }
+    writer.write_slice(&length.to_be_bytes()[USIZE_LEN - (octets - padding)..])
+}
+
 /// Writes asn1 length into provided writer
 pub fn write_len(length: usize, writer: &mut Writer) -> Asn1Result<()> {
     if length < 128 {
@@ -45,6 +105,47 @@ pub fn write_len(length: usize, writer: &mut Writer) -> Asn1Result<()> {
     }
 }
 
+/// Writes length and contents octets into provided writer.
+///
+/// Growable writers fill in the length octets after the contents octets are encoded, so `value_len`
+/// is called only for fixed-size buffers. It makes the encoding of nested structures linear.
+pub fn write_len_and_value(
+    writer: &mut Writer,
+    value_len: impl FnOnce() -> usize,
+    encode_value: impl FnOnce(&mut Writer) -> Asn1Result<()>,
+) -> Asn1Result<()> {
+    if writer.is_growable() {
+        let mark = writer.begin_len();
+        encode_value(writer)?;
+        writer.end_len(mark);
+
+        Ok(())
+    } else {
+        write_len(value_len(), writer)?;
+        encode_value(writer)
+    }
+}
+
+/// Same as [write_len_and_value], but the length is written in the long form with provided amount of subsequent
+/// octets (see [write_len_with_octets])
+pub(crate) fn write_len_with_octets_and_value(
+    writer: &mut Writer,
+    octets: usize,
+    value_len: impl FnOnce() -> usize,
+    encode_value: impl FnOnce(&mut Writer) -> Asn1Result<()>,
+) -> Asn1Result<()> {
+    if writer.is_growable() {
+        let mark = writer.begin_len_with_octets(octets);
+        encode_value(writer)?;
+        writer.end_len(mark);
+
+        Ok(())
+    } else {
+        write_len_with_octets(value_len(), octets, writer)?;
+        encode_value(writer)
+    }
+}
+
 /// Returns how many bytes encoded length will take
 pub fn len_size(data_len: usize) -> usize {
     if data_len < 128 {
@@ -53,3 +154,47 @@ pub fn len_size(data_len: usize) -> usize {
         1 + USIZE_LEN - (data_len.leading_zeros() / 8) as usize
     }
 }
+
+/// Calculates the length of the contents octets encoded using the indefinite form.
+///
+/// The reader should point right after the length octets. The returned length does not include
+/// the end-of-contents octets. The reader position is not changed.
+pub fn indefinite_len(reader: &Reader) -> Asn1Result<usize> {
+    let mut contents = Reader::new(reader.remaining());
+    contents.set_offset(reader.full_offset());
+    // amount of nested indefinite-length encodings that are not terminated yet
+    let mut depth = 0_usize;
+
+    loop {
+        if contents.remaining().starts_with(&END_OF_CONTENTS) {
+            if depth == 0 {
+                return Ok(contents.position());
+            }
+
+            contents.read(END_OF_CONTENTS.len())?;
+            depth -= 1;
+
+            continue;
+        }
+
+        let tag = read_tag(&mut contents)?;
+        let length_offset = contents.full_offset();
+
+        match read_len(&mut contents)?.0 {
+            Length::Definite(len) => {
+                contents.read(len)?;
+            }
+            Length::Indefinite => {
+                if tag.is_primitive() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidLength,
+                        "Indefinite length is not allowed for primitive types",
+                    )
+                    .with_offset(length_offset));
+                }
+
+                depth += 1;
+            }
+        }
+    }
+}
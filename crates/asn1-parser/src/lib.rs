@@ -10,29 +10,73 @@ mod macros;
 extern crate log;
 
 mod asn1;
+mod builder;
 mod constructors;
+mod cursor;
+mod der;
+mod edit;
 mod error;
 mod length;
+#[cfg(feature = "oid-registry")]
+mod oid_registry;
 mod primitives;
+mod query;
 mod reader;
+mod schema;
+#[cfg(feature = "serde")]
+mod serialization;
+mod shared;
+#[cfg(feature = "std")]
+mod stream;
 mod string;
 mod tag;
 mod tags;
+mod text_dump;
 mod time;
 mod tlv;
+mod tree_diff;
+mod typed;
+mod unknown;
+mod unparsed;
+mod visitor;
 mod writer;
 
+use alloc::vec::Vec;
+
 pub use asn1::{Asn1, Asn1Type, OwnedAsn1, OwnedAsn1Type, OwnedRawAsn1EntityData, RawAsn1EntityData};
+pub use builder::Asn1Builder;
 pub use constructors::*;
-pub use error::Error;
+pub use cursor::{Asn1Cursor, Asn1Header};
+pub use der::{canonicalize_der, check_der, DerViolation, DerViolationKind};
+pub use edit::{edit_node, set_node_value};
+pub use error::{Error, ErrorKind, ErrorPathNode};
+pub use length::Length;
+use length::{len_size, write_len, write_len_and_value};
+#[cfg(feature = "oid-registry")]
+pub use oid_registry::{OidInfo, OidRegistry};
 pub use primitives::*;
-use reader::Reader;
+pub use query::{find_all, node_value, Asn1Path};
+pub use reader::{DecodeOptions, Reader};
+pub use schema::{Asn1Schema, SchemaAnnotation};
+pub use shared::{OwnedRawBytes, RawBytes};
+#[cfg(feature = "std")]
+pub use stream::StreamDecoder;
 pub use string::*;
-pub use tag::Tag;
+use tag::{tag_size, write_tag};
+pub use tag::{Tag, TagClass};
 pub use tags::*;
+pub use text_dump::{text_dump, TextDumpStyle};
 pub use time::*;
 pub use tlv::Tlv;
-use writer::Writer;
+pub use tree_diff::{diff_asn1, Asn1Change, Asn1ChangeKind, Asn1ChangedNode};
+pub use typed::{
+    decode_explicit, decode_implicit, encode_explicit, encode_implicit, encodes_equal, explicit_len, explicit_tag,
+    implicit_len, is_implicit_tag, peek_tag,
+};
+pub use unknown::{OwnedUnknown, Unknown};
+pub use unparsed::{OwnedUnparsed, Unparsed};
+pub use visitor::{Asn1Visitor, Asn1VisitorMut, VisitContext, VisitFlow};
+pub use writer::Writer;
 
 pub type Asn1Result<T> = Result<T, Error>;
 
@@ -48,12 +92,22 @@ pub trait Asn1Decoder<'data>: Sized {
     fn decode_buff(buff: &'data [u8]) -> Asn1Result<Self> {
         Self::decode(&mut Reader::new(buff))
     }
+
+    /// Decodes the asn1 entity using provided buffer and decoding limits.
+    fn decode_buff_with_options(buff: &'data [u8], options: DecodeOptions) -> Asn1Result<Self> {
+        Self::decode(&mut Reader::with_options(buff, options))
+    }
 }
 
 pub trait Asn1ValueDecoder<'data>: Sized {
     fn decode(tag: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self>;
 
     fn compare_tags(tag: Tag) -> bool;
+
+    /// Returns the tag expected by the decoder if there is only one. It is used for error reporting.
+    fn expected_tag() -> Option<Tag> {
+        None
+    }
 }
 
 /// General trait for encoding asn1 entities
@@ -68,6 +122,67 @@ pub trait Asn1Encoder {
 
     /// Encodes asn1 entity into provided writer
     fn encode(&self, writer: &mut Writer) -> Asn1Result<()>;
+
+    /// Encodes asn1 entity into a new vector. Unlike [Asn1Encoder::encode_buff], it doesn't need
+    /// the buffer size in advance, so nested lengths are calculated only once.
+    fn encode_to_vec(&self) -> Asn1Result<Vec<u8>> {
+        let mut writer = Writer::growable();
+        self.encode(&mut writer)?;
+
+        writer.into_vec()
+    }
+}
+
+/// General trait for encoding asn1 entity values (contents octets).
+///
+/// Tag and length octets are written by the [Asn1Encoder] implementation.
+pub trait Asn1ValueEncoder: Taggable {
+    /// Returns length of the encoded asn1 entity value
+    fn value_len(&self) -> usize;
+
+    /// Encodes asn1 entity value into provided writer
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()>;
+
+    /// Returns length of the encoded tag and length octets
+    fn header_len(&self) -> usize {
+        self.header_len_for(self.value_len())
+    }
+
+    /// Returns length of the encoded tag and length octets for the already computed value length.
+    ///
+    /// Constructed values compute their length from the children, so it should be done only once per entity.
+    fn header_len_for(&self, value_len: usize) -> usize {
+        tag_size(self.tag()) + len_size(value_len)
+    }
+
+    /// Encodes tag and length octets into provided writer
+    fn encode_header(&self, writer: &mut Writer) -> Asn1Result<()> {
+        write_tag(self.tag(), writer)?;
+        write_len(self.value_len(), writer)
+    }
+
+    /// Encodes asn1 entity into provided growable writer. The length octets are filled in by the writer.
+    fn encode_growable(&self, writer: &mut Writer) -> Asn1Result<()> {
+        write_tag(self.tag(), writer)?;
+        write_len_and_value(writer, || self.value_len(), |writer| self.encode_value(writer))
+    }
+}
+
+impl<T: Asn1ValueEncoder> Asn1Encoder for T {
+    fn needed_buf_size(&self) -> usize {
+        let value_len = self.value_len();
+
+        self.header_len_for(value_len) + value_len
+    }
+
+    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
+        if writer.is_growable() {
+            return self.encode_growable(writer);
+        }
+
+        self.encode_header(writer)?;
+        self.encode_value(writer)
+    }
 }
 
 /// Every asn1 entity should implement this trait.
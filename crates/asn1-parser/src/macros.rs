@@ -1,19 +1,19 @@
 macro_rules! impl_utf8_asn1 {
     ($name:ident, $tag:expr, $validator_fn:ident) => {
         #[derive(Debug, Clone, PartialEq, Eq)]
-        pub struct $name<'data>(Utf8Value<'data, 19>);
+        pub struct $name<'data>(Utf8Value<'data, $tag>);
 
         paste::paste! {
             pub type [<Owned $name>] = $name<'static>;
         }
 
-        impl Asn1Encoder for $name<'_> {
-            fn needed_buf_size(&self) -> usize {
-                self.0.needed_buf_size()
+        impl Asn1ValueEncoder for $name<'_> {
+            fn value_len(&self) -> usize {
+                self.0.value_len()
             }
 
-            fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-                self.0.encode(writer)
+            fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+                self.0.encode_value(writer)
             }
         }
 
@@ -34,7 +34,7 @@ macro_rules! impl_utf8_asn1 {
                 let utf8_value = Utf8Value::decode(tag, reader)?;
 
                 if !$validator_fn(utf8_value.as_str()) {
-                    return Err("invalid string data".into());
+                    return Err(Error::new(ErrorKind::InvalidValue, "invalid string data"));
                 }
 
                 Ok(Self(utf8_value))
@@ -43,10 +43,14 @@ macro_rules! impl_utf8_asn1 {
             fn compare_tags(tag: Tag) -> bool {
                 Self::TAG == tag
             }
+
+            fn expected_tag() -> Option<Tag> {
+                Some(Self::TAG)
+            }
         }
 
         impl $name<'_> {
-            pub const TAG: Tag = Tag($tag);
+            pub const TAG: Tag = Tag::from_byte($tag);
 
             pub fn raw_data(&self) -> &[u8] {
                 self.0.as_bytes()
@@ -56,9 +60,22 @@ macro_rules! impl_utf8_asn1 {
                 self.0.as_str()
             }
 
+            /// Replaces the string value. The value should satisfy the character set of the string type
+            pub fn set_string(&mut self, value: String) -> Asn1Result<()> {
+                if !$validator_fn(&value) {
+                    return Err(Error::new(ErrorKind::InvalidValue, "invalid string data"));
+                }
+                self.0 = value.into();
+
+                Ok(())
+            }
+
             pub fn to_owned(&self) -> $name<'static> {
-                use crate::alloc::string::ToString;
-                $name(self.0.as_str().to_string().into())
+                self.to_owned_in(&CopiedRawData)
+            }
+
+            pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> $name<'static> {
+                $name(self.0.to_owned_in(storage))
             }
         }
 
@@ -70,6 +87,95 @@ macro_rules! impl_utf8_asn1 {
     };
 }
 
+/// Defines a string type whose character set is not compatible with UTF-8 (T.61, T.100, ISO 2022).
+///
+/// The contents octets are kept as is. They are interpreted as ISO 8859-1 when the string is displayed,
+/// which matches the commonly used ASCII-compatible subset of those character sets.
+macro_rules! impl_byte_string_asn1 {
+    ($name:ident, $tag:expr) => {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name<'data>(RawBytes<'data>);
+
+        paste::paste! {
+            pub type [<Owned $name>] = $name<'static>;
+        }
+
+        impl Asn1ValueEncoder for $name<'_> {
+            fn value_len(&self) -> usize {
+                self.0.len()
+            }
+
+            fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+                writer.write_slice(&self.0)
+            }
+        }
+
+        impl From<Vec<u8>> for $name<'static> {
+            fn from(value: Vec<u8>) -> Self {
+                Self(RawBytes::from(value))
+            }
+        }
+
+        impl crate::Taggable for $name<'_> {
+            fn tag(&self) -> Tag {
+                Self::TAG
+            }
+        }
+
+        impl<'data> Asn1ValueDecoder<'data> for $name<'data> {
+            fn decode(_: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
+                Ok(Self(RawBytes::from(reader.read_remaining())))
+            }
+
+            fn compare_tags(tag: Tag) -> bool {
+                Self::TAG == tag
+            }
+
+            fn expected_tag() -> Option<Tag> {
+                Some(Self::TAG)
+            }
+        }
+
+        impl $name<'_> {
+            pub const TAG: Tag = Tag::from_byte($tag);
+
+            pub fn raw_data(&self) -> &[u8] {
+                &self.0
+            }
+
+            pub fn string(&self) -> String {
+                self.0.iter().map(|byte| char::from(*byte)).collect()
+            }
+
+            /// Replaces the string value. Every character is encoded as one ISO 8859-1 octet
+            pub fn set_string(&mut self, value: &str) -> Asn1Result<()> {
+                self.0 = value
+                    .chars()
+                    .map(|c| u8::try_from(u32::from(c)))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| Error::new(ErrorKind::InvalidValue, "String value should be Latin-1 text"))?
+                    .into();
+
+                Ok(())
+            }
+
+            pub fn to_owned(&self) -> $name<'static> {
+                self.to_owned_in(&CopiedRawData)
+            }
+
+            pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> $name<'static> {
+                $name(storage.store_bytes(&self.0))
+            }
+        }
+
+        impl<'data> From<&'data [u8]> for $name<'data> {
+            fn from(data: &'data [u8]) -> Self {
+                Self(RawBytes::from(data))
+            }
+        }
+    };
+}
+
 macro_rules! decode_asn1 {
     ($($name:ident),*; in $tag:expr, $reader:expr)  => {
         {
@@ -0,0 +1,444 @@
+use super::OidInfo;
+
+/// Built-in object identifiers. Names are unique within the database
+#[rustfmt::skip]
+pub(super) static BUILTIN_OIDS: &[OidInfo] = &[
+    // PKCS #1 (RFC 8017)
+    OidInfo::builtin("1.2.840.113549.1.1", "pkcs-1", "PKCS #1"),
+    OidInfo::builtin("1.2.840.113549.1.1.1", "rsaEncryption", "RSA encryption (PKCS #1)"),
+    OidInfo::builtin("1.2.840.113549.1.1.2", "md2WithRSAEncryption", "MD2 with RSA encryption (PKCS #1)"),
+    OidInfo::builtin("1.2.840.113549.1.1.3", "md4WithRSAEncryption", "MD4 with RSA encryption (PKCS #1)"),
+    OidInfo::builtin("1.2.840.113549.1.1.4", "md5WithRSAEncryption", "MD5 with RSA encryption (PKCS #1)"),
+    OidInfo::builtin("1.2.840.113549.1.1.5", "sha1WithRSAEncryption", "SHA-1 with RSA encryption (PKCS #1)"),
+    OidInfo::builtin("1.2.840.113549.1.1.7", "id-RSAES-OAEP", "RSAES-OAEP encryption scheme (PKCS #1)"),
+    OidInfo::builtin("1.2.840.113549.1.1.8", "id-mgf1", "MGF1 mask generation function (PKCS #1)"),
+    OidInfo::builtin("1.2.840.113549.1.1.9", "id-pSpecified", "OAEP encoding parameters source (PKCS #1)"),
+    OidInfo::builtin("1.2.840.113549.1.1.10", "id-RSASSA-PSS", "RSASSA-PSS signature scheme (PKCS #1)"),
+    OidInfo::builtin("1.2.840.113549.1.1.11", "sha256WithRSAEncryption", "SHA-256 with RSA encryption (PKCS #1)"),
+    OidInfo::builtin("1.2.840.113549.1.1.12", "sha384WithRSAEncryption", "SHA-384 with RSA encryption (PKCS #1)"),
+    OidInfo::builtin("1.2.840.113549.1.1.13", "sha512WithRSAEncryption", "SHA-512 with RSA encryption (PKCS #1)"),
+    OidInfo::builtin("1.2.840.113549.1.1.14", "sha224WithRSAEncryption", "SHA-224 with RSA encryption (PKCS #1)"),
+    OidInfo::builtin(
+        "1.2.840.113549.1.1.15",
+        "sha512-224WithRSAEncryption",
+        "SHA-512/224 with RSA encryption (PKCS #1)",
+    ),
+    OidInfo::builtin(
+        "1.2.840.113549.1.1.16",
+        "sha512-256WithRSAEncryption",
+        "SHA-512/256 with RSA encryption (PKCS #1)",
+    ),
+    // PKCS #3
+    OidInfo::builtin("1.2.840.113549.1.3.1", "dhKeyAgreement", "Diffie-Hellman key agreement (PKCS #3)"),
+    // PKCS #5 (RFC 8018)
+    OidInfo::builtin("1.2.840.113549.1.5.1", "pbeWithMD2AndDES-CBC", "PBES1 with MD2 and DES-CBC (PKCS #5)"),
+    OidInfo::builtin("1.2.840.113549.1.5.3", "pbeWithMD5AndDES-CBC", "PBES1 with MD5 and DES-CBC (PKCS #5)"),
+    OidInfo::builtin("1.2.840.113549.1.5.10", "pbeWithSHA1AndDES-CBC", "PBES1 with SHA-1 and DES-CBC (PKCS #5)"),
+    OidInfo::builtin("1.2.840.113549.1.5.12", "id-PBKDF2", "PBKDF2 key derivation function (PKCS #5)"),
+    OidInfo::builtin("1.2.840.113549.1.5.13", "id-PBES2", "PBES2 encryption scheme (PKCS #5)"),
+    OidInfo::builtin("1.2.840.113549.1.5.14", "id-PBMAC1", "PBMAC1 message authentication scheme (PKCS #5)"),
+    // PKCS #7 and CMS (RFC 5652)
+    OidInfo::builtin("1.2.840.113549.1.7.1", "id-data", "Data content type (PKCS #7)"),
+    OidInfo::builtin("1.2.840.113549.1.7.2", "id-signedData", "Signed data content type (PKCS #7)"),
+    OidInfo::builtin("1.2.840.113549.1.7.3", "id-envelopedData", "Enveloped data content type (PKCS #7)"),
+    OidInfo::builtin(
+        "1.2.840.113549.1.7.4",
+        "id-signedAndEnvelopedData",
+        "Signed and enveloped data content type (PKCS #7)",
+    ),
+    OidInfo::builtin("1.2.840.113549.1.7.5", "id-digestedData", "Digested data content type (PKCS #7)"),
+    OidInfo::builtin("1.2.840.113549.1.7.6", "id-encryptedData", "Encrypted data content type (PKCS #7)"),
+    // PKCS #9 (RFC 2985)
+    OidInfo::builtin("1.2.840.113549.1.9.1", "pkcs-9-at-emailAddress", "Email address (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.2", "pkcs-9-at-unstructuredName", "Unstructured name (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.3", "pkcs-9-at-contentType", "Content type (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.4", "pkcs-9-at-messageDigest", "Message digest (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.5", "pkcs-9-at-signingTime", "Signing time (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.6", "pkcs-9-at-counterSignature", "Countersignature (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.7", "pkcs-9-at-challengePassword", "Challenge password (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.8", "pkcs-9-at-unstructuredAddress", "Unstructured address (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.14", "pkcs-9-at-extensionRequest", "Certificate extension request (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.15", "pkcs-9-at-smimeCapabilities", "S/MIME capabilities (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.16", "id-smime", "S/MIME (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.20", "pkcs-9-at-friendlyName", "Friendly name (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.21", "pkcs-9-at-localKeyId", "Local key identifier (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.22.1", "x509Certificate", "X.509 certificate bag type (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.22.2", "sdsiCertificate", "SDSI certificate bag type (PKCS #9)"),
+    OidInfo::builtin("1.2.840.113549.1.9.23.1", "x509Crl", "X.509 CRL bag type (PKCS #9)"),
+    OidInfo::builtin(
+        "1.2.840.113549.1.9.52",
+        "id-aa-CMSAlgorithmProtection",
+        "CMS algorithm protection attribute (RFC 6211)",
+    ),
+    // S/MIME
+    OidInfo::builtin("1.2.840.113549.1.9.16.1.4", "id-ct-TSTInfo", "Time-stamp token info content type (RFC 3161)"),
+    OidInfo::builtin("1.2.840.113549.1.9.16.1.9", "id-ct-compressedData", "Compressed data content type (RFC 3274)"),
+    OidInfo::builtin(
+        "1.2.840.113549.1.9.16.2.12",
+        "id-aa-signingCertificate",
+        "Signing certificate attribute (RFC 2634)",
+    ),
+    OidInfo::builtin("1.2.840.113549.1.9.16.2.14", "id-aa-timeStampToken", "Time-stamp token attribute (RFC 3161)"),
+    OidInfo::builtin(
+        "1.2.840.113549.1.9.16.2.47",
+        "id-aa-signingCertificateV2",
+        "Signing certificate V2 attribute (RFC 5035)",
+    ),
+    // PKCS #12 (RFC 7292)
+    OidInfo::builtin("1.2.840.113549.1.12.1.1", "pbeWithSHAAnd128BitRC4", "PBE with SHA-1 and 128-bit RC4 (PKCS #12)"),
+    OidInfo::builtin("1.2.840.113549.1.12.1.2", "pbeWithSHAAnd40BitRC4", "PBE with SHA-1 and 40-bit RC4 (PKCS #12)"),
+    OidInfo::builtin(
+        "1.2.840.113549.1.12.1.3",
+        "pbeWithSHAAnd3-KeyTripleDES-CBC",
+        "PBE with SHA-1 and 3-key Triple DES CBC (PKCS #12)",
+    ),
+    OidInfo::builtin(
+        "1.2.840.113549.1.12.1.4",
+        "pbeWithSHAAnd2-KeyTripleDES-CBC",
+        "PBE with SHA-1 and 2-key Triple DES CBC (PKCS #12)",
+    ),
+    OidInfo::builtin(
+        "1.2.840.113549.1.12.1.5",
+        "pbeWithSHAAnd128BitRC2-CBC",
+        "PBE with SHA-1 and 128-bit RC2 CBC (PKCS #12)",
+    ),
+    OidInfo::builtin(
+        "1.2.840.113549.1.12.1.6",
+        "pbeWithSHAAnd40BitRC2-CBC",
+        "PBE with SHA-1 and 40-bit RC2 CBC (PKCS #12)",
+    ),
+    OidInfo::builtin("1.2.840.113549.1.12.10.1.1", "keyBag", "Key bag (PKCS #12)"),
+    OidInfo::builtin("1.2.840.113549.1.12.10.1.2", "pkcs-8ShroudedKeyBag", "PKCS #8 shrouded key bag (PKCS #12)"),
+    OidInfo::builtin("1.2.840.113549.1.12.10.1.3", "certBag", "Certificate bag (PKCS #12)"),
+    OidInfo::builtin("1.2.840.113549.1.12.10.1.4", "crlBag", "CRL bag (PKCS #12)"),
+    OidInfo::builtin("1.2.840.113549.1.12.10.1.5", "secretBag", "Secret bag (PKCS #12)"),
+    OidInfo::builtin("1.2.840.113549.1.12.10.1.6", "safeContentsBag", "Safe contents bag (PKCS #12)"),
+    // RSADSI digest and encryption algorithms
+    OidInfo::builtin("1.2.840.113549.2", "digestAlgorithm", "RSADSI digest algorithms"),
+    OidInfo::builtin("1.2.840.113549.2.2", "md2", "MD2 hash algorithm"),
+    OidInfo::builtin("1.2.840.113549.2.4", "md4", "MD4 hash algorithm"),
+    OidInfo::builtin("1.2.840.113549.2.5", "md5", "MD5 hash algorithm"),
+    OidInfo::builtin("1.2.840.113549.2.7", "hmacWithSHA1", "HMAC with SHA-1"),
+    OidInfo::builtin("1.2.840.113549.2.8", "hmacWithSHA224", "HMAC with SHA-224"),
+    OidInfo::builtin("1.2.840.113549.2.9", "hmacWithSHA256", "HMAC with SHA-256"),
+    OidInfo::builtin("1.2.840.113549.2.10", "hmacWithSHA384", "HMAC with SHA-384"),
+    OidInfo::builtin("1.2.840.113549.2.11", "hmacWithSHA512", "HMAC with SHA-512"),
+    OidInfo::builtin("1.2.840.113549.3.2", "rc2-cbc", "RC2 in CBC mode"),
+    OidInfo::builtin("1.2.840.113549.3.4", "rc4", "RC4 stream cipher"),
+    OidInfo::builtin("1.2.840.113549.3.7", "des-ede3-cbc", "Triple DES in CBC mode"),
+    // ANSI X9.57 and X9.42
+    OidInfo::builtin("1.2.840.10040.4.1", "id-dsa", "DSA (ANSI X9.57)"),
+    OidInfo::builtin("1.2.840.10040.4.3", "id-dsa-with-sha1", "DSA with SHA-1 (ANSI X9.57)"),
+    OidInfo::builtin("1.2.840.10046.2.1", "dhpublicnumber", "Diffie-Hellman public number (ANSI X9.42)"),
+    // ANSI X9.62
+    OidInfo::builtin("1.2.840.10045.1.1", "prime-field", "Prime field (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.1.2", "characteristic-two-field", "Characteristic two field (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.2.1", "id-ecPublicKey", "Elliptic curve public key (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.3.1.1", "prime192v1", "NIST P-192 curve, secp192r1 (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.3.1.2", "prime192v2", "192-bit prime curve v2 (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.3.1.3", "prime192v3", "192-bit prime curve v3 (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.3.1.4", "prime239v1", "239-bit prime curve v1 (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.3.1.5", "prime239v2", "239-bit prime curve v2 (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.3.1.6", "prime239v3", "239-bit prime curve v3 (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.3.1.7", "prime256v1", "NIST P-256 curve, secp256r1 (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.4.1", "ecdsa-with-SHA1", "ECDSA with SHA-1 (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.4.3.1", "ecdsa-with-SHA224", "ECDSA with SHA-224 (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.4.3.2", "ecdsa-with-SHA256", "ECDSA with SHA-256 (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.4.3.3", "ecdsa-with-SHA384", "ECDSA with SHA-384 (ANSI X9.62)"),
+    OidInfo::builtin("1.2.840.10045.4.3.4", "ecdsa-with-SHA512", "ECDSA with SHA-512 (ANSI X9.62)"),
+    // SECG curves (SEC 2)
+    OidInfo::builtin("1.3.132.0.1", "sect163k1", "NIST K-163 curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.2", "sect163r1", "163-bit binary curve r1 (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.10", "secp256k1", "256-bit Koblitz prime curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.15", "sect163r2", "NIST B-163 curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.16", "sect283k1", "NIST K-283 curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.17", "sect283r1", "NIST B-283 curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.26", "sect233k1", "NIST K-233 curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.27", "sect233r1", "NIST B-233 curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.33", "secp224r1", "NIST P-224 curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.34", "secp384r1", "NIST P-384 curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.35", "secp521r1", "NIST P-521 curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.36", "sect409k1", "NIST K-409 curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.37", "sect409r1", "NIST B-409 curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.38", "sect571k1", "NIST K-571 curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.0.39", "sect571r1", "NIST B-571 curve (SEC 2)"),
+    OidInfo::builtin("1.3.132.1.12", "id-ecDH", "Elliptic curve Diffie-Hellman (SEC 1)"),
+    OidInfo::builtin("1.3.132.1.13", "id-ecMQV", "Elliptic curve MQV (SEC 1)"),
+    // Brainpool curves (RFC 5639)
+    OidInfo::builtin("1.3.36.3.3.2.8.1.1.7", "brainpoolP256r1", "Brainpool P-256 r1 curve (RFC 5639)"),
+    OidInfo::builtin("1.3.36.3.3.2.8.1.1.11", "brainpoolP384r1", "Brainpool P-384 r1 curve (RFC 5639)"),
+    OidInfo::builtin("1.3.36.3.3.2.8.1.1.13", "brainpoolP512r1", "Brainpool P-512 r1 curve (RFC 5639)"),
+    // Edwards and Montgomery curves (RFC 8410)
+    OidInfo::builtin("1.3.101.110", "id-X25519", "X25519 key agreement (RFC 8410)"),
+    OidInfo::builtin("1.3.101.111", "id-X448", "X448 key agreement (RFC 8410)"),
+    OidInfo::builtin("1.3.101.112", "id-Ed25519", "Ed25519 signature algorithm (RFC 8410)"),
+    OidInfo::builtin("1.3.101.113", "id-Ed448", "Ed448 signature algorithm (RFC 8410)"),
+    // OIW
+    OidInfo::builtin("1.3.14.3.2.7", "desCBC", "DES in CBC mode (OIW)"),
+    OidInfo::builtin("1.3.14.3.2.26", "id-sha1", "SHA-1 hash algorithm (OIW)"),
+    OidInfo::builtin("1.3.14.3.2.29", "sha1WithRSASignature", "SHA-1 with RSA signature (OIW)"),
+    // NIST AES
+    OidInfo::builtin("2.16.840.1.101.3.4.1.1", "id-aes128-ECB", "AES-128 in ECB mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.2", "id-aes128-CBC", "AES-128 in CBC mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.3", "id-aes128-OFB", "AES-128 in OFB mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.4", "id-aes128-CFB", "AES-128 in CFB mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.5", "id-aes128-wrap", "AES-128 key wrap (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.6", "id-aes128-GCM", "AES-128 in GCM mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.7", "id-aes128-CCM", "AES-128 in CCM mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.8", "id-aes128-wrap-pad", "AES-128 key wrap with padding (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.21", "id-aes192-ECB", "AES-192 in ECB mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.22", "id-aes192-CBC", "AES-192 in CBC mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.23", "id-aes192-OFB", "AES-192 in OFB mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.24", "id-aes192-CFB", "AES-192 in CFB mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.25", "id-aes192-wrap", "AES-192 key wrap (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.26", "id-aes192-GCM", "AES-192 in GCM mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.27", "id-aes192-CCM", "AES-192 in CCM mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.28", "id-aes192-wrap-pad", "AES-192 key wrap with padding (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.41", "id-aes256-ECB", "AES-256 in ECB mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.42", "id-aes256-CBC", "AES-256 in CBC mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.43", "id-aes256-OFB", "AES-256 in OFB mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.44", "id-aes256-CFB", "AES-256 in CFB mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.45", "id-aes256-wrap", "AES-256 key wrap (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.46", "id-aes256-GCM", "AES-256 in GCM mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.47", "id-aes256-CCM", "AES-256 in CCM mode (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.1.48", "id-aes256-wrap-pad", "AES-256 key wrap with padding (NIST)"),
+    // NIST hash algorithms
+    OidInfo::builtin("2.16.840.1.101.3.4.2.1", "id-sha256", "SHA-256 hash algorithm (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.2.2", "id-sha384", "SHA-384 hash algorithm (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.2.3", "id-sha512", "SHA-512 hash algorithm (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.2.4", "id-sha224", "SHA-224 hash algorithm (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.2.5", "id-sha512-224", "SHA-512/224 hash algorithm (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.2.6", "id-sha512-256", "SHA-512/256 hash algorithm (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.2.7", "id-sha3-224", "SHA3-224 hash algorithm (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.2.8", "id-sha3-256", "SHA3-256 hash algorithm (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.2.9", "id-sha3-384", "SHA3-384 hash algorithm (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.2.10", "id-sha3-512", "SHA3-512 hash algorithm (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.2.11", "id-shake128", "SHAKE128 extendable-output function (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.2.12", "id-shake256", "SHAKE256 extendable-output function (NIST)"),
+    // NIST signature algorithms
+    OidInfo::builtin("2.16.840.1.101.3.4.3.1", "id-dsa-with-sha224", "DSA with SHA-224 (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.2", "id-dsa-with-sha256", "DSA with SHA-256 (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.3", "id-dsa-with-sha384", "DSA with SHA-384 (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.4", "id-dsa-with-sha512", "DSA with SHA-512 (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.5", "id-dsa-with-sha3-224", "DSA with SHA3-224 (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.6", "id-dsa-with-sha3-256", "DSA with SHA3-256 (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.7", "id-dsa-with-sha3-384", "DSA with SHA3-384 (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.8", "id-dsa-with-sha3-512", "DSA with SHA3-512 (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.9", "id-ecdsa-with-sha3-224", "ECDSA with SHA3-224 (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.10", "id-ecdsa-with-sha3-256", "ECDSA with SHA3-256 (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.11", "id-ecdsa-with-sha3-384", "ECDSA with SHA3-384 (NIST)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.12", "id-ecdsa-with-sha3-512", "ECDSA with SHA3-512 (NIST)"),
+    OidInfo::builtin(
+        "2.16.840.1.101.3.4.3.13",
+        "id-rsassa-pkcs1-v1_5-with-sha3-224",
+        "RSASSA-PKCS1-v1_5 with SHA3-224 (NIST)",
+    ),
+    OidInfo::builtin(
+        "2.16.840.1.101.3.4.3.14",
+        "id-rsassa-pkcs1-v1_5-with-sha3-256",
+        "RSASSA-PKCS1-v1_5 with SHA3-256 (NIST)",
+    ),
+    OidInfo::builtin(
+        "2.16.840.1.101.3.4.3.15",
+        "id-rsassa-pkcs1-v1_5-with-sha3-384",
+        "RSASSA-PKCS1-v1_5 with SHA3-384 (NIST)",
+    ),
+    OidInfo::builtin(
+        "2.16.840.1.101.3.4.3.16",
+        "id-rsassa-pkcs1-v1_5-with-sha3-512",
+        "RSASSA-PKCS1-v1_5 with SHA3-512 (NIST)",
+    ),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.17", "id-ml-dsa-44", "ML-DSA-44 signature algorithm (FIPS 204)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.18", "id-ml-dsa-65", "ML-DSA-65 signature algorithm (FIPS 204)"),
+    OidInfo::builtin("2.16.840.1.101.3.4.3.19", "id-ml-dsa-87", "ML-DSA-87 signature algorithm (FIPS 204)"),
+    // X.520 attribute types (RFC 5280)
+    OidInfo::builtin("2.5.4.3", "commonName", "Common name (X.520)"),
+    OidInfo::builtin("2.5.4.4", "surname", "Surname (X.520)"),
+    OidInfo::builtin("2.5.4.5", "serialNumber", "Serial number (X.520)"),
+    OidInfo::builtin("2.5.4.6", "countryName", "Country name (X.520)"),
+    OidInfo::builtin("2.5.4.7", "localityName", "Locality name (X.520)"),
+    OidInfo::builtin("2.5.4.8", "stateOrProvinceName", "State or province name (X.520)"),
+    OidInfo::builtin("2.5.4.9", "streetAddress", "Street address (X.520)"),
+    OidInfo::builtin("2.5.4.10", "organizationName", "Organization name (X.520)"),
+    OidInfo::builtin("2.5.4.11", "organizationalUnitName", "Organizational unit name (X.520)"),
+    OidInfo::builtin("2.5.4.12", "title", "Title (X.520)"),
+    OidInfo::builtin("2.5.4.13", "description", "Description (X.520)"),
+    OidInfo::builtin("2.5.4.15", "businessCategory", "Business category (X.520)"),
+    OidInfo::builtin("2.5.4.17", "postalCode", "Postal code (X.520)"),
+    OidInfo::builtin("2.5.4.20", "telephoneNumber", "Telephone number (X.520)"),
+    OidInfo::builtin("2.5.4.41", "name", "Name (X.520)"),
+    OidInfo::builtin("2.5.4.42", "givenName", "Given name (X.520)"),
+    OidInfo::builtin("2.5.4.43", "initials", "Initials (X.520)"),
+    OidInfo::builtin("2.5.4.44", "generationQualifier", "Generation qualifier (X.520)"),
+    OidInfo::builtin("2.5.4.45", "x500UniqueIdentifier", "X.500 unique identifier (X.520)"),
+    OidInfo::builtin("2.5.4.46", "dnQualifier", "Distinguished name qualifier (X.520)"),
+    OidInfo::builtin("2.5.4.65", "pseudonym", "Pseudonym (X.520)"),
+    OidInfo::builtin("2.5.4.97", "organizationIdentifier", "Organization identifier (X.520)"),
+    OidInfo::builtin("2.5.8.1.1", "id-ea-rsa", "RSA encryption (X.509)"),
+    OidInfo::builtin("0.9.2342.19200300.100.1.1", "userId", "User identifier (RFC 4519)"),
+    OidInfo::builtin("0.9.2342.19200300.100.1.25", "domainComponent", "Domain component (RFC 4519)"),
+    // Certificate and CRL extensions (RFC 5280)
+    OidInfo::builtin("2.5.29.9", "subjectDirectoryAttributes", "Subject directory attributes extension"),
+    OidInfo::builtin("2.5.29.14", "subjectKeyIdentifier", "Subject key identifier extension"),
+    OidInfo::builtin("2.5.29.15", "keyUsage", "Key usage extension"),
+    OidInfo::builtin("2.5.29.16", "privateKeyUsagePeriod", "Private key usage period extension"),
+    OidInfo::builtin("2.5.29.17", "subjectAltName", "Subject alternative name extension"),
+    OidInfo::builtin("2.5.29.18", "issuerAltName", "Issuer alternative name extension"),
+    OidInfo::builtin("2.5.29.19", "basicConstraints", "Basic constraints extension"),
+    OidInfo::builtin("2.5.29.20", "cRLNumber", "CRL number extension"),
+    OidInfo::builtin("2.5.29.21", "cRLReason", "CRL reason code extension"),
+    OidInfo::builtin("2.5.29.23", "holdInstructionCode", "Hold instruction code extension"),
+    OidInfo::builtin("2.5.29.24", "invalidityDate", "Invalidity date extension"),
+    OidInfo::builtin("2.5.29.27", "deltaCRLIndicator", "Delta CRL indicator extension"),
+    OidInfo::builtin("2.5.29.28", "issuingDistributionPoint", "Issuing distribution point extension"),
+    OidInfo::builtin("2.5.29.29", "certificateIssuer", "Certificate issuer extension"),
+    OidInfo::builtin("2.5.29.30", "nameConstraints", "Name constraints extension"),
+    OidInfo::builtin("2.5.29.31", "cRLDistributionPoints", "CRL distribution points extension"),
+    OidInfo::builtin("2.5.29.32", "certificatePolicies", "Certificate policies extension"),
+    OidInfo::builtin("2.5.29.32.0", "anyPolicy", "Any certificate policy"),
+    OidInfo::builtin("2.5.29.33", "policyMappings", "Policy mappings extension"),
+    OidInfo::builtin("2.5.29.35", "authorityKeyIdentifier", "Authority key identifier extension"),
+    OidInfo::builtin("2.5.29.36", "policyConstraints", "Policy constraints extension"),
+    OidInfo::builtin("2.5.29.37", "extKeyUsage", "Extended key usage extension"),
+    OidInfo::builtin("2.5.29.37.0", "anyExtendedKeyUsage", "Any extended key usage"),
+    OidInfo::builtin("2.5.29.46", "freshestCRL", "Freshest CRL extension"),
+    OidInfo::builtin("2.5.29.54", "inhibitAnyPolicy", "Inhibit any policy extension"),
+    // PKIX (RFC 5280, RFC 6960)
+    OidInfo::builtin("1.3.6.1.5.5.7.1.1", "authorityInfoAccess", "Authority information access extension"),
+    OidInfo::builtin("1.3.6.1.5.5.7.1.3", "qcStatements", "Qualified certificate statements extension (RFC 3739)"),
+    OidInfo::builtin("1.3.6.1.5.5.7.1.11", "subjectInfoAccess", "Subject information access extension"),
+    OidInfo::builtin("1.3.6.1.5.5.7.1.24", "tlsFeature", "TLS feature extension (RFC 7633)"),
+    OidInfo::builtin("1.3.6.1.5.5.7.2.1", "id-qt-cps", "CPS pointer policy qualifier"),
+    OidInfo::builtin("1.3.6.1.5.5.7.2.2", "id-qt-unotice", "User notice policy qualifier"),
+    OidInfo::builtin("1.3.6.1.5.5.7.3.1", "id-kp-serverAuth", "TLS server authentication"),
+    OidInfo::builtin("1.3.6.1.5.5.7.3.2", "id-kp-clientAuth", "TLS client authentication"),
+    OidInfo::builtin("1.3.6.1.5.5.7.3.3", "id-kp-codeSigning", "Code signing"),
+    OidInfo::builtin("1.3.6.1.5.5.7.3.4", "id-kp-emailProtection", "Email protection"),
+    OidInfo::builtin("1.3.6.1.5.5.7.3.5", "id-kp-ipsecEndSystem", "IPsec end system"),
+    OidInfo::builtin("1.3.6.1.5.5.7.3.6", "id-kp-ipsecTunnel", "IPsec tunnel"),
+    OidInfo::builtin("1.3.6.1.5.5.7.3.7", "id-kp-ipsecUser", "IPsec user"),
+    OidInfo::builtin("1.3.6.1.5.5.7.3.8", "id-kp-timeStamping", "Time stamping"),
+    OidInfo::builtin("1.3.6.1.5.5.7.3.9", "id-kp-OCSPSigning", "OCSP signing"),
+    OidInfo::builtin("1.3.6.1.5.5.7.3.17", "id-kp-ipsecIKE", "IPsec IKE (RFC 4945)"),
+    OidInfo::builtin("1.3.6.1.5.5.7.48.1", "id-ad-ocsp", "OCSP access method"),
+    OidInfo::builtin("1.3.6.1.5.5.7.48.1.1", "id-pkix-ocsp-basic", "Basic OCSP response (RFC 6960)"),
+    OidInfo::builtin("1.3.6.1.5.5.7.48.1.2", "id-pkix-ocsp-nonce", "OCSP nonce extension (RFC 6960)"),
+    OidInfo::builtin("1.3.6.1.5.5.7.48.1.5", "id-pkix-ocsp-nocheck", "OCSP no check extension (RFC 6960)"),
+    OidInfo::builtin("1.3.6.1.5.5.7.48.2", "id-ad-caIssuers", "CA issuers access method"),
+    OidInfo::builtin("1.3.6.1.5.5.7.48.3", "id-ad-timeStamping", "Time stamping access method"),
+    OidInfo::builtin("1.3.6.1.5.5.7.48.5", "id-ad-caRepository", "CA repository access method"),
+    // CA/Browser Forum, Certificate Transparency, Let's Encrypt, Netscape
+    OidInfo::builtin("2.23.140.1.1", "ev-guidelines", "Extended validation (CA/Browser Forum)"),
+    OidInfo::builtin("2.23.140.1.2.1", "domain-validated", "Domain validated (CA/Browser Forum)"),
+    OidInfo::builtin("2.23.140.1.2.2", "organization-validated", "Organization validated (CA/Browser Forum)"),
+    OidInfo::builtin("2.23.140.1.2.3", "individual-validated", "Individual validated (CA/Browser Forum)"),
+    OidInfo::builtin(
+        "1.3.6.1.4.1.11129.2.4.2",
+        "ctPrecertificateSCTs",
+        "Embedded signed certificate timestamps (RFC 6962)",
+    ),
+    OidInfo::builtin("1.3.6.1.4.1.11129.2.4.3", "ctPrecertificatePoison", "Precertificate poison (RFC 6962)"),
+    OidInfo::builtin("1.3.6.1.4.1.44947.1.1.1", "isrg-domain-validated", "ISRG domain validated (by Let's Encrypt)"),
+    OidInfo::builtin("2.16.840.1.113730.1.1", "netscape-cert-type", "Netscape certificate type"),
+    OidInfo::builtin("2.16.840.1.113730.1.13", "netscape-comment", "Netscape comment"),
+    // Microsoft
+    OidInfo::builtin("1.3.6.1.4.1.311.2.1.4", "SPC_INDIRECT_DATA_OBJID", "Authenticode indirect data content"),
+    OidInfo::builtin("1.3.6.1.4.1.311.2.1.11", "SPC_STATEMENT_TYPE_OBJID", "Authenticode statement type"),
+    OidInfo::builtin("1.3.6.1.4.1.311.2.1.12", "SPC_SP_OPUS_INFO_OBJID", "Authenticode SpcSpOpusInfo"),
+    OidInfo::builtin("1.3.6.1.4.1.311.2.1.15", "SPC_PE_IMAGE_DATA_OBJID", "Authenticode PE image data"),
+    OidInfo::builtin("1.3.6.1.4.1.311.2.1.30", "SPC_SIPINFO_OBJID", "Authenticode SIP information"),
+    OidInfo::builtin("1.3.6.1.4.1.311.2.2.10", "NTLM", "NTLM security mechanism"),
+    OidInfo::builtin("1.3.6.1.4.1.311.2.2.30", "NEGOEX", "SPNEGO extended negotiation security mechanism"),
+    OidInfo::builtin("1.3.6.1.4.1.311.3.2.1", "SPC_TIME_STAMP_REQUEST_OBJID", "Authenticode time stamp request"),
+    OidInfo::builtin("1.3.6.1.4.1.311.3.3.1", "szOID_RFC3161_counterSign", "Timestamping signature (Ms-CounterSign)"),
+    OidInfo::builtin("1.3.6.1.4.1.311.10.1", "szOID_CTL", "Certificate trust list"),
+    OidInfo::builtin("1.3.6.1.4.1.311.10.3.1", "szOID_KP_CTL_USAGE_SIGNING", "Certificate trust list signing"),
+    OidInfo::builtin("1.3.6.1.4.1.311.10.3.3", "szOID_SERVER_GATED_CRYPTO", "Server gated cryptography"),
+    OidInfo::builtin("1.3.6.1.4.1.311.10.3.4", "szOID_EFS_CRYPTO", "Encrypting file system"),
+    OidInfo::builtin("1.3.6.1.4.1.311.10.3.9", "szOID_ROOT_LIST_SIGNER", "Root list signer"),
+    OidInfo::builtin("1.3.6.1.4.1.311.10.3.12", "szOID_KP_DOCUMENT_SIGNING", "Document signing"),
+    OidInfo::builtin("1.3.6.1.4.1.311.10.3.13", "szOID_KP_LIFETIME_SIGNING", "Lifetime signing"),
+    OidInfo::builtin("1.3.6.1.4.1.311.10.11.9", "CERT_ENHKEY_USAGE_PROP_ID", "Enhanced key usage property"),
+    OidInfo::builtin("1.3.6.1.4.1.311.10.11.11", "CERT_FRIENDLY_NAME_PROP_ID", "Friendly name property"),
+    OidInfo::builtin("1.3.6.1.4.1.311.10.11.20", "CERT_KEY_IDENTIFIER_PROP_ID", "Key identifier property"),
+    OidInfo::builtin(
+        "1.3.6.1.4.1.311.10.11.29",
+        "CERT_SUBJECT_NAME_MD5_HASH_PROP_ID",
+        "Subject name MD5 hash property",
+    ),
+    OidInfo::builtin(
+        "1.3.6.1.4.1.311.10.11.83",
+        "CERT_ROOT_PROGRAM_CERT_POLICIES_PROP_ID",
+        "Root program certificate policies property",
+    ),
+    OidInfo::builtin(
+        "1.3.6.1.4.1.311.10.11.98",
+        "CERT_AUTH_ROOT_SHA256_HASH_PROP_ID",
+        "Authority root SHA-256 hash property",
+    ),
+    OidInfo::builtin("1.3.6.1.4.1.311.10.11.104", "CERT_DISALLOWED_FILETIME_PROP_ID", "Disallowed file time property"),
+    OidInfo::builtin(
+        "1.3.6.1.4.1.311.10.11.105",
+        "CERT_ROOT_PROGRAM_CHAIN_POLICIES_PROP_ID",
+        "Root program chain policies property",
+    ),
+    OidInfo::builtin(
+        "1.3.6.1.4.1.311.10.11.122",
+        "CERT_DISALLOWED_ENHKEY_USAGE_PROP_ID",
+        "Disallowed enhanced key usage property",
+    ),
+    OidInfo::builtin("1.3.6.1.4.1.311.10.11.126", "CERT_NOT_BEFORE_FILETIME_PROP_ID", "Not before file time property"),
+    OidInfo::builtin(
+        "1.3.6.1.4.1.311.10.11.127",
+        "CERT_NOT_BEFORE_ENHKEY_USAGE_PROP_ID",
+        "Not before enhanced key usage property",
+    ),
+    OidInfo::builtin("1.3.6.1.4.1.311.13.2.1", "szOID_ENROLLMENT_NAME_VALUE_PAIR", "Enrollment name-value pair"),
+    OidInfo::builtin("1.3.6.1.4.1.311.13.2.2", "szOID_ENROLLMENT_CSP_PROVIDER", "Enrollment CSP provider"),
+    OidInfo::builtin("1.3.6.1.4.1.311.13.2.3", "szOID_OS_VERSION", "Operating system version"),
+    OidInfo::builtin(
+        "1.3.6.1.4.1.311.17.1",
+        "szOID_PKCS_12_KEY_PROVIDER_NAME_ATTR",
+        "PKCS #12 key provider name attribute",
+    ),
+    OidInfo::builtin("1.3.6.1.4.1.311.17.2", "szOID_LOCAL_MACHINE_KEYSET", "Local machine key set attribute"),
+    OidInfo::builtin("1.3.6.1.4.1.311.20.2", "szOID_ENROLL_CERTTYPE_EXTENSION", "Certificate template name"),
+    OidInfo::builtin("1.3.6.1.4.1.311.20.2.2", "szOID_KP_SMARTCARD_LOGON", "Smart card logon"),
+    OidInfo::builtin("1.3.6.1.4.1.311.20.2.3", "szOID_NT_PRINCIPAL_NAME", "User principal name"),
+    OidInfo::builtin("1.3.6.1.4.1.311.21.1", "szOID_CERTSRV_CA_VERSION", "Certificate services CA version"),
+    OidInfo::builtin(
+        "1.3.6.1.4.1.311.21.2",
+        "szOID_CERTSRV_PREVIOUS_CERT_HASH",
+        "Certificate services previous CA certificate hash",
+    ),
+    OidInfo::builtin("1.3.6.1.4.1.311.21.7", "szOID_CERTIFICATE_TEMPLATE", "Certificate template information"),
+    OidInfo::builtin("1.3.6.1.4.1.311.21.10", "szOID_APPLICATION_CERT_POLICIES", "Application certificate policies"),
+    OidInfo::builtin("1.3.6.1.4.1.311.21.20", "szOID_REQUEST_CLIENT_INFO", "Certificate request client information"),
+    OidInfo::builtin("1.3.6.1.4.1.311.25.2", "szOID_NTDS_CA_SECURITY_EXT", "Active Directory security identifier"),
+    OidInfo::builtin("1.3.6.1.4.1.311.60.3.2", "szOID_AUTO_UPDATE_END_REVOCATION", "Auto update end revocation"),
+    // Kerberos and GSS-API (RFC 1964, RFC 2743, RFC 4556)
+    OidInfo::builtin("1.2.840.113554.1.2.1.1", "GSS_C_NT_USER_NAME", "GSS-API user name type"),
+    OidInfo::builtin("1.2.840.113554.1.2.1.2", "GSS_C_NT_MACHINE_UID_NAME", "GSS-API machine UID name type"),
+    OidInfo::builtin("1.2.840.113554.1.2.1.3", "GSS_C_NT_STRING_UID_NAME", "GSS-API string UID name type"),
+    OidInfo::builtin("1.2.840.113554.1.2.1.4", "GSS_C_NT_HOSTBASED_SERVICE", "GSS-API host-based service name type"),
+    OidInfo::builtin("1.2.840.113554.1.2.2", "krb5", "Kerberos 5 GSS-API mechanism"),
+    OidInfo::builtin("1.2.840.113554.1.2.2.1", "GSS_KRB5_NT_PRINCIPAL_NAME", "Kerberos 5 principal name type"),
+    OidInfo::builtin("1.2.840.113554.1.2.2.3", "krb5-user-to-user", "Kerberos 5 user-to-user GSS-API mechanism"),
+    OidInfo::builtin("1.2.840.48018.1.2.2", "MS-KILE", "Microsoft Kerberos 5 GSS-API mechanism"),
+    OidInfo::builtin(
+        "1.3.6.1.5.6.2",
+        "GSS_C_NT_HOSTBASED_SERVICE_X",
+        "GSS-API host-based service name type (obsolete)",
+    ),
+    OidInfo::builtin("1.3.6.1.5.6.3", "GSS_C_NT_ANONYMOUS", "GSS-API anonymous name type"),
+    OidInfo::builtin("1.3.6.1.5.6.4", "GSS_C_NT_EXPORT_NAME", "GSS-API exported name type"),
+    OidInfo::builtin("1.3.6.1.5.2.2", "id-pkinit-san", "Kerberos principal name (RFC 4556)"),
+    OidInfo::builtin("1.3.6.1.5.2.3.1", "id-pkinit-authData", "PKINIT signed auth pack (RFC 4556)"),
+    OidInfo::builtin("1.3.6.1.5.2.3.2", "id-pkinit-DHKeyData", "PKINIT Diffie-Hellman key data (RFC 4556)"),
+    OidInfo::builtin("1.3.6.1.5.2.3.3", "id-pkinit-rkeyData", "PKINIT reply key pack (RFC 4556)"),
+    OidInfo::builtin("1.3.6.1.5.2.3.4", "id-pkinit-KPClientAuth", "PKINIT client authentication (RFC 4556)"),
+    OidInfo::builtin("1.3.6.1.5.2.3.5", "id-pkinit-KPKdc", "PKINIT KDC (RFC 4556)"),
+    OidInfo::builtin("1.3.6.1.5.2.7", "id-pku2u", "PKU2U GSS-API mechanism"),
+    // SPNEGO (RFC 4178)
+    OidInfo::builtin("1.3.6.1.5.5.2", "spnego", "Simple and protected GSS-API negotiation mechanism"),
+];
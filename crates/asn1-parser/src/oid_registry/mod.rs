@@ -0,0 +1,134 @@
+//! Registry of well-known object identifiers.
+//!
+//! The built-in database covers the common RFC 5280 (X.520 attributes, certificate extensions, PKIX), PKCS, ANSI
+//! X9.57/X9.62, SECG, NIST, Microsoft, Kerberos/GSS-API, and SPNEGO arcs. Every entry has a short name (e.g.
+//! `sha256WithRSAEncryption`) that is unique within the database, and a long human-readable description.
+
+mod database;
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use self::database::BUILTIN_OIDS;
+use crate::{Asn1Result, ObjectIdentifier};
+
+/// Registered object identifier: dot notation, short name, and long description
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OidInfo {
+    oid: Cow<'static, str>,
+    name: Cow<'static, str>,
+    description: Cow<'static, str>,
+}
+
+impl OidInfo {
+    const fn builtin(oid: &'static str, name: &'static str, description: &'static str) -> Self {
+        Self {
+            oid: Cow::Borrowed(oid),
+            name: Cow::Borrowed(name),
+            description: Cow::Borrowed(description),
+        }
+    }
+
+    /// Returns the object identifier in the dot notation, e.g. `1.2.840.113549.1.1.11`
+    pub fn oid(&self) -> &str {
+        &self.oid
+    }
+
+    /// Returns the short name, e.g. `sha256WithRSAEncryption`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the long description, e.g. `SHA-256 with RSA encryption (PKCS #1)`
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+/// Object identifier registry.
+///
+/// The registry is created with the built-in database. Custom object identifiers can be registered at runtime and
+/// take precedence over the built-in ones.
+///
+/// ```
+/// use asn1_parser::OidRegistry;
+///
+/// let mut registry = OidRegistry::new();
+///
+/// assert_eq!(registry.get("2.5.4.3").unwrap().name(), "commonName");
+/// assert_eq!(registry.get_by_name("rsaEncryption").unwrap().oid(), "1.2.840.113549.1.1.1");
+///
+/// registry.register("1.3.6.1.4.1.55555.1", "myAttribute", "Custom attribute").unwrap();
+/// assert_eq!(registry.get("1.3.6.1.4.1.55555.1").unwrap().description(), "Custom attribute");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OidRegistry {
+    /// Custom entries by the object identifier
+    custom: BTreeMap<String, OidInfo>,
+    /// Object identifiers of the custom entries by their names
+    custom_names: BTreeMap<String, String>,
+}
+
+impl OidRegistry {
+    /// Creates a new registry with the built-in database
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the custom object identifier. Previously registered entry with the same object identifier is
+    /// replaced. Fails if the object identifier is not a valid dot notation.
+    pub fn register(&mut self, oid: &str, name: impl Into<String>, description: impl Into<String>) -> Asn1Result<()> {
+        let oid = ObjectIdentifier::try_from(oid)?.format();
+        let name = name.into();
+
+        // the name of the replaced entry could be registered for another object identifier since then
+        if let Some(replaced) = self.custom.get(&oid) {
+            if self.custom_names.get(replaced.name()) == Some(&oid) {
+                self.custom_names.remove(replaced.name());
+            }
+        }
+
+        self.custom_names.insert(name.clone(), oid.clone());
+        self.custom.insert(
+            oid.clone(),
+            OidInfo {
+                oid: Cow::Owned(oid),
+                name: Cow::Owned(name),
+                description: Cow::Owned(description.into()),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Looks up the object identifier in the dot notation
+    pub fn get(&self, oid: &str) -> Option<&OidInfo> {
+        self.custom
+            .get(oid)
+            .or_else(|| BUILTIN_OIDS.iter().find(|info| info.oid() == oid))
+    }
+
+    /// Looks up the decoded object identifier
+    pub fn get_oid(&self, oid: &ObjectIdentifier) -> Option<&OidInfo> {
+        self.get(&oid.format())
+    }
+
+    /// Looks up the object identifier by its short name
+    pub fn get_by_name(&self, name: &str) -> Option<&OidInfo> {
+        if let Some(oid) = self.custom_names.get(name) {
+            return self.custom.get(oid);
+        }
+
+        BUILTIN_OIDS
+            .iter()
+            .find(|info| info.name() == name && !self.custom.contains_key(info.oid()))
+    }
+
+    /// Returns all known object identifiers: custom ones first, then the built-in database
+    pub fn iter(&self) -> impl Iterator<Item = &OidInfo> {
+        self.custom
+            .values()
+            .chain(BUILTIN_OIDS.iter().filter(|info| !self.custom.contains_key(info.oid())))
+    }
+}
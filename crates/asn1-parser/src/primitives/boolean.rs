@@ -1,29 +1,48 @@
-use crate::length::write_len;
 use crate::reader::Reader;
 use crate::writer::Writer;
-use crate::{Asn1Encoder, Asn1Result, Asn1ValueDecoder, Error, Tag, Taggable};
+use crate::{Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, Tag, Taggable};
 
 /// [Boolen](https://www.oss.com/asn1/resources/asn1-made-simple/asn1-quick-reference/boolean.html)
 ///
 /// The ASN.1 BOOLEAN type has two possible values: TRUE and FALSE.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
-pub struct Bool(bool);
+///
+/// BER allows any non-zero octet for TRUE, so the decoded octet is kept to re-encode the value exactly.
+/// Like other types that keep the original encoding, values are compared by the decoded value.
+#[derive(Debug, Clone, Default)]
+pub struct Bool(u8);
+
+impl PartialEq for Bool {
+    fn eq(&self, other: &Self) -> bool {
+        self.value() == other.value()
+    }
+}
+
+impl Eq for Bool {}
 
 impl Bool {
-    pub const TAG: Tag = Tag(1);
+    pub const TAG: Tag = Tag::from_byte(1);
 
     pub fn value(&self) -> bool {
-        self.0
+        self.0 != 0
     }
 
     pub fn from_byte(byte: u8) -> Self {
-        Bool(byte != 0)
+        Bool(byte)
+    }
+
+    /// Returns the encoded octet of the value
+    pub fn as_byte(&self) -> u8 {
+        self.0
+    }
+
+    pub fn set_value(&mut self, value: bool) {
+        *self = Self::from(value);
     }
 }
 
 impl From<bool> for Bool {
     fn from(flag: bool) -> Self {
-        Self(flag)
+        Self(if flag { 0xff } else { 0x00 })
     }
 }
 
@@ -38,7 +57,10 @@ impl<'data> Asn1ValueDecoder<'data> for Bool {
         let data = reader.remaining();
 
         if data.len() != 1 {
-            return Err(Error::from("Bool data len should be equal to 1"));
+            return Err(
+                Error::new(ErrorKind::InvalidValue, "Bool data len should be equal to 1")
+                    .with_offset(reader.full_offset()),
+            );
         }
 
         Ok(Bool::from_byte(data[0]))
@@ -47,19 +69,18 @@ impl<'data> Asn1ValueDecoder<'data> for Bool {
     fn compare_tags(tag: Tag) -> bool {
         Self::TAG == tag
     }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
+    }
 }
 
-impl Asn1Encoder for Bool {
-    fn needed_buf_size(&self) -> usize {
-        1 /* tag */ + 1 /* len */ + 1 /* bool value */
+impl Asn1ValueEncoder for Bool {
+    fn value_len(&self) -> usize {
+        1
     }
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        writer.write_byte(Self::TAG.into())?;
-        write_len(1, writer)?;
-        writer.write_byte(match self.0 {
-            true => 0xff,
-            false => 0,
-        })
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        writer.write_byte(self.0)
     }
 }
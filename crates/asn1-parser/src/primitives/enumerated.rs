@@ -0,0 +1,75 @@
+use alloc::vec::Vec;
+
+use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawDataStorage};
+use crate::writer::Writer;
+use crate::{Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Integer, OwnedInteger, Tag, Taggable};
+
+/// [Enumerated](https://www.oss.com/asn1/resources/asn1-made-simple/asn1-quick-reference/enumerated.html)
+///
+/// The ASN.1 ENUMERATED type is encoded in the same way as the INTEGER type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Enumerated<'data>(Integer<'data>);
+
+pub type OwnedEnumerated = Enumerated<'static>;
+
+impl<'data> Enumerated<'data> {
+    pub const TAG: Tag = Tag::from_byte(10);
+
+    /// Returns the enumerated value as an [Integer]
+    pub fn value(&self) -> &Integer<'data> {
+        &self.0
+    }
+
+    pub fn raw_data(&self) -> &[u8] {
+        self.0.raw_data()
+    }
+
+    pub fn set_value(&mut self, value: impl Into<OwnedInteger>) {
+        self.0.set_value(value);
+    }
+
+    pub fn to_owned(&self) -> OwnedEnumerated {
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedEnumerated {
+        Enumerated(self.0.to_owned_in(storage))
+    }
+}
+
+impl From<Vec<u8>> for OwnedEnumerated {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes.into())
+    }
+}
+
+impl Taggable for Enumerated<'_> {
+    fn tag(&self) -> Tag {
+        Self::TAG
+    }
+}
+
+impl<'data> Asn1ValueDecoder<'data> for Enumerated<'data> {
+    fn decode(tag: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
+        Ok(Self(Integer::decode(tag, reader)?))
+    }
+
+    fn compare_tags(tag: Tag) -> bool {
+        Self::TAG == tag
+    }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
+    }
+}
+
+impl Asn1ValueEncoder for Enumerated<'_> {
+    fn value_len(&self) -> usize {
+        self.0.value_len()
+    }
+
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        self.0.encode_value(writer)
+    }
+}
@@ -1,25 +1,28 @@
-use alloc::borrow::Cow;
 use alloc::vec::Vec;
 
-use num_bigint_dig::BigUint;
+use num_bigint_dig::{BigInt, BigUint};
+use num_traits::ToPrimitive;
 
-use crate::length::{len_size, write_len};
 use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawBytes, RawDataStorage};
 use crate::writer::Writer;
-use crate::{Asn1Encoder, Asn1Result, Asn1ValueDecoder, Tag, Taggable};
+use crate::{Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, Tag, Taggable};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Integer<'data>(Cow<'data, [u8]>);
+pub struct Integer<'data>(RawBytes<'data>);
 
 pub type OwnedInteger = Integer<'static>;
 
 impl Integer<'_> {
-    pub const TAG: Tag = Tag(2);
+    pub const TAG: Tag = Tag::from_byte(2);
 
     pub fn raw_data(&self) -> &[u8] {
         self.0.as_ref()
     }
 
+    /// Interprets the content octets as an unsigned number ignoring the sign bit.
+    ///
+    /// Use [Integer::as_big_int] to get the actual (two's complement) value.
     pub fn as_big_uint(&self) -> BigUint {
         BigUint::from_bytes_be(if self.0.len() > 1 {
             if self.0[0] == 0x00 {
@@ -34,17 +37,105 @@ impl Integer<'_> {
         })
     }
 
+    /// Interprets the content octets as a two's complement number
+    pub fn as_big_int(&self) -> BigInt {
+        BigInt::from_signed_bytes_be(&self.0)
+    }
+
+    pub fn is_negative(&self) -> bool {
+        matches!(self.0.first(), Some(byte) if byte & 0x80 != 0)
+    }
+
+    /// Replaces the integer value, e.g. with `i64`, `u64`, or `&BigInt`
+    pub fn set_value(&mut self, value: impl Into<OwnedInteger>) {
+        *self = value.into();
+    }
+
     pub fn to_owned(&self) -> OwnedInteger {
-        Integer(Cow::Owned(self.0.as_ref().to_vec()))
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedInteger {
+        Integer(storage.store_bytes(&self.0))
     }
 }
 
 impl From<Vec<u8>> for OwnedInteger {
     fn from(bytes: Vec<u8>) -> Self {
-        Self(Cow::Owned(bytes))
+        Self(RawBytes::from(bytes))
+    }
+}
+
+/// Removes redundant leading octets from the two's complement number (X.690 8.3.2)
+pub(crate) fn minimal_signed_bytes(mut bytes: &[u8]) -> &[u8] {
+    while let [first, second, ..] = bytes {
+        if (*first == 0x00 && second & 0x80 == 0) || (*first == 0xff && second & 0x80 != 0) {
+            bytes = &bytes[1..];
+        } else {
+            break;
+        }
+    }
+
+    bytes
+}
+
+impl From<i64> for OwnedInteger {
+    fn from(value: i64) -> Self {
+        Self(RawBytes::from(minimal_signed_bytes(&value.to_be_bytes()).to_vec()))
+    }
+}
+
+impl From<i32> for OwnedInteger {
+    fn from(value: i32) -> Self {
+        i64::from(value).into()
     }
 }
 
+impl From<u64> for OwnedInteger {
+    fn from(value: u64) -> Self {
+        let mut bytes = [0; 9];
+        bytes[1..].copy_from_slice(&value.to_be_bytes());
+
+        Self(RawBytes::from(minimal_signed_bytes(&bytes).to_vec()))
+    }
+}
+
+impl From<&BigInt> for OwnedInteger {
+    fn from(value: &BigInt) -> Self {
+        Self(RawBytes::from(value.to_signed_bytes_be()))
+    }
+}
+
+impl From<&BigUint> for OwnedInteger {
+    fn from(value: &BigUint) -> Self {
+        let mut bytes = Vec::from([0]);
+        bytes.extend_from_slice(&value.to_bytes_be());
+
+        Self(RawBytes::from(minimal_signed_bytes(&bytes).to_vec()))
+    }
+}
+
+macro_rules! impl_try_from_integer {
+    ($int:ty, $convert:ident) => {
+        impl TryFrom<&Integer<'_>> for $int {
+            type Error = Error;
+
+            fn try_from(integer: &Integer<'_>) -> Result<Self, Self::Error> {
+                integer.as_big_int().$convert().ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidValue,
+                        concat!("Integer value does not fit into ", stringify!($int)),
+                    )
+                })
+            }
+        }
+    };
+}
+
+impl_try_from_integer!(i32, to_i32);
+impl_try_from_integer!(i64, to_i64);
+impl_try_from_integer!(u64, to_u64);
+
 impl Taggable for Integer<'_> {
     fn tag(&self) -> Tag {
         Self::TAG
@@ -53,24 +144,24 @@ impl Taggable for Integer<'_> {
 
 impl<'data> Asn1ValueDecoder<'data> for Integer<'data> {
     fn decode(_: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
-        Ok(Self(Cow::Borrowed(reader.remaining())))
+        Ok(Self(RawBytes::from(reader.remaining())))
     }
 
     fn compare_tags(tag: Tag) -> bool {
         Self::TAG == tag
     }
-}
 
-impl Asn1Encoder for Integer<'_> {
-    fn needed_buf_size(&self) -> usize {
-        let data_len = self.0.len();
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
+    }
+}
 
-        1 /* tag */ + len_size(data_len) + data_len
+impl Asn1ValueEncoder for Integer<'_> {
+    fn value_len(&self) -> usize {
+        self.0.len()
     }
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        writer.write_byte(Self::TAG.into())?;
-        write_len(self.0.len(), writer)?;
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
         writer.write_slice(self.0.as_ref())
     }
 }
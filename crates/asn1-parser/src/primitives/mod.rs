@@ -1,9 +1,16 @@
 mod boolean;
+mod enumerated;
 mod integer;
 mod null;
 mod object_identifier;
+mod real;
+mod relative_oid;
 
 pub use boolean::Bool;
+pub use enumerated::{Enumerated, OwnedEnumerated};
+pub(crate) use integer::minimal_signed_bytes;
 pub use integer::{Integer, OwnedInteger};
 pub use null::Null;
 pub use object_identifier::ObjectIdentifier;
+pub use real::{OwnedReal, Real};
+pub use relative_oid::RelativeOid;
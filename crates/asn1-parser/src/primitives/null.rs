@@ -1,7 +1,6 @@
-use crate::length::write_len;
 use crate::reader::Reader;
 use crate::writer::Writer;
-use crate::{Asn1Encoder, Asn1Result, Asn1ValueDecoder, Error, Tag, Taggable};
+use crate::{Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, Tag, Taggable};
 
 /// [Null](https://www.oss.com/asn1/resources/asn1-made-simple/asn1-quick-reference/null.html)
 ///
@@ -12,7 +11,7 @@ use crate::{Asn1Encoder, Asn1Result, Asn1ValueDecoder, Error, Tag, Taggable};
 pub struct Null;
 
 impl Null {
-    pub const TAG: Tag = Tag(5);
+    pub const TAG: Tag = Tag::from_byte(5);
 }
 
 impl Taggable for Null {
@@ -24,7 +23,9 @@ impl Taggable for Null {
 impl<'data> Asn1ValueDecoder<'data> for Null {
     fn decode(_: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
         if !reader.remaining().is_empty() {
-            return Err(Error::from("Null data should be empty"));
+            return Err(
+                Error::new(ErrorKind::InvalidValue, "Null data should be empty").with_offset(reader.full_offset())
+            );
         }
 
         Ok(Self)
@@ -33,17 +34,18 @@ impl<'data> Asn1ValueDecoder<'data> for Null {
     fn compare_tags(tag: Tag) -> bool {
         Self::TAG == tag
     }
-}
 
-impl Asn1Encoder for Null {
-    fn needed_buf_size(&self) -> usize {
-        1 /* tag */ + 1 /* length (always zero) */
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
     }
+}
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        writer.write_byte(Self::TAG.into())?;
-        write_len(0, writer)?;
+impl Asn1ValueEncoder for Null {
+    fn value_len(&self) -> usize {
+        0
+    }
 
+    fn encode_value(&self, _: &mut Writer) -> Asn1Result<()> {
         Ok(())
     }
 }
@@ -1,29 +1,83 @@
+use alloc::format;
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::fmt::Write;
 
-use crate::length::{len_size, write_len};
+use super::relative_oid::read_subidentifiers;
 use crate::reader::Reader;
 use crate::writer::Writer;
-use crate::{Asn1Encoder, Asn1Result, Asn1ValueDecoder, Tag, Taggable};
+use crate::{Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, RelativeOid, Tag, Taggable};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ObjectIdentifier(oid::ObjectIdentifier);
+pub struct ObjectIdentifier {
+    oid: oid::ObjectIdentifier,
+    // subidentifiers of the encoded value: the first two arcs are combined into one (X.690 8.19.4).
+    // The `oid` crate encoder emits a redundant leading octet for arcs greater than or equal to 2^28, so the value
+    // is encoded the same way as the RELATIVE-OID.
+    subidentifiers: RelativeOid,
+}
 
 impl ObjectIdentifier {
-    pub const TAG: Tag = Tag(0x06);
+    pub const TAG: Tag = Tag::from_byte(0x06);
 
     pub fn oid(&self) -> &oid::ObjectIdentifier {
-        &self.0
+        &self.oid
     }
 
     pub fn format(&self) -> String {
-        { &self.0 }.into()
+        { &self.oid }.into()
+    }
+}
+
+/// Combines the first two arcs into one subidentifier
+fn subidentifiers(arcs: &[u64]) -> Asn1Result<RelativeOid> {
+    let invalid = || {
+        Error::new(
+            ErrorKind::InvalidValue,
+            "OBJECT IDENTIFIER should have at least two valid arcs",
+        )
+    };
+
+    let [first_arc, second_arc, arcs @ ..] = arcs else {
+        return Err(invalid());
+    };
+    let first = first_arc
+        .checked_mul(40)
+        .and_then(|first| first.checked_add(*second_arc))
+        .ok_or_else(invalid)?;
+
+    let mut subidentifiers = Vec::with_capacity(arcs.len() + 1);
+    subidentifiers.push(first);
+    subidentifiers.extend_from_slice(arcs);
+
+    Ok(subidentifiers.into())
+}
+
+impl TryFrom<oid::ObjectIdentifier> for ObjectIdentifier {
+    type Error = Error;
+
+    fn try_from(value: oid::ObjectIdentifier) -> Asn1Result<Self> {
+        let formatted: String = (&value).into();
+
+        Self::try_from(formatted.as_str())
     }
 }
 
-impl From<oid::ObjectIdentifier> for ObjectIdentifier {
-    fn from(value: oid::ObjectIdentifier) -> Self {
-        Self(value)
+impl TryFrom<&str> for ObjectIdentifier {
+    type Error = Error;
+
+    /// Parses the dot notation, e.g. `1.2.840.113549.1.1.11`
+    fn try_from(value: &str) -> Asn1Result<Self> {
+        let oid = oid::ObjectIdentifier::try_from(value)?;
+        let arcs = value
+            .split('.')
+            .map(|arc| arc.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            oid,
+            subidentifiers: subidentifiers(&arcs)?,
+        })
     }
 }
 
@@ -35,26 +89,50 @@ impl Taggable for ObjectIdentifier {
 
 impl Asn1ValueDecoder<'_> for ObjectIdentifier {
     fn decode(_tag: Tag, reader: &mut Reader<'_>) -> Asn1Result<Self> {
-        Ok(Self(oid::ObjectIdentifier::try_from(reader.remaining())?))
+        if reader.empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidValue,
+                "OBJECT IDENTIFIER should have at least one subidentifier",
+            )
+            .with_offset(reader.full_offset()));
+        }
+
+        // the `oid` crate decoder silently skips truncated subidentifiers and redundant leading octets,
+        // so the subidentifiers are validated here and the value is built from the dot notation
+        let subidentifiers = read_subidentifiers(reader)?;
+        let (first_arc, second_arc) = match subidentifiers[0] {
+            subidentifier @ 0..=39 => (0, subidentifier),
+            subidentifier @ 40..=79 => (1, subidentifier - 40),
+            subidentifier => (2, subidentifier - 80),
+        };
+
+        let mut formatted = format!("{}.{}", first_arc, second_arc);
+        for arc in &subidentifiers[1..] {
+            // writing into the String can not fail
+            let _ = write!(formatted, ".{}", arc);
+        }
+
+        Ok(Self {
+            oid: oid::ObjectIdentifier::try_from(formatted)?,
+            subidentifiers: subidentifiers.into(),
+        })
     }
 
     fn compare_tags(tag: Tag) -> bool {
         Self::TAG == tag
     }
-}
 
-impl Asn1Encoder for ObjectIdentifier {
-    fn needed_buf_size(&self) -> usize {
-        let encoded: Vec<u8> = self.0.clone().into();
-        encoded.len() + len_size(encoded.len()) + 1 /* tag */
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
     }
+}
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        let encoded: Vec<u8> = self.0.clone().into();
-
-        writer.write_byte(Self::TAG.into())?;
-        write_len(encoded.len(), writer)?;
+impl Asn1ValueEncoder for ObjectIdentifier {
+    fn value_len(&self) -> usize {
+        self.subidentifiers.value_len()
+    }
 
-        writer.write_slice(&encoded)
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        self.subidentifiers.encode_value(writer)
     }
 }
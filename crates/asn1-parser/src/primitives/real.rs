@@ -0,0 +1,259 @@
+use alloc::vec::Vec;
+use core::str::from_utf8;
+
+use num_traits::float::FloatCore;
+
+use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawBytes, RawDataStorage};
+use crate::writer::Writer;
+use crate::{Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, Tag, Taggable};
+
+/// Bit of the first contents octet that indicates the binary encoding
+const BINARY_ENCODING: u8 = 0x80;
+/// Bit of the first contents octet that indicates the special real value
+const SPECIAL_REAL_VALUE: u8 = 0x40;
+
+const PLUS_INFINITY: u8 = 0x40;
+const MINUS_INFINITY: u8 = 0x41;
+const NOT_A_NUMBER: u8 = 0x42;
+const MINUS_ZERO: u8 = 0x43;
+
+/// Max absolute value of the binary exponent. Any bigger exponent gives zero or infinity anyway.
+const MAX_EXPONENT: i64 = 2200;
+
+/// [Real](https://www.oss.com/asn1/resources/asn1-made-simple/asn1-quick-reference/real.html)
+///
+/// The ASN.1 REAL type can be encoded in the binary (base 2, 8, or 16), decimal (ISO 6093), or special value form (X.690 8.5).
+/// The raw contents octets are kept as is, so the value is re-encoded in the same form.
+#[derive(Debug, Clone)]
+pub struct Real<'data> {
+    data: RawBytes<'data>,
+    value: f64,
+}
+
+pub type OwnedReal = Real<'static>;
+
+impl Real<'_> {
+    pub const TAG: Tag = Tag::from_byte(9);
+
+    /// Returns decoded real value
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Returns raw contents octets
+    pub fn raw_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn to_owned(&self) -> OwnedReal {
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedReal {
+        Real {
+            data: storage.store_bytes(&self.data),
+            value: self.value,
+        }
+    }
+}
+
+/// Encodes the value using the DER rules: base 2 binary form with the odd mantissa (X.690 11.3.1).
+impl From<f64> for OwnedReal {
+    fn from(value: f64) -> Self {
+        Self {
+            data: RawBytes::from(encode_real(value)),
+            value,
+        }
+    }
+}
+
+// Different encodings of the same value are equal. Bits are compared, so NaN is equal to itself and -0 is not
+// equal to +0.
+impl PartialEq for Real<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value.to_bits() == other.value.to_bits()
+    }
+}
+
+impl Eq for Real<'_> {}
+
+impl Taggable for Real<'_> {
+    fn tag(&self) -> Tag {
+        Self::TAG
+    }
+}
+
+impl<'data> Asn1ValueDecoder<'data> for Real<'data> {
+    fn decode(_: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
+        let offset = reader.full_offset();
+        let data = reader.read_remaining();
+
+        let value = decode_real(data).map_err(|err| err.with_offset(offset))?;
+
+        Ok(Self {
+            data: RawBytes::from(data),
+            value,
+        })
+    }
+
+    fn compare_tags(tag: Tag) -> bool {
+        Self::TAG == tag
+    }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
+    }
+}
+
+impl Asn1ValueEncoder for Real<'_> {
+    fn value_len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        writer.write_slice(&self.data)
+    }
+}
+
+fn decode_real(data: &[u8]) -> Asn1Result<f64> {
+    let Some((first, data)) = data.split_first() else {
+        return Ok(0.0);
+    };
+
+    if first & BINARY_ENCODING != 0 {
+        decode_binary(*first, data)
+    } else if first & SPECIAL_REAL_VALUE != 0 {
+        if !data.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidValue,
+                "REAL special value should be one octet long",
+            ));
+        }
+
+        match *first {
+            PLUS_INFINITY => Ok(f64::INFINITY),
+            MINUS_INFINITY => Ok(f64::NEG_INFINITY),
+            NOT_A_NUMBER => Ok(f64::NAN),
+            MINUS_ZERO => Ok(-0.0),
+            _ => Err(Error::new(ErrorKind::InvalidValue, "Unknown REAL special value")),
+        }
+    } else {
+        decode_decimal(*first, data)
+    }
+}
+
+fn decode_binary(first: u8, data: &[u8]) -> Asn1Result<f64> {
+    // amount of bits in one base digit
+    let base_bits = match (first >> 4) & 0x03 {
+        0 => 1,
+        1 => 3,
+        2 => 4,
+        _ => return Err(Error::new(ErrorKind::InvalidValue, "Reserved REAL base")),
+    };
+    let scale = i64::from((first >> 2) & 0x03);
+
+    let (exponent_len, data) = match first & 0x03 {
+        3 => data
+            .split_first()
+            .map(|(len, data)| (usize::from(*len), data))
+            .ok_or_else(|| Error::new(ErrorKind::InvalidValue, "Missing REAL exponent length"))?,
+        len => (usize::from(len) + 1, data),
+    };
+
+    if exponent_len == 0 || exponent_len >= data.len() {
+        return Err(Error::new(
+            ErrorKind::InvalidValue,
+            "Invalid REAL exponent or mantissa length",
+        ));
+    }
+    if exponent_len > 4 {
+        return Err(Error::new(ErrorKind::InvalidValue, "REAL exponent is too large"));
+    }
+
+    let (exponent, mantissa) = data.split_at(exponent_len);
+
+    // two's complement number
+    let sign: i64 = if exponent[0] & 0x80 != 0 { -1 } else { 0 };
+    let exponent = exponent
+        .iter()
+        .fold(sign, |exponent, byte| (exponent << 8) | i64::from(*byte));
+    let exponent = (exponent * base_bits + scale).clamp(-MAX_EXPONENT, MAX_EXPONENT) as i32;
+
+    let mantissa = mantissa
+        .iter()
+        .fold(0.0_f64, |mantissa, byte| mantissa * 256.0 + f64::from(*byte));
+
+    // the exponent is applied in two steps to not lose subnormal values when the mantissa is big
+    let value = mantissa * FloatCore::powi(2.0_f64, exponent / 2) * FloatCore::powi(2.0_f64, exponent - exponent / 2);
+
+    Ok(if first & 0x40 != 0 { -value } else { value })
+}
+
+fn decode_decimal(first: u8, data: &[u8]) -> Asn1Result<f64> {
+    // NR1, NR2, and NR3 forms
+    if !(1..=3).contains(&(first & 0x3f)) {
+        return Err(Error::new(
+            ErrorKind::InvalidValue,
+            "Unknown REAL decimal encoding form",
+        ));
+    }
+
+    let value = from_utf8(data)?.trim_matches(' ').replace(',', ".");
+
+    Ok(value.parse::<f64>()?)
+}
+
+fn encode_real(value: f64) -> Vec<u8> {
+    if value.is_nan() {
+        return Vec::from([NOT_A_NUMBER]);
+    }
+    if value.is_infinite() {
+        return Vec::from([if value.is_sign_negative() {
+            MINUS_INFINITY
+        } else {
+            PLUS_INFINITY
+        }]);
+    }
+    if value == 0.0 {
+        return if value.is_sign_negative() {
+            Vec::from([MINUS_ZERO])
+        } else {
+            Vec::new()
+        };
+    }
+
+    let bits = value.to_bits();
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+
+    let (mut mantissa, mut exponent) = if biased_exponent == 0 {
+        // subnormal number
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), biased_exponent - 1075)
+    };
+
+    // DER requires the mantissa to be zero or odd
+    let zeros = mantissa.trailing_zeros();
+    mantissa >>= zeros;
+    exponent += zeros as i32;
+
+    // the exponent of the finite f64 value always fits into two octets
+    let exponent = exponent as i16;
+    let exponent_bytes = exponent.to_be_bytes();
+    let exponent_bytes = if i8::try_from(exponent).is_ok() {
+        &exponent_bytes[1..]
+    } else {
+        &exponent_bytes[..]
+    };
+    let mantissa_bytes = mantissa.to_be_bytes();
+    let mantissa_bytes = &mantissa_bytes[(mantissa.leading_zeros() / 8) as usize..];
+
+    let mut data = Vec::with_capacity(1 + exponent_bytes.len() + mantissa_bytes.len());
+    data.push(BINARY_ENCODING | if value.is_sign_negative() { 0x40 } else { 0 } | (exponent_bytes.len() as u8 - 1));
+    data.extend_from_slice(exponent_bytes);
+    data.extend_from_slice(mantissa_bytes);
+
+    data
+}
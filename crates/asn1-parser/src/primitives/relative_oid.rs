@@ -0,0 +1,131 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::reader::Reader;
+use crate::writer::Writer;
+use crate::{Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, Tag, Taggable};
+
+/// [Relative object identifier](https://www.oss.com/asn1/resources/asn1-made-simple/asn1-quick-reference/relative-oid.html)
+///
+/// The ASN.1 RELATIVE-OID type identifies an object relative to some known object identifier.
+/// Unlike the OBJECT IDENTIFIER, every arc is encoded separately (X.690 8.20).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelativeOid(Vec<u64>);
+
+impl RelativeOid {
+    pub const TAG: Tag = Tag::from_byte(13);
+
+    /// Returns relative object identifier arcs
+    pub fn arcs(&self) -> &[u64] {
+        &self.0
+    }
+
+    /// Formats relative object identifier using the dot notation
+    pub fn format(&self) -> String {
+        let mut formatted = String::new();
+
+        for (i, arc) in self.0.iter().enumerate() {
+            if i != 0 {
+                formatted.push('.');
+            }
+            // writing into the String can not fail
+            let _ = write!(formatted, "{}", arc);
+        }
+
+        formatted
+    }
+}
+
+impl From<Vec<u64>> for RelativeOid {
+    fn from(arcs: Vec<u64>) -> Self {
+        Self(arcs)
+    }
+}
+
+impl Taggable for RelativeOid {
+    fn tag(&self) -> Tag {
+        Self::TAG
+    }
+}
+
+impl Asn1ValueDecoder<'_> for RelativeOid {
+    fn decode(_: Tag, reader: &mut Reader<'_>) -> Asn1Result<Self> {
+        if reader.empty() {
+            return Err(
+                Error::new(ErrorKind::InvalidValue, "RELATIVE-OID should have at least one arc")
+                    .with_offset(reader.full_offset()),
+            );
+        }
+
+        Ok(Self(read_subidentifiers(reader)?))
+    }
+
+    fn compare_tags(tag: Tag) -> bool {
+        Self::TAG == tag
+    }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
+    }
+}
+
+impl Asn1ValueEncoder for RelativeOid {
+    fn value_len(&self) -> usize {
+        self.0.iter().map(|arc| arc_len(*arc)).sum()
+    }
+
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        for arc in self.0.iter() {
+            let len = arc_len(*arc);
+
+            for i in (0..len).rev() {
+                let digit = ((arc >> (7 * i)) & 0x7f) as u8;
+                writer.write_byte(if i == 0 { digit } else { digit | 0x80 })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads base-128 encoded subidentifiers of the OBJECT IDENTIFIER or RELATIVE-OID value until the end of the reader
+pub(super) fn read_subidentifiers(reader: &mut Reader<'_>) -> Asn1Result<Vec<u64>> {
+    let mut arcs = Vec::new();
+
+    while !reader.empty() {
+        let offset = reader.full_offset();
+        let mut arc: u64 = 0;
+
+        loop {
+            let byte = reader.read_byte()?;
+
+            if arc == 0 && byte == 0x80 {
+                return Err(
+                    Error::new(ErrorKind::InvalidValue, "Subidentifier has redundant leading octet")
+                        .with_offset(offset),
+                );
+            }
+            if arc.leading_zeros() < 7 {
+                return Err(Error::new(ErrorKind::InvalidValue, "Subidentifier is too big").with_offset(offset));
+            }
+
+            arc = (arc << 7) | u64::from(byte & 0x7f);
+
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+
+        arcs.push(arc);
+    }
+
+    Ok(arcs)
+}
+
+/// Returns the amount of base-128 digits needed to encode the arc
+fn arc_len(arc: u64) -> usize {
+    let bits = (u64::BITS - arc.leading_zeros()).max(1) as usize;
+
+    (bits + 6) / 7
+}
@@ -0,0 +1,297 @@
+//! Path-based queries over the decoded asn1 tree.
+
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::visitor::children;
+use crate::{
+    Asn1, Asn1Result, Asn1Type, Asn1Visitor, Error, ErrorKind, Tag, TagClass, Taggable, VisitContext, VisitFlow,
+};
+
+/// Universal types that can be used in paths
+const TYPE_NAMES: &[(&str, u32)] = &[
+    ("bool", 1),
+    ("int", 2),
+    ("bit", 3),
+    ("octet", 4),
+    ("null", 5),
+    ("oid", 6),
+    ("descriptor", 7),
+    ("real", 9),
+    ("enum", 10),
+    ("utf8", 12),
+    ("roid", 13),
+    ("seq", 16),
+    ("set", 17),
+    ("numeric", 18),
+    ("printable", 19),
+    ("teletex", 20),
+    ("videotex", 21),
+    ("ia5", 22),
+    ("utc", 23),
+    ("gtime", 24),
+    ("graphic", 25),
+    ("visible", 26),
+    ("general", 27),
+    ("universal", 28),
+    ("bmp", 30),
+];
+
+const TAG_CLASSES: &[(&str, TagClass)] = &[
+    ("univ", TagClass::Universal),
+    ("app", TagClass::Application),
+    ("ctx", TagClass::ContextSpecific),
+    ("priv", TagClass::Private),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selector {
+    Index(usize),
+    Any,
+    Tag(TagClass, Option<u32>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Node { selector: Selector, value: Option<String> },
+    Descendants,
+}
+
+/// Compact path to the asn1 nodes.
+///
+/// The path is a list of segments separated by `/` or `.`. Every segment selects nodes among the children
+/// of the nodes selected by the previous segment. The first segment selects among the root node itself.
+///
+/// Supported segments:
+/// * `2`: the third child.
+/// * `*`: any child.
+/// * `**`: any descendant (zero or more levels deep).
+/// * `seq`, `int`, `oid`, `utf8`, etc: children of the corresponding universal type.
+/// * `[3]` or `ctx[3]`: children with the context-specific tag number 3. `app[3]`, `priv[3]`, and `univ[3]`
+///   are used for other tag classes. The tag number can be omitted (`ctx`) to match any tag of the class.
+///
+/// Any segment except `**` can be followed by the value filter: `oid=2.5.4.3`, `int=5`, `bool=true`,
+/// `utf8=hello`. The value lasts until the next `/`, so it can contain dots.
+///
+/// Examples: `0.2.[1].oid`, `seq/ctx[3]/seq/int`, `**/oid=2.5.4.3`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asn1Path {
+    segments: Vec<Segment>,
+}
+
+impl Asn1Path {
+    /// Parses the path. The error offset points to the invalid segment.
+    pub fn parse(path: &str) -> Asn1Result<Self> {
+        let mut segments = Vec::new();
+        let mut rest = path.trim();
+        let mut offset = path.len() - path.trim_start().len();
+
+        while !rest.is_empty() {
+            let segment_len = segment_len(rest);
+            let segment = parse_segment(&rest[..segment_len]).map_err(|error| error.with_offset(offset))?;
+
+            // consecutive `**` select the same nodes
+            if segment != Segment::Descendants || segments.last() != Some(&Segment::Descendants) {
+                segments.push(segment);
+            }
+
+            let consumed = (segment_len + 1).min(rest.len());
+            rest = &rest[consumed..];
+            offset += consumed;
+        }
+
+        if segments.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidPath, "Empty path").with_offset(0));
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Returns all nodes matched by the path
+    pub fn find<'a>(&self, root: &'a Asn1<'_>) -> Vec<&'a Asn1<'a>> {
+        let mut groups: Vec<Vec<&'a Asn1<'a>>> = vec![vec![root]];
+        let mut matched = Vec::new();
+
+        for (index, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Descendants => {
+                    groups = groups.into_iter().flat_map(|group| expand(group)).collect();
+                    matched = groups.concat();
+                }
+                Segment::Node { selector, value } => {
+                    matched = groups
+                        .iter()
+                        .flat_map(|group| select(selector, group))
+                        .filter(|node| {
+                            value
+                                .as_ref()
+                                .map_or(true, |value| node_value(node).as_ref() == Some(value))
+                        })
+                        .collect();
+
+                    if index + 1 < self.segments.len() {
+                        groups = matched.iter().map(|node| children(node)).collect();
+                    }
+                }
+            }
+        }
+
+        matched
+    }
+}
+
+/// Returns all nodes of the tree matching the predicate in the tree order
+pub fn find_all<'a>(root: &'a Asn1<'_>, predicate: impl Fn(&Asn1<'_>) -> bool) -> Vec<&'a Asn1<'a>> {
+    let mut filter = Filter {
+        predicate,
+        matched: Vec::new(),
+    };
+    filter.visit(root);
+
+    filter.matched
+}
+
+struct Filter<'a, P> {
+    predicate: P,
+    matched: Vec<&'a Asn1<'a>>,
+}
+
+impl<'a, P: Fn(&Asn1<'_>) -> bool> Asn1Visitor<'a> for Filter<'a, P> {
+    fn enter_node(&mut self, node: &'a Asn1<'a>, _context: &VisitContext<'_>) -> VisitFlow {
+        if (self.predicate)(node) {
+            self.matched.push(node);
+        }
+
+        VisitFlow::Continue
+    }
+}
+
+/// Returns the node value in the same format as it is used in the path value filter
+pub fn node_value(asn1: &Asn1<'_>) -> Option<String> {
+    Some(match asn1.inner_asn1() {
+        Asn1Type::Bool(boolean) => boolean.value().to_string(),
+        Asn1Type::Integer(integer) => integer.as_big_int().to_string(),
+        Asn1Type::Enumerated(enumerated) => enumerated.value().as_big_int().to_string(),
+        Asn1Type::ObjectIdentifier(oid) => oid.format(),
+        Asn1Type::RelativeOid(oid) => oid.format(),
+        Asn1Type::Utf8String(string) => string.string().into(),
+        Asn1Type::NumericString(string) => string.string().into(),
+        Asn1Type::PrintableString(string) => string.string().into(),
+        Asn1Type::IA5String(string) => string.string().into(),
+        Asn1Type::VisibleString(string) => string.string().into(),
+        Asn1Type::GeneralString(string) => string.string().into(),
+        Asn1Type::UniversalString(string) => string.string(),
+        Asn1Type::TeletexString(string) => string.string(),
+        Asn1Type::VideotexString(string) => string.string(),
+        Asn1Type::GraphicString(string) => string.string(),
+        Asn1Type::ObjectDescriptor(string) => string.string(),
+        _ => return None,
+    })
+}
+
+/// Returns the segment length in bytes. The value filter can contain dots, so it lasts until the next slash.
+fn segment_len(path: &str) -> usize {
+    let end = match path.find('=') {
+        Some(value_start) if path[..value_start].find(['/', '.']).is_none() => {
+            return path.find('/').unwrap_or(path.len());
+        }
+        _ => path.find(['/', '.']),
+    };
+
+    end.unwrap_or(path.len())
+}
+
+fn parse_segment(segment: &str) -> Asn1Result<Segment> {
+    let (selector, value) = match segment.split_once('=') {
+        Some((selector, value)) => (selector.trim(), Some(value.trim().to_string())),
+        None => (segment.trim(), None),
+    };
+
+    if selector == "**" {
+        return match value {
+            Some(_) => Err(Error::new(
+                ErrorKind::InvalidPath,
+                "Value filter is not allowed after `**`",
+            )),
+            None => Ok(Segment::Descendants),
+        };
+    }
+
+    Ok(Segment::Node {
+        selector: parse_selector(selector)?,
+        value,
+    })
+}
+
+fn parse_selector(selector: &str) -> Asn1Result<Selector> {
+    if selector == "*" {
+        return Ok(Selector::Any);
+    }
+
+    if let Ok(index) = selector.parse::<usize>() {
+        return Ok(Selector::Index(index));
+    }
+
+    if let Some(&(_, number)) = TYPE_NAMES.iter().find(|(name, _)| *name == selector) {
+        return Ok(Selector::Tag(TagClass::Universal, Some(number)));
+    }
+
+    let (class, number) = match selector.find('[') {
+        Some(bracket) => (&selector[..bracket], Some(&selector[bracket..])),
+        None => (selector, None),
+    };
+    let class = match class {
+        "" if number.is_some() => TagClass::ContextSpecific,
+        class => TAG_CLASSES
+            .iter()
+            .find(|(name, _)| *name == class)
+            .map(|(_, class)| *class)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidPath, "Unknown path segment"))?,
+    };
+    let number = number
+        .map(|number| {
+            number
+                .strip_prefix('[')
+                .and_then(|number| number.strip_suffix(']'))
+                .and_then(|number| number.trim().parse::<u32>().ok())
+                .ok_or_else(|| Error::new(ErrorKind::InvalidPath, "Invalid tag number"))
+        })
+        .transpose()?;
+
+    Ok(Selector::Tag(class, number))
+}
+
+fn select<'a>(selector: &Selector, nodes: &[&'a Asn1<'a>]) -> Vec<&'a Asn1<'a>> {
+    match selector {
+        Selector::Index(index) => nodes.get(*index).copied().into_iter().collect(),
+        Selector::Any => nodes.to_vec(),
+        Selector::Tag(class, number) => nodes
+            .iter()
+            .copied()
+            .filter(|node| matches_tag(node.inner_asn1().tag(), *class, *number))
+            .collect(),
+    }
+}
+
+fn matches_tag(tag: Tag, class: TagClass, number: Option<u32>) -> bool {
+    tag.class() == class && number.map_or(true, |number| tag.number() == number)
+}
+
+/// Returns the sibling groups reachable from the group: the group itself and the children of every descendant
+fn expand<'a>(group: Vec<&'a Asn1<'a>>) -> Vec<Vec<&'a Asn1<'a>>> {
+    let mut groups = vec![group];
+    let mut index = 0;
+
+    while index < groups.len() {
+        let children = groups[index]
+            .iter()
+            .map(|node| children(node))
+            .filter(|children| !children.is_empty())
+            .collect::<Vec<_>>();
+        groups.extend(children);
+        index += 1;
+    }
+
+    groups
+}
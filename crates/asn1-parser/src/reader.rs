@@ -1,6 +1,36 @@
+use alloc::vec::Vec;
 use core::ops::Range;
 
-use crate::{Asn1Result, Error};
+use crate::{Asn1Result, Error, ErrorKind};
+
+/// Limits for decoding the untrusted input.
+///
+/// Decoding fails with the [ErrorKind::LimitExceeded] error when any limit is hit. Default limits are safe for
+/// the small stack of the WASM environment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Maximum nesting depth of the decoded tree. The root entity has depth 0
+    pub max_depth: usize,
+    /// Maximum amount of decoded asn1 entities, including the encapsulated ones
+    pub max_nodes: usize,
+    /// Try to decode the payload of primitive OCTET STRING, BIT STRING, and IMPLICIT tagged entities as asn1
+    pub decode_encapsulated: bool,
+}
+
+impl DecodeOptions {
+    pub const DEFAULT_MAX_DEPTH: usize = 64;
+    pub const DEFAULT_MAX_NODES: usize = 1_000_000;
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+            max_nodes: Self::DEFAULT_MAX_NODES,
+            decode_encapsulated: true,
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Reader<'data> {
@@ -8,6 +38,13 @@ pub struct Reader<'data> {
     offset: usize,
     position: usize,
     inner: &'data [u8],
+    // collected decoding errors. `Some` only in the lenient mode
+    diagnostics: Option<Vec<Error>>,
+    options: DecodeOptions,
+    // nesting depth of the entities read by this reader
+    depth: usize,
+    // amount of entities decoded so far, shared with nested readers
+    nodes: usize,
 }
 
 impl<'data> Reader<'data> {
@@ -17,9 +54,94 @@ impl<'data> Reader<'data> {
             next_node_id: 0,
             position: 0,
             inner: data,
+            diagnostics: None,
+            options: DecodeOptions::default(),
+            depth: 0,
+            nodes: 0,
+        }
+    }
+
+    pub fn with_options(data: &'data [u8], options: DecodeOptions) -> Self {
+        Self {
+            options,
+            ..Self::new(data)
         }
     }
 
+    pub fn options(&self) -> &DecodeOptions {
+        &self.options
+    }
+
+    /// Creates a reader for the contents octets of the current entity.
+    ///
+    /// The nested reader is one level deeper and shares the node ids and limits with this reader. Call
+    /// [Reader::finish_nested] to take them back after decoding.
+    pub(crate) fn nested(&mut self, data: &'data [u8], offset: usize) -> Reader<'data> {
+        let mut reader = Reader::with_options(data, self.options);
+        reader.set_next_id(self.next_id());
+        reader.set_offset(offset);
+        reader.depth = self.depth + 1;
+        reader.nodes = self.nodes;
+
+        reader
+    }
+
+    /// Creates a reader for the asn1 entities encapsulated into the contents octets read by this reader.
+    ///
+    /// Unlike [Reader::nested], the reader is not one level deeper: this reader is already nested into the entity
+    /// whose contents octets are decoded. Call [Reader::finish_nested] to take the node ids and limits back.
+    pub(crate) fn encapsulated(&mut self, data: &'data [u8], offset: usize) -> Reader<'data> {
+        let mut reader = self.nested(data, offset);
+        reader.depth = self.depth;
+
+        reader
+    }
+
+    pub(crate) fn finish_nested(&mut self, nested: &Reader<'data>) {
+        self.set_next_id(nested.next_node_id);
+        self.nodes = nested.nodes;
+    }
+
+    /// Checks decoding limits before reading the next entity and counts it
+    pub(crate) fn enter_node(&mut self) -> Asn1Result<()> {
+        if self.depth > self.options.max_depth {
+            return Err(
+                Error::new(ErrorKind::LimitExceeded, "Maximum nesting depth exceeded").with_offset(self.full_offset())
+            );
+        }
+
+        if self.nodes >= self.options.max_nodes {
+            return Err(Error::new(ErrorKind::LimitExceeded, "Maximum amount of nodes exceeded")
+                .with_offset(self.full_offset()));
+        }
+        self.nodes += 1;
+
+        Ok(())
+    }
+
+    /// Enables the lenient decoding mode.
+    ///
+    /// In this mode, decoders try to recover from errors and report them as diagnostics.
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.diagnostics = if lenient { Some(Vec::new()) } else { None };
+    }
+
+    pub fn is_lenient(&self) -> bool {
+        self.diagnostics.is_some()
+    }
+
+    /// Saves the error as a diagnostic. Does nothing if the reader is not in the lenient mode.
+    pub fn push_diagnostic(&mut self, error: Error) {
+        if let Some(diagnostics) = self.diagnostics.as_mut() {
+            diagnostics.push(error);
+        }
+    }
+
+    /// Returns collected diagnostics and clears them
+    pub fn take_diagnostics(&mut self) -> Vec<Error> {
+        self.diagnostics.as_mut().map(core::mem::take).unwrap_or_default()
+    }
+
     pub fn set_offset(&mut self, offset: usize) {
         self.offset = offset;
     }
@@ -37,6 +159,10 @@ impl<'data> Reader<'data> {
         self.position
     }
 
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position.min(self.inner.len());
+    }
+
     pub fn full_offset(&self) -> usize {
         self.offset + self.position
     }
@@ -57,15 +183,18 @@ impl<'data> Reader<'data> {
 
     pub fn data_in_range(&self, range: Range<usize>) -> Asn1Result<&'data [u8]> {
         if range.end > self.inner.len() {
-            return Err(Error::from("Invalid range"));
+            return Err(
+                Error::new(ErrorKind::UnexpectedEof, "Invalid range").with_offset(self.offset + self.inner.len())
+            );
         }
 
         Ok(&self.inner[range])
     }
 
     pub fn read(&mut self, len: usize) -> Asn1Result<&'data [u8]> {
-        if self.position + len > self.inner.len() {
-            return Err(Error::from("Outside"));
+        // `position + len` can overflow for lengths decoded from the untrusted input
+        if len > self.inner.len() - self.position {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "Outside").with_offset(self.full_offset()));
         }
 
         let data = &self.inner[self.position..(self.position + len)];
@@ -87,7 +216,7 @@ impl<'data> Reader<'data> {
 
     pub fn peek_byte(&self) -> Asn1Result<u8> {
         if self.position == self.inner.len() {
-            return Err(Error::from("End of the buffer"));
+            return Err(Error::new(ErrorKind::UnexpectedEof, "End of the buffer").with_offset(self.full_offset()));
         }
 
         Ok(self.inner[self.position])
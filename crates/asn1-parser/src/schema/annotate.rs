@@ -0,0 +1,305 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+
+use super::{Asn1Schema, Component, SchemaType};
+use crate::{Asn1, Asn1Entity, Asn1Type, Tag};
+
+/// Max amount of nested type references followed without matching a node. It protects from reference cycles.
+const MAX_REFERENCE_DEPTH: usize = 32;
+
+/// Unknown (e.g. imported) types match any node
+static UNKNOWN_TYPE: SchemaType = SchemaType::Any;
+
+/// Schema information about the asn1 node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaAnnotation {
+    /// Component names from the root separated by dots, e.g. `tbsCertificate.validity.notBefore`.
+    /// SEQUENCE OF and SET OF elements are denoted by their index (`extensions[0]`). It's empty for the root node.
+    pub path: String,
+    /// Name of the type from the schema or the built-in type name
+    pub type_name: String,
+}
+
+/// Annotates the node and its children if the node tag matches the type
+pub fn annotate_node(
+    schema: &Asn1Schema,
+    ty: &SchemaType,
+    name: Option<String>,
+    node: &Asn1<'_>,
+    path: &str,
+    annotations: &mut BTreeMap<u64, SchemaAnnotation>,
+) {
+    annotate_node_in_choices(schema, ty, name, node, path, annotations, &mut Vec::new());
+}
+
+/// Annotates the node using the type that can be an alternative of the already entered CHOICEs.
+/// The entered CHOICEs are not entered again, so recursive CHOICEs do not cause an infinite recursion.
+fn annotate_node_in_choices<'s>(
+    schema: &'s Asn1Schema,
+    ty: &'s SchemaType,
+    name: Option<String>,
+    node: &Asn1<'_>,
+    path: &str,
+    annotations: &mut BTreeMap<u64, SchemaAnnotation>,
+    choices: &mut Vec<&'s SchemaType>,
+) {
+    let (ty, name) = resolve(schema, ty, name);
+
+    if !matches_tag_visiting(schema, ty, node.tag(), &mut choices.clone()) {
+        return;
+    }
+
+    match ty {
+        SchemaType::Choice(alternatives) => {
+            choices.push(ty);
+
+            if let Some(alternative) = alternatives
+                .iter()
+                .find(|alternative| matches_tag_visiting(schema, &alternative.ty, node.tag(), &mut choices.clone()))
+            {
+                let name = format!("{} ({})", name.as_deref().unwrap_or("CHOICE"), alternative.name);
+                annotate_node_in_choices(schema, &alternative.ty, Some(name), node, path, annotations, choices);
+            }
+        }
+        SchemaType::Tagged { explicit, inner, .. } => {
+            let (inner_ty, inner_name) = resolve(schema, inner, None);
+
+            // tags of the CHOICE and open types are always explicit (X.680 31.2.7)
+            if *explicit || matches!(inner_ty, SchemaType::Choice(_) | SchemaType::Any) {
+                annotate_content(
+                    schema,
+                    ty,
+                    name.unwrap_or_else(|| type_label(ty)),
+                    node,
+                    path,
+                    annotations,
+                );
+
+                if let Some([child]) = children(node) {
+                    annotate_node(schema, inner, None, child, path, annotations);
+                }
+            } else {
+                let name = name.or(inner_name).unwrap_or_else(|| type_label(inner_ty));
+                annotate_content(schema, inner_ty, name, node, path, annotations);
+            }
+        }
+        _ => annotate_content(
+            schema,
+            ty,
+            name.unwrap_or_else(|| type_label(ty)),
+            node,
+            path,
+            annotations,
+        ),
+    }
+}
+
+/// Annotates the node and matches its children against the type components
+fn annotate_content(
+    schema: &Asn1Schema,
+    ty: &SchemaType,
+    type_name: String,
+    node: &Asn1<'_>,
+    path: &str,
+    annotations: &mut BTreeMap<u64, SchemaAnnotation>,
+) {
+    annotations.insert(
+        node.id(),
+        SchemaAnnotation {
+            path: path.into(),
+            type_name,
+        },
+    );
+
+    let Some(children) = children(node) else {
+        return;
+    };
+
+    match ty {
+        SchemaType::Sequence(components) => {
+            let components = flatten_components(schema, components, 0);
+            let mut next = 0;
+
+            for child in children {
+                let Some(position) = components[next..]
+                    .iter()
+                    .position(|component| matches_tag(schema, &component.ty, child.tag()))
+                else {
+                    break;
+                };
+
+                // the node does not fit the schema if the required component is missing
+                if components[next..next + position]
+                    .iter()
+                    .any(|component| !component.optional)
+                {
+                    break;
+                }
+
+                let component = components[next + position];
+                annotate_node(
+                    schema,
+                    &component.ty,
+                    None,
+                    child,
+                    &join(path, &component.name),
+                    annotations,
+                );
+
+                next += position + 1;
+            }
+        }
+        SchemaType::Set(components) => {
+            let components = flatten_components(schema, components, 0);
+            let mut used = vec![false; components.len()];
+
+            for child in children {
+                let Some(index) = (0..components.len())
+                    .find(|index| !used[*index] && matches_tag(schema, &components[*index].ty, child.tag()))
+                else {
+                    continue;
+                };
+
+                used[index] = true;
+                let component = components[index];
+                annotate_node(
+                    schema,
+                    &component.ty,
+                    None,
+                    child,
+                    &join(path, &component.name),
+                    annotations,
+                );
+            }
+        }
+        SchemaType::SequenceOf(element) | SchemaType::SetOf(element) => {
+            for (index, child) in children.iter().enumerate() {
+                annotate_node(
+                    schema,
+                    element,
+                    None,
+                    child,
+                    &format!("{}[{}]", path, index),
+                    annotations,
+                );
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Follows type references. The name of the first reference is used as the type name.
+fn resolve<'s>(
+    schema: &'s Asn1Schema,
+    ty: &'s SchemaType,
+    mut name: Option<String>,
+) -> (&'s SchemaType, Option<String>) {
+    let mut ty = ty;
+
+    for _ in 0..MAX_REFERENCE_DEPTH {
+        let SchemaType::Reference(reference) = ty else {
+            return (ty, name);
+        };
+
+        name.get_or_insert_with(|| reference.clone());
+
+        match schema.get(reference) {
+            Some(referenced) => ty = referenced,
+            None => return (&UNKNOWN_TYPE, name),
+        }
+    }
+
+    (&UNKNOWN_TYPE, name)
+}
+
+/// Checks if the node with the tag can be of this type. The constructed bit is not compared.
+fn matches_tag(schema: &Asn1Schema, ty: &SchemaType, tag: Tag) -> bool {
+    matches_tag_visiting(schema, ty, tag, &mut Vec::new())
+}
+
+/// Every CHOICE is checked at most once: an already visited CHOICE either did not match or is being checked
+/// right now (a recursive CHOICE), so recursive CHOICEs take linear time instead of exponential.
+fn matches_tag_visiting<'s>(
+    schema: &'s Asn1Schema,
+    ty: &'s SchemaType,
+    tag: Tag,
+    visited: &mut Vec<&'s SchemaType>,
+) -> bool {
+    let same_tag = |expected: Tag| expected.class() == tag.class() && expected.number() == tag.number();
+
+    match resolve(schema, ty, None).0 {
+        SchemaType::Builtin(_, expected) => same_tag(*expected),
+        SchemaType::Sequence(_) | SchemaType::SequenceOf(_) => same_tag(Tag::from_byte(0x10)),
+        SchemaType::Set(_) | SchemaType::SetOf(_) => same_tag(Tag::from_byte(0x11)),
+        choice @ SchemaType::Choice(alternatives) => {
+            if visited.iter().any(|visited| core::ptr::eq(*visited, choice)) {
+                return false;
+            }
+            visited.push(choice);
+
+            alternatives
+                .iter()
+                .any(|alternative| matches_tag_visiting(schema, &alternative.ty, tag, visited))
+        }
+        SchemaType::Any => true,
+        SchemaType::Tagged { tag: expected, .. } => same_tag(*expected),
+        SchemaType::Reference(_) => false,
+    }
+}
+
+/// Replaces `COMPONENTS OF Type` with the components of the referenced type
+fn flatten_components<'s>(schema: &'s Asn1Schema, components: &'s [Component], depth: usize) -> Vec<&'s Component> {
+    let mut flattened = Vec::new();
+
+    for component in components {
+        if !component.components_of {
+            flattened.push(component);
+            continue;
+        }
+
+        if depth > MAX_REFERENCE_DEPTH {
+            continue;
+        }
+
+        if let (SchemaType::Sequence(inner) | SchemaType::Set(inner), _) = resolve(schema, &component.ty, None) {
+            flattened.extend(flatten_components(schema, inner, depth + 1));
+        }
+    }
+
+    flattened
+}
+
+fn children<'a>(node: &'a Asn1<'_>) -> Option<&'a [Asn1<'a>]> {
+    match node.inner_asn1() {
+        Asn1Type::Sequence(sequence) => Some(sequence.fields()),
+        Asn1Type::Set(set) => Some(set.fields()),
+        Asn1Type::ExplicitTag(explicit) => Some(explicit.inner()),
+        Asn1Type::ApplicationTag(application) => Some(application.inner()),
+        Asn1Type::Unknown(unknown) => unknown.fields(),
+        _ => None,
+    }
+}
+
+fn type_label(ty: &SchemaType) -> String {
+    match ty {
+        SchemaType::Builtin(name, _) => (*name).into(),
+        SchemaType::Reference(name) => name.clone(),
+        SchemaType::Sequence(_) => "SEQUENCE".into(),
+        SchemaType::Set(_) => "SET".into(),
+        SchemaType::SequenceOf(_) => "SEQUENCE OF".into(),
+        SchemaType::SetOf(_) => "SET OF".into(),
+        SchemaType::Choice(_) => "CHOICE".into(),
+        SchemaType::Any => "ANY".into(),
+        SchemaType::Tagged { tag, .. } => tag.to_string(),
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.into()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
@@ -0,0 +1,166 @@
+use alloc::vec::Vec;
+
+use crate::{Asn1Result, Error, ErrorKind};
+
+/// Lexical item of the ASN.1 notation (X.680 12)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// Type reference, identifier, or keyword
+    Word(&'a str),
+    Number(&'a str),
+    /// Character string (`"..."`), binary string (`'...'B`), or hexadecimal string (`'...'H`)
+    Text(&'a str),
+    /// Assignment (`::=`), range (`..`), ellipsis (`...`), version brackets (`[[`, `]]`), or a single character
+    Symbol(&'a str),
+}
+
+/// Token and its position in the schema text
+#[derive(Debug, Clone, Copy)]
+pub struct Lexeme<'a> {
+    pub token: Token<'a>,
+    pub offset: usize,
+}
+
+const MULTI_CHAR_SYMBOLS: [&str; 5] = ["::=", "...", "..", "[[", "]]"];
+
+/// Splits the ASN.1 module into tokens skipping whitespaces and comments
+pub fn tokenize(module: &str) -> Asn1Result<Vec<Lexeme<'_>>> {
+    let bytes = module.as_bytes();
+    let mut lexemes = Vec::new();
+    let mut position = 0;
+
+    while position < bytes.len() {
+        let byte = bytes[position];
+        let rest = &module[position..];
+
+        if byte.is_ascii_whitespace() {
+            position += 1;
+        } else if rest.starts_with("--") {
+            position += single_line_comment_len(rest);
+        } else if rest.starts_with("/*") {
+            position += multi_line_comment_len(rest)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidSchema, "Unterminated comment").with_offset(position))?;
+        } else if byte.is_ascii_alphabetic() {
+            let len = word_len(rest);
+            lexemes.push(Lexeme {
+                token: Token::Word(&rest[..len]),
+                offset: position,
+            });
+            position += len;
+        } else if byte.is_ascii_digit() {
+            let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+            lexemes.push(Lexeme {
+                token: Token::Number(&rest[..len]),
+                offset: position,
+            });
+            position += len;
+        } else if byte == b'"' || byte == b'\'' {
+            let len = text_len(rest)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidSchema, "Unterminated string").with_offset(position))?;
+            lexemes.push(Lexeme {
+                token: Token::Text(&rest[..len]),
+                offset: position,
+            });
+            position += len;
+        } else {
+            let len = MULTI_CHAR_SYMBOLS
+                .iter()
+                .find(|symbol| rest.starts_with(*symbol))
+                .map(|symbol| symbol.len())
+                .unwrap_or_else(|| rest.chars().next().map(char::len_utf8).unwrap_or(1));
+            lexemes.push(Lexeme {
+                token: Token::Symbol(&rest[..len]),
+                offset: position,
+            });
+            position += len;
+        }
+    }
+
+    Ok(lexemes)
+}
+
+/// The comment ends with the next `--` or at the end of the line (X.680 12.6.3)
+fn single_line_comment_len(comment: &str) -> usize {
+    let bytes = comment.as_bytes();
+    let mut len = 2;
+
+    while len < bytes.len() {
+        if bytes[len] == b'\n' || bytes[len] == b'\r' {
+            return len;
+        }
+        if bytes[len..].starts_with(b"--") {
+            return len + 2;
+        }
+        len += 1;
+    }
+
+    len
+}
+
+/// Multi-line comments can be nested (X.680 12.6.4)
+fn multi_line_comment_len(comment: &str) -> Option<usize> {
+    let bytes = comment.as_bytes();
+    let mut depth = 0;
+    let mut len = 0;
+
+    while len < bytes.len() {
+        if bytes[len..].starts_with(b"/*") {
+            depth += 1;
+            len += 2;
+        } else if bytes[len..].starts_with(b"*/") {
+            depth -= 1;
+            len += 2;
+
+            if depth == 0 {
+                return Some(len);
+            }
+        } else {
+            len += 1;
+        }
+    }
+
+    None
+}
+
+/// Words consist of letters, digits, and hyphens. A hyphen can not be the last character or followed by another hyphen.
+fn word_len(word: &str) -> usize {
+    let bytes = word.as_bytes();
+    let mut len = 1;
+
+    while len < bytes.len() {
+        match bytes[len] {
+            byte if byte.is_ascii_alphanumeric() => len += 1,
+            b'-' if bytes.get(len + 1).map(u8::is_ascii_alphanumeric).unwrap_or_default() => len += 1,
+            _ => break,
+        }
+    }
+
+    len
+}
+
+/// Returns the length of the quoted string including the `B`/`H` suffix of binary and hexadecimal strings
+fn text_len(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let quote = bytes[0];
+    let mut len = 1;
+
+    loop {
+        if *bytes.get(len)? == quote {
+            // doubled quotation mark is an escaped one
+            if quote == b'"' && bytes.get(len + 1) == Some(&b'"') {
+                len += 2;
+                continue;
+            }
+
+            len += 1;
+            break;
+        }
+        len += 1;
+    }
+
+    if quote == b'\'' && matches!(bytes.get(len), Some(b'B' | b'H')) {
+        len += 1;
+    }
+
+    Some(len)
+}
@@ -0,0 +1,99 @@
+//! ASN.1 module (schema) notation parser.
+//!
+//! The parsed schema is used to annotate the decoded asn1 tree with field and type names.
+
+mod annotate;
+mod lexer;
+mod parser;
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub use self::annotate::SchemaAnnotation;
+use crate::{Asn1, Asn1Result, Tag};
+
+/// Default tagging of the module (X.680 13.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TagDefault {
+    Explicit,
+    Implicit,
+    Automatic,
+}
+
+/// ASN.1 type definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SchemaType {
+    /// Built-in simple type: name from the notation and its universal tag
+    Builtin(&'static str, Tag),
+    /// Reference to the type defined in the module
+    Reference(String),
+    Sequence(Vec<Component>),
+    Set(Vec<Component>),
+    SequenceOf(Box<SchemaType>),
+    SetOf(Box<SchemaType>),
+    Choice(Vec<Component>),
+    /// ANY or open type: any asn1 entity matches it
+    Any,
+    Tagged {
+        tag: Tag,
+        explicit: bool,
+        inner: Box<SchemaType>,
+    },
+}
+
+/// SEQUENCE or SET component, or CHOICE alternative
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Component {
+    name: String,
+    ty: SchemaType,
+    /// OPTIONAL or DEFAULT component
+    optional: bool,
+    /// `COMPONENTS OF Type`: components of the referenced type are included instead of this one
+    components_of: bool,
+}
+
+/// Parsed ASN.1 module.
+///
+/// Only type assignments are kept. Value assignments, constraints, and named numbers are skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asn1Schema {
+    /// Type names in the definition order
+    names: Vec<String>,
+    types: BTreeMap<String, SchemaType>,
+}
+
+impl Asn1Schema {
+    /// Parses the ASN.1 module. The module header (`... DEFINITIONS ::= BEGIN`) is optional.
+    pub fn parse(module: &str) -> Asn1Result<Self> {
+        parser::parse_module(module)
+    }
+
+    /// Returns names of the defined types in the definition order
+    pub fn type_names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Matches the asn1 tree against the type and returns the schema annotations for the matched nodes.
+    ///
+    /// The matching is lenient: nodes that do not fit the schema are left without annotation.
+    pub fn annotate(&self, type_name: &str, asn1: &Asn1<'_>) -> BTreeMap<u64, SchemaAnnotation> {
+        let mut annotations = BTreeMap::new();
+
+        annotate::annotate_node(
+            self,
+            &SchemaType::Reference(type_name.into()),
+            None,
+            asn1,
+            "",
+            &mut annotations,
+        );
+
+        annotations
+    }
+
+    fn get(&self, name: &str) -> Option<&SchemaType> {
+        self.types.get(name)
+    }
+}
@@ -0,0 +1,507 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::lexer::{tokenize, Lexeme, Token};
+use super::{Asn1Schema, Component, SchemaType, TagDefault};
+use crate::{Asn1Result, Error, ErrorKind, Tag, TagClass};
+
+/// Parses ASN.1 module(s) and collects all type assignments
+pub fn parse_module(module: &str) -> Asn1Result<Asn1Schema> {
+    let mut parser = Parser {
+        lexemes: tokenize(module)?,
+        position: 0,
+        end_offset: module.len(),
+        tag_default: TagDefault::Explicit,
+    };
+    let mut schema = Asn1Schema {
+        names: Vec::new(),
+        types: BTreeMap::new(),
+    };
+
+    while parser.peek().is_some() {
+        parser.module_header()?;
+
+        while parser.peek().is_some() {
+            if parser.eat_word("END") {
+                break;
+            }
+
+            if let Some((name, ty)) = parser.assignment()? {
+                if schema.types.insert(name.clone(), ty).is_none() {
+                    schema.names.push(name);
+                }
+            }
+        }
+    }
+
+    Ok(schema)
+}
+
+struct Parser<'a> {
+    lexemes: Vec<Lexeme<'a>>,
+    position: usize,
+    end_offset: usize,
+    tag_default: TagDefault,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<Token<'a>> {
+        self.lexemes.get(self.position).map(|lexeme| lexeme.token)
+    }
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.peek();
+        if token.is_some() {
+            self.position += 1;
+        }
+
+        token
+    }
+
+    fn error(&self, message: &'static str) -> Error {
+        let offset = self
+            .lexemes
+            .get(self.position)
+            .map(|lexeme| lexeme.offset)
+            .unwrap_or(self.end_offset);
+
+        Error::new(ErrorKind::InvalidSchema, message).with_offset(offset)
+    }
+
+    fn is_word(&self, word: &str) -> bool {
+        self.peek() == Some(Token::Word(word))
+    }
+
+    fn is_symbol(&self, symbol: &str) -> bool {
+        self.peek() == Some(Token::Symbol(symbol))
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        let is_word = self.is_word(word);
+        if is_word {
+            self.position += 1;
+        }
+
+        is_word
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        let is_symbol = self.is_symbol(symbol);
+        if is_symbol {
+            self.position += 1;
+        }
+
+        is_symbol
+    }
+
+    fn expect_word(&mut self, word: &str, message: &'static str) -> Asn1Result<()> {
+        if self.eat_word(word) {
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: &str, message: &'static str) -> Asn1Result<()> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    /// Skips tokens between the opening symbol and the matching closing one
+    fn skip_balanced(&mut self, open: &str, close: &str) -> Asn1Result<()> {
+        self.expect_symbol(open, "Expected opening bracket")?;
+
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next() {
+                Some(Token::Symbol(symbol)) if symbol == open => depth += 1,
+                Some(Token::Symbol(symbol)) if symbol == close => depth -= 1,
+                Some(_) => {}
+                None => return Err(self.error("Unbalanced brackets")),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Skips tokens until the component separator (`,`, `}`, or `]]`) outside of any brackets
+    fn skip_until_separator(&mut self) -> Asn1Result<()> {
+        let mut depth = 0_usize;
+
+        loop {
+            match self.peek() {
+                Some(Token::Symbol("," | "}" | "]]")) if depth == 0 => return Ok(()),
+                Some(Token::Symbol("(" | "{" | "[")) => depth += 1,
+                Some(Token::Symbol(")" | "}" | "]")) => depth = depth.saturating_sub(1),
+                Some(_) => {}
+                None => return Err(self.error("Unexpected end of the module")),
+            }
+            self.position += 1;
+        }
+    }
+
+    /// Parses the optional module header: `ModuleName [{ oid }] DEFINITIONS [TagDefault TAGS] ::= BEGIN`
+    fn module_header(&mut self) -> Asn1Result<()> {
+        let start = self.position;
+
+        if matches!(self.next(), Some(Token::Word(_))) {
+            if self.is_symbol("{") {
+                self.skip_balanced("{", "}")?;
+            }
+
+            if self.eat_word("DEFINITIONS") {
+                loop {
+                    if self.eat_word("EXPLICIT") {
+                        self.tag_default = TagDefault::Explicit;
+                    } else if self.eat_word("IMPLICIT") {
+                        self.tag_default = TagDefault::Implicit;
+                    } else if self.eat_word("AUTOMATIC") {
+                        self.tag_default = TagDefault::Automatic;
+                    } else if !(self.eat_word("TAGS") || self.eat_word("EXTENSIBILITY") || self.eat_word("IMPLIED")) {
+                        break;
+                    }
+                }
+
+                self.expect_symbol("::=", "Expected `::=` after the module definition")?;
+                self.expect_word("BEGIN", "Expected `BEGIN` after the module definition")?;
+
+                for section in ["EXPORTS", "IMPORTS"] {
+                    if self.eat_word(section) {
+                        while !self.eat_symbol(";") {
+                            if self.next().is_none() {
+                                return Err(self.error("Expected `;` at the end of the exports/imports"));
+                            }
+                        }
+                    }
+                }
+
+                return Ok(());
+            }
+        }
+
+        self.position = start;
+
+        Ok(())
+    }
+
+    /// Parses the type or value assignment. Returns `None` for value assignments.
+    fn assignment(&mut self) -> Asn1Result<Option<(String, SchemaType)>> {
+        let Some(Token::Word(name)) = self.peek() else {
+            return Err(self.error("Expected type or value assignment"));
+        };
+        self.position += 1;
+
+        if is_type_reference(name) {
+            if self.is_symbol("{") {
+                return Err(self.error("Parameterized types are not supported"));
+            }
+            self.expect_symbol("::=", "Expected `::=` after the type name")?;
+
+            Ok(Some((name.into(), self.ty()?)))
+        } else {
+            self.ty()?;
+            self.expect_symbol("::=", "Expected `::=` after the value type")?;
+            self.skip_value()?;
+
+            Ok(None)
+        }
+    }
+
+    fn skip_value(&mut self) -> Asn1Result<()> {
+        if self.is_symbol("{") {
+            return self.skip_balanced("{", "}");
+        }
+
+        self.eat_symbol("-");
+        self.next().map(|_| ()).ok_or_else(|| self.error("Expected value"))
+    }
+
+    /// Parses the type with its constraints
+    fn ty(&mut self) -> Asn1Result<SchemaType> {
+        let ty = self.unconstrained_type()?;
+
+        while self.is_symbol("(") {
+            self.skip_balanced("(", ")")?;
+        }
+
+        Ok(ty)
+    }
+
+    fn unconstrained_type(&mut self) -> Asn1Result<SchemaType> {
+        if self.is_symbol("[") {
+            return self.tagged_type();
+        }
+
+        let Some(Token::Word(word)) = self.peek() else {
+            return Err(self.error("Expected type"));
+        };
+        let error = self.error("Expected type");
+        self.position += 1;
+
+        if let Some((name, tag)) = builtin_type(word) {
+            // BIT STRING, OCTET STRING, OBJECT IDENTIFIER, etc
+            if let Some(second_word) = name.split(' ').nth(1) {
+                self.expect_word(second_word, "Invalid built-in type name")?;
+            }
+            // named numbers and named bits
+            if matches!(word, "INTEGER" | "ENUMERATED" | "BIT") && self.is_symbol("{") {
+                self.skip_balanced("{", "}")?;
+            }
+
+            return Ok(SchemaType::Builtin(name, Tag::from_byte(tag)));
+        }
+
+        match word {
+            "ANY" => {
+                if self.eat_word("DEFINED") {
+                    self.expect_word("BY", "Expected `BY` after `ANY DEFINED`")?;
+                    self.next();
+                }
+
+                Ok(SchemaType::Any)
+            }
+            "SEQUENCE" | "SET" => {
+                if self.is_symbol("{") {
+                    let components = self.components()?;
+
+                    return Ok(if word == "SEQUENCE" {
+                        SchemaType::Sequence(components)
+                    } else {
+                        SchemaType::Set(components)
+                    });
+                }
+
+                self.eat_word("SIZE");
+                if self.is_symbol("(") {
+                    self.skip_balanced("(", ")")?;
+                }
+                self.expect_word("OF", "Expected `{` or `OF`")?;
+
+                // SEQUENCE OF can contain the element name
+                if matches!(self.peek(), Some(Token::Word(name)) if !is_type_reference(name)) {
+                    self.position += 1;
+                }
+
+                let element = Box::new(self.ty()?);
+
+                Ok(if word == "SEQUENCE" {
+                    SchemaType::SequenceOf(element)
+                } else {
+                    SchemaType::SetOf(element)
+                })
+            }
+            "CHOICE" => Ok(SchemaType::Choice(self.components()?)),
+            "CLASS" | "INSTANCE" => Err(error),
+            name if is_type_reference(name) => {
+                let mut name = name;
+
+                // external type reference: `ModuleName.TypeName`
+                if self.eat_symbol(".") {
+                    match self.next() {
+                        Some(Token::Word(type_name)) => name = type_name,
+                        // information object class field: `CLASS.&Type`
+                        Some(Token::Symbol("&")) => {
+                            self.next();
+                            return Ok(SchemaType::Any);
+                        }
+                        _ => return Err(self.error("Expected type name")),
+                    }
+                }
+
+                // actual parameters of the parameterized type
+                if self.is_symbol("{") {
+                    self.skip_balanced("{", "}")?;
+                }
+
+                Ok(SchemaType::Reference(name.into()))
+            }
+            _ => Err(error),
+        }
+    }
+
+    /// Parses `[class number] [IMPLICIT | EXPLICIT] Type`
+    fn tagged_type(&mut self) -> Asn1Result<SchemaType> {
+        self.expect_symbol("[", "Expected tag")?;
+
+        let class = if self.eat_word("UNIVERSAL") {
+            TagClass::Universal
+        } else if self.eat_word("APPLICATION") {
+            TagClass::Application
+        } else if self.eat_word("PRIVATE") {
+            TagClass::Private
+        } else {
+            TagClass::ContextSpecific
+        };
+
+        let number = match self.peek() {
+            Some(Token::Number(number)) => number.parse::<u32>().map_err(|_| self.error("Invalid tag number"))?,
+            _ => return Err(self.error("Expected tag number")),
+        };
+        self.position += 1;
+        self.expect_symbol("]", "Expected `]` after the tag number")?;
+
+        let explicit = if self.eat_word("EXPLICIT") {
+            true
+        } else if self.eat_word("IMPLICIT") {
+            false
+        } else {
+            self.tag_default == TagDefault::Explicit
+        };
+
+        Ok(SchemaType::Tagged {
+            tag: Tag::new(class, false, number),
+            explicit,
+            inner: Box::new(self.ty()?),
+        })
+    }
+
+    /// Parses SEQUENCE and SET components or CHOICE alternatives
+    fn components(&mut self) -> Asn1Result<Vec<Component>> {
+        self.expect_symbol("{", "Expected `{`")?;
+
+        let mut components = Vec::new();
+
+        if !self.eat_symbol("}") {
+            loop {
+                if self.eat_symbol("[[") {
+                    // extension addition group with the optional version number
+                    if matches!(self.peek(), Some(Token::Number(_))) {
+                        self.position += 1;
+                        self.expect_symbol(":", "Expected `:` after the version number")?;
+                    }
+
+                    loop {
+                        self.component(&mut components)?;
+
+                        if !self.eat_symbol(",") {
+                            break;
+                        }
+                    }
+                    self.expect_symbol("]]", "Expected `]]` at the end of the extension addition group")?;
+                } else {
+                    self.component(&mut components)?;
+                }
+
+                if !self.eat_symbol(",") {
+                    break;
+                }
+            }
+
+            self.expect_symbol("}", "Expected `,` or `}`")?;
+        }
+
+        // X.680 25.3
+        let is_tagged = components
+            .iter()
+            .any(|component| !component.components_of && matches!(component.ty, SchemaType::Tagged { .. }));
+        if self.tag_default == TagDefault::Automatic && !is_tagged {
+            let untagged = components.iter_mut().filter(|component| !component.components_of);
+            for (number, component) in (0..).zip(untagged) {
+                let ty = core::mem::replace(&mut component.ty, SchemaType::Any);
+                component.ty = SchemaType::Tagged {
+                    tag: Tag::new(TagClass::ContextSpecific, false, number),
+                    explicit: false,
+                    inner: Box::new(ty),
+                };
+            }
+        }
+
+        Ok(components)
+    }
+
+    /// Parses one component and adds it to the list. Extension markers are skipped.
+    fn component(&mut self, components: &mut Vec<Component>) -> Asn1Result<()> {
+        if self.eat_symbol("...") {
+            // exception specification
+            if self.eat_symbol("!") {
+                self.skip_until_separator()?;
+            }
+
+            return Ok(());
+        }
+
+        if self.eat_word("COMPONENTS") {
+            self.expect_word("OF", "Expected `OF` after `COMPONENTS`")?;
+
+            components.push(Component {
+                name: String::new(),
+                ty: self.ty()?,
+                optional: false,
+                components_of: true,
+            });
+
+            return Ok(());
+        }
+
+        let name = match self.peek() {
+            Some(Token::Word(name)) if !is_type_reference(name) => name,
+            _ => return Err(self.error("Expected component name")),
+        };
+        self.position += 1;
+
+        let ty = self.ty()?;
+
+        let optional = if self.eat_word("OPTIONAL") {
+            true
+        } else if self.eat_word("DEFAULT") {
+            self.skip_until_separator()?;
+            true
+        } else {
+            false
+        };
+
+        components.push(Component {
+            name: name.into(),
+            ty,
+            optional,
+            components_of: false,
+        });
+
+        Ok(())
+    }
+}
+
+/// Type references start with an upper-case letter, identifiers start with a lower-case one (X.680 12.2, 12.3)
+fn is_type_reference(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// Returns the full name and the universal tag of the built-in type by its first word
+fn builtin_type(word: &str) -> Option<(&'static str, u8)> {
+    Some(match word {
+        "BOOLEAN" => ("BOOLEAN", 0x01),
+        "INTEGER" => ("INTEGER", 0x02),
+        "BIT" => ("BIT STRING", 0x03),
+        "OCTET" => ("OCTET STRING", 0x04),
+        "NULL" => ("NULL", 0x05),
+        "OBJECT" => ("OBJECT IDENTIFIER", 0x06),
+        "ObjectDescriptor" => ("ObjectDescriptor", 0x07),
+        "EXTERNAL" => ("EXTERNAL", 0x28),
+        "REAL" => ("REAL", 0x09),
+        "ENUMERATED" => ("ENUMERATED", 0x0a),
+        "EMBEDDED" => ("EMBEDDED PDV", 0x2b),
+        "UTF8String" => ("UTF8String", 0x0c),
+        "RELATIVE-OID" => ("RELATIVE-OID", 0x0d),
+        "NumericString" => ("NumericString", 0x12),
+        "PrintableString" => ("PrintableString", 0x13),
+        "TeletexString" => ("TeletexString", 0x14),
+        "T61String" => ("T61String", 0x14),
+        "VideotexString" => ("VideotexString", 0x15),
+        "IA5String" => ("IA5String", 0x16),
+        "UTCTime" => ("UTCTime", 0x17),
+        "GeneralizedTime" => ("GeneralizedTime", 0x18),
+        "GraphicString" => ("GraphicString", 0x19),
+        "VisibleString" => ("VisibleString", 0x1a),
+        "ISO646String" => ("ISO646String", 0x1a),
+        "GeneralString" => ("GeneralString", 0x1b),
+        "UniversalString" => ("UniversalString", 0x1c),
+        "CHARACTER" => ("CHARACTER STRING", 0x3d),
+        "BMPString" => ("BMPString", 0x1e),
+        _ => return None,
+    })
+}
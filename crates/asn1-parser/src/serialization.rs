@@ -0,0 +1,286 @@
+//! Serde support for asn1 trees.
+//!
+//! The asn1 tree is serialized into the stable human-readable structure:
+//!
+//! ```json
+//! {
+//!   "type": "Sequence",
+//!   "tag": { "class": "universal", "constructed": true, "number": 16 },
+//!   "offset": 0,
+//!   "children": [
+//!     {
+//!       "type": "Integer",
+//!       "tag": { "class": "universal", "constructed": false, "number": 2 },
+//!       "offset": 2,
+//!       "value": "5"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! The `value` of the primitive node is rendered according to its tag: BOOLEAN as `true`/`false`, INTEGER and
+//! ENUMERATED in decimal, OBJECT IDENTIFIER and RELATIVE-OID in the dot notation, character strings and times as text,
+//! and everything else as hex-encoded contents octets. The constructed node has `children`, or the hex-encoded
+//! `value` if its contents can not be represented as a list of asn1 nodes (e.g. segmented BER strings).
+//!
+//! The `type` and `offset` fields are informational and ignored by the deserialization. `children` of the primitive
+//! nodes are the decoded view of the encapsulated asn1 entity, so they are ignored too. The deserialized tree is
+//! encoded and decoded again, so it has valid offsets and node ids.
+
+use alloc::borrow::ToOwned;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::str::from_utf8;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::edit::parse_big_int;
+use crate::visitor::children;
+use crate::writer::Writer;
+use crate::{
+    node_value, write_len, write_tag, Asn1, Asn1Decoder, Asn1Entity, Asn1Result, Asn1Type, Asn1ValueEncoder, Bool,
+    Error, ErrorKind, Null, ObjectIdentifier, OwnedAsn1, OwnedInteger, RelativeOid, Tag, TagClass,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SerializedTag {
+    class: TagClass,
+    constructed: bool,
+    number: u32,
+}
+
+impl From<Tag> for SerializedTag {
+    fn from(tag: Tag) -> Self {
+        Self {
+            class: tag.class(),
+            constructed: tag.is_constructed(),
+            number: tag.number(),
+        }
+    }
+}
+
+impl From<&SerializedTag> for Tag {
+    fn from(tag: &SerializedTag) -> Self {
+        Tag::new(tag.class, tag.constructed, tag.number)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SerializedAsn1 {
+    #[serde(rename = "type", default)]
+    type_name: String,
+    tag: SerializedTag,
+    #[serde(default)]
+    offset: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    children: Vec<SerializedAsn1>,
+}
+
+impl From<&Asn1<'_>> for SerializedAsn1 {
+    fn from(asn1: &Asn1<'_>) -> Self {
+        let tag = asn1.tag();
+        let fields = match asn1.inner_asn1() {
+            Asn1Type::Sequence(_) | Asn1Type::Set(_) | Asn1Type::ExplicitTag(_) | Asn1Type::ApplicationTag(_) => true,
+            Asn1Type::Unknown(unknown) => unknown.fields().is_some(),
+            _ => false,
+        };
+
+        let value = if tag.is_constructed() && fields {
+            None
+        } else {
+            render_value(asn1)
+        };
+
+        Self {
+            type_name: type_name(asn1.inner_asn1()).to_owned(),
+            tag: tag.into(),
+            offset: asn1.meta().tag_position(),
+            value,
+            children: children(asn1).into_iter().map(SerializedAsn1::from).collect(),
+        }
+    }
+}
+
+impl SerializedAsn1 {
+    /// Encodes the node using its tag, value, and children
+    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
+        if self.type_name == "Unparsed" {
+            return Err(Error::new(
+                ErrorKind::InvalidValue,
+                "Unparsed nodes can not be deserialized",
+            ));
+        }
+
+        let tag = Tag::from(&self.tag);
+        let contents = match (&self.value, tag.is_constructed()) {
+            (Some(value), false) => parse_value(tag, value)?,
+            (Some(value), true) => decode_hex(value)?,
+            (None, false) if tag == Null::TAG => Vec::new(),
+            (None, false) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidValue,
+                    "Primitive node should have a value",
+                ))
+            }
+            (None, true) => {
+                let mut writer = Writer::growable();
+                for child in &self.children {
+                    child.encode(&mut writer)?;
+                }
+                writer.into_vec()?
+            }
+        };
+
+        write_tag(tag, writer)?;
+        write_len(contents.len(), writer)?;
+        writer.write_slice(&contents)
+    }
+}
+
+impl Serialize for Asn1<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedAsn1::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for OwnedAsn1 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let serialized = SerializedAsn1::deserialize(deserializer)?;
+
+        let mut writer = Writer::growable();
+        let encoded = serialized
+            .encode(&mut writer)
+            .and_then(|_| writer.into_vec())
+            .map_err(de::Error::custom)?;
+        let asn1 = Asn1::decode_buff(&encoded).map_err(de::Error::custom)?;
+
+        Ok(asn1.to_shared())
+    }
+}
+
+fn type_name(asn1: &Asn1Type<'_>) -> &'static str {
+    match asn1 {
+        Asn1Type::Sequence(_) => "Sequence",
+        Asn1Type::Set(_) => "Set",
+        Asn1Type::OctetString(_) => "OctetString",
+        Asn1Type::Utf8String(_) => "Utf8String",
+        Asn1Type::BitString(_) => "BitString",
+        Asn1Type::BmpString(_) => "BmpString",
+        Asn1Type::IA5String(_) => "IA5String",
+        Asn1Type::PrintableString(_) => "PrintableString",
+        Asn1Type::GeneralString(_) => "GeneralString",
+        Asn1Type::NumericString(_) => "NumericString",
+        Asn1Type::VisibleString(_) => "VisibleString",
+        Asn1Type::UniversalString(_) => "UniversalString",
+        Asn1Type::TeletexString(_) => "TeletexString",
+        Asn1Type::VideotexString(_) => "VideotexString",
+        Asn1Type::GraphicString(_) => "GraphicString",
+        Asn1Type::ObjectDescriptor(_) => "ObjectDescriptor",
+        Asn1Type::UtcTime(_) => "UtcTime",
+        Asn1Type::GeneralizedTime(_) => "GeneralizedTime",
+        Asn1Type::Bool(_) => "Bool",
+        Asn1Type::Null(_) => "Null",
+        Asn1Type::Integer(_) => "Integer",
+        Asn1Type::Enumerated(_) => "Enumerated",
+        Asn1Type::Real(_) => "Real",
+        Asn1Type::ObjectIdentifier(_) => "ObjectIdentifier",
+        Asn1Type::RelativeOid(_) => "RelativeOid",
+        Asn1Type::ExplicitTag(_) => "ExplicitTag",
+        Asn1Type::ImplicitTag(_) => "ImplicitTag",
+        Asn1Type::ApplicationTag(_) => "ApplicationTag",
+        Asn1Type::Unknown(_) => "Unknown",
+        Asn1Type::Unparsed(_) => "Unparsed",
+    }
+}
+
+fn render_value(asn1: &Asn1<'_>) -> Option<String> {
+    if let Asn1Type::Null(_) = asn1.inner_asn1() {
+        return None;
+    }
+
+    let contents = asn1.inner_asn1().contents();
+
+    let rendered = match asn1.inner_asn1() {
+        Asn1Type::UtcTime(_) | Asn1Type::GeneralizedTime(_) => from_utf8(&contents).ok().map(ToString::to_string),
+        _ if asn1.tag().is_universal() && asn1.tag().is_primitive() => node_value(asn1),
+        _ => None,
+    };
+
+    Some(rendered.unwrap_or_else(|| encode_hex(&contents)))
+}
+
+/// Parses the value of the primitive node into contents octets
+fn parse_value(tag: Tag, value: &str) -> Asn1Result<Vec<u8>> {
+    if !tag.is_universal() {
+        return decode_hex(value);
+    }
+
+    let mut writer = Writer::growable();
+    match tag.number() {
+        1 => match value {
+            "true" => Bool::from(true),
+            "false" => Bool::from(false),
+            _ => return Err(Error::new(ErrorKind::InvalidValue, "Invalid BOOLEAN value")),
+        }
+        .encode_value(&mut writer)?,
+        2 | 10 => OwnedInteger::from(&parse_big_int(value)?).encode_value(&mut writer)?,
+        6 => ObjectIdentifier::try_from(value)?.encode_value(&mut writer)?,
+        13 => RelativeOid::from(
+            value
+                .split('.')
+                .map(|arc| arc.parse::<u64>())
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+        .encode_value(&mut writer)?,
+        12 | 18 | 19 | 22 | 23 | 24 | 26 | 27 => writer.write_slice(value.as_bytes())?,
+        // byte strings are rendered as Latin-1
+        7 | 20 | 21 | 25 => {
+            for c in value.chars() {
+                let byte = u8::try_from(u32::from(c))
+                    .map_err(|_| Error::new(ErrorKind::InvalidValue, "Byte string value should be Latin-1 text"))?;
+                writer.write_byte(byte)?;
+            }
+        }
+        28 => {
+            for c in value.chars() {
+                writer.write_slice(&u32::from(c).to_be_bytes())?;
+            }
+        }
+        _ => return decode_hex(value),
+    }
+
+    writer.into_vec()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    use core::fmt::Write;
+
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        // writing into the string can not fail
+        let _ = write!(hex, "{:02x}", byte);
+    }
+
+    hex
+}
+
+/// Decodes hex string. Whitespaces between the bytes are allowed
+fn decode_hex(value: &str) -> Asn1Result<Vec<u8>> {
+    let digits = value
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .map(|c| c.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidValue, "Invalid hex value"))?;
+
+    if digits.len() % 2 == 1 {
+        return Err(Error::new(
+            ErrorKind::InvalidValue,
+            "Hex value should have an even amount of digits",
+        ));
+    }
+
+    Ok(digits.chunks(2).map(|pair| (pair[0] << 4) | pair[1]).collect())
+}
@@ -0,0 +1,193 @@
+//! Owned asn1 trees that share one reference-counted copy of the input data.
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Deref, Range};
+
+use crate::{Asn1, OwnedAsn1, OwnedRawAsn1EntityData, RawAsn1EntityData};
+
+/// Raw bytes of the asn1 entity.
+///
+/// Decoded entities borrow their bytes from the input buffer. Owned entities keep a range of the reference-counted
+/// buffer, so the nodes of the tree created by [Asn1::to_shared] point into the same allocation and cloning them
+/// does not copy any data.
+#[derive(Clone)]
+pub struct RawBytes<'data>(Repr<'data>);
+
+pub type OwnedRawBytes = RawBytes<'static>;
+
+#[derive(Clone)]
+enum Repr<'data> {
+    Borrowed(&'data [u8]),
+    Shared { buffer: Arc<[u8]>, range: Range<usize> },
+}
+
+impl OwnedRawBytes {
+    /// Creates raw bytes that point into the shared buffer.
+    ///
+    /// Returns `None` if the range is outside the buffer.
+    pub fn shared(buffer: Arc<[u8]>, range: Range<usize>) -> Option<Self> {
+        buffer.get(range.clone())?;
+
+        Some(Self(Repr::Shared { buffer, range }))
+    }
+}
+
+impl RawBytes<'_> {
+    pub fn as_slice(&self) -> &[u8] {
+        match &self.0 {
+            Repr::Borrowed(bytes) => bytes,
+            Repr::Shared { buffer, range } => &buffer[range.clone()],
+        }
+    }
+
+    /// Returns the reference-counted buffer if the bytes are shared
+    pub fn shared_buffer(&self) -> Option<&Arc<[u8]>> {
+        match &self.0 {
+            Repr::Borrowed(_) => None,
+            Repr::Shared { buffer, .. } => Some(buffer),
+        }
+    }
+
+    /// Returns owned version of the [RawBytes]. Shared bytes are not copied
+    pub fn to_owned(&self) -> OwnedRawBytes {
+        match &self.0 {
+            Repr::Borrowed([]) => RawBytes::default(),
+            Repr::Borrowed(bytes) => RawBytes::from(bytes.to_vec()),
+            Repr::Shared { buffer, range } => RawBytes(Repr::Shared {
+                buffer: Arc::clone(buffer),
+                range: range.clone(),
+            }),
+        }
+    }
+}
+
+impl Default for RawBytes<'_> {
+    fn default() -> Self {
+        Self(Repr::Borrowed(&[]))
+    }
+}
+
+impl<'data> From<&'data [u8]> for RawBytes<'data> {
+    fn from(bytes: &'data [u8]) -> Self {
+        Self(Repr::Borrowed(bytes))
+    }
+}
+
+impl From<Vec<u8>> for OwnedRawBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        let range = 0..bytes.len();
+
+        Self(Repr::Shared {
+            buffer: bytes.into(),
+            range,
+        })
+    }
+}
+
+impl Deref for RawBytes<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for RawBytes<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl PartialEq for RawBytes<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for RawBytes<'_> {}
+
+impl fmt::Debug for RawBytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+/// Storage of the raw data of the owned asn1 entities
+pub(crate) trait RawDataStorage {
+    fn store(&self, raw: &RawAsn1EntityData<'_>) -> OwnedRawAsn1EntityData;
+
+    /// Stores the contents octets kept by the asn1 value, e.g. OCTET STRING octets or INTEGER bytes
+    fn store_bytes(&self, bytes: &RawBytes<'_>) -> OwnedRawBytes;
+}
+
+/// Every owned entity gets its own copy of the raw data
+pub(crate) struct CopiedRawData;
+
+impl RawDataStorage for CopiedRawData {
+    fn store(&self, raw: &RawAsn1EntityData<'_>) -> OwnedRawAsn1EntityData {
+        raw.to_owned()
+    }
+
+    fn store_bytes(&self, bytes: &RawBytes<'_>) -> OwnedRawBytes {
+        bytes.to_owned()
+    }
+}
+
+/// Raw data of the owned entities points into one copy of the root entity data
+struct SharedBuffer<'a> {
+    original: &'a [u8],
+    buffer: Arc<[u8]>,
+}
+
+impl SharedBuffer<'_> {
+    fn share(&self, bytes: &RawBytes<'_>) -> OwnedRawBytes {
+        let original = self.original.as_ptr_range();
+        let range = bytes.as_ptr_range();
+
+        // edited or built nodes can have the data that is not a part of the root entity
+        if bytes.is_empty() || range.start < original.start || range.end > original.end {
+            return bytes.to_owned();
+        }
+
+        let start = range.start as usize - original.start as usize;
+        RawBytes(Repr::Shared {
+            buffer: Arc::clone(&self.buffer),
+            range: start..start + bytes.len(),
+        })
+    }
+}
+
+impl RawDataStorage for SharedBuffer<'_> {
+    fn store(&self, raw: &RawAsn1EntityData<'_>) -> OwnedRawAsn1EntityData {
+        RawAsn1EntityData {
+            raw_data: self.share(&raw.raw_data),
+            tag: raw.tag,
+            length: raw.length.clone(),
+            data: raw.data.clone(),
+        }
+    }
+
+    fn store_bytes(&self, bytes: &RawBytes<'_>) -> OwnedRawBytes {
+        self.share(bytes)
+    }
+}
+
+impl Asn1<'_> {
+    /// Returns owned version of the asn1 tree.
+    ///
+    /// The raw data of the root entity is copied once into the reference-counted buffer, and raw data of all nested
+    /// nodes (including the encapsulated ones) points into it. Decoded values, e.g. strings or OCTET STRING octets,
+    /// point into it too. Unlike [Asn1Type::to_owned](crate::Asn1Type::to_owned),
+    /// it does not copy the raw data of every node.
+    pub fn to_shared(&self) -> OwnedAsn1 {
+        let original = self.meta().raw_bytes();
+        let storage = SharedBuffer {
+            original,
+            buffer: original.into(),
+        };
+
+        self.to_owned_in(&storage, self.inner_asn1().to_owned_in(&storage))
+    }
+}
@@ -0,0 +1,343 @@
+//! Streaming decoding of the asn1 entities from the [Read] source.
+
+use alloc::format;
+use alloc::vec::Vec;
+use std::io::{self, Read};
+
+use crate::length::{read_len, Length, END_OF_CONTENTS, INDEFINITE_LENGTH, USIZE_LEN};
+use crate::reader::{DecodeOptions, Reader};
+use crate::tag::{read_tag, HIGH_TAG_NUMBER, MAX_SUBSEQUENT_OCTETS, NUMBER_MASK};
+use crate::{Asn1, Asn1Decoder, Asn1Result, Error, ErrorKind, OwnedAsn1, Tag};
+
+/// Decoder that reads asn1 entities from the [Read] source one by one.
+///
+/// Only the entity that is being decoded is kept in memory, so it suits inputs that do not fit into memory, e.g.
+/// a concatenated stream of DER messages. [StreamDecoder::enter] reads only the header of the constructed entity,
+/// so the next entities are its fields. It allows iterating over the elements of a huge SEQUENCE (like the revoked
+/// certificates of a CRL) without loading the whole SEQUENCE.
+///
+/// Offsets of the decoded entities are positions in the stream.
+///
+/// Tag and length octets are read one byte at a time, so every header byte is a separate [Read::read] call.
+/// Sources that make a system call per read, like [File](std::fs::File) or [TcpStream](std::net::TcpStream),
+/// should be wrapped into [BufReader](std::io::BufReader).
+#[derive(Debug)]
+pub struct StreamDecoder<R> {
+    source: R,
+    // amount of bytes read from the source
+    offset: usize,
+    options: DecodeOptions,
+    // entered constructed entities: the end of the contents octets, or `None` for the indefinite length
+    entered: Vec<Option<usize>>,
+    // the iteration stops after the first error because the stream position is unknown
+    failed: bool,
+}
+
+impl<R: Read> StreamDecoder<R> {
+    pub fn new(source: R) -> Self {
+        Self::with_options(source, DecodeOptions::default())
+    }
+
+    /// Creates a new decoder. The limits are applied to every decoded entity and to the depth of the entered entities
+    pub fn with_options(source: R, options: DecodeOptions) -> Self {
+        Self {
+            source,
+            offset: 0,
+            options,
+            entered: Vec::new(),
+            failed: false,
+        }
+    }
+
+    /// Returns the amount of bytes read from the source
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the amount of the entered entities whose contents are not read yet
+    pub fn depth(&self) -> usize {
+        self.entered.len()
+    }
+
+    pub fn into_inner(self) -> R {
+        self.source
+    }
+
+    /// Reads and decodes the next entity.
+    ///
+    /// Returns `None` when the source ends or when the contents of the entered entity end. In the latter case,
+    /// the decoder continues with the entities after the entered one.
+    pub fn next_entity(&mut self) -> Asn1Result<Option<OwnedAsn1>> {
+        let start = self.offset;
+        let (tag, length, mut raw) = match self.read_header()? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+
+        self.read_contents(tag, length, &mut raw)?;
+        self.check_entered_end(start)?;
+
+        let mut reader = Reader::with_options(&raw, self.options);
+        reader.set_offset(start);
+
+        Ok(Some(Asn1::decode(&mut reader)?.to_shared()))
+    }
+
+    /// Reads the header of the next constructed entity. The next decoded entities are its fields.
+    ///
+    /// Returns the tag of the entered entity or `None` if there are no more entities at the current level.
+    pub fn enter(&mut self) -> Asn1Result<Option<Tag>> {
+        let start = self.offset;
+        let (tag, length, _) = match self.read_header()? {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+
+        if tag.is_primitive() {
+            return Err(
+                Error::new(ErrorKind::UnexpectedTag, "Only constructed entities can be entered").with_offset(start),
+            );
+        }
+
+        if self.entered.len() >= self.options.max_depth {
+            return Err(Error::new(ErrorKind::LimitExceeded, "Maximum nesting depth exceeded").with_offset(start));
+        }
+
+        let end = match length {
+            Length::Definite(len) => {
+                let end = self
+                    .offset
+                    .checked_add(len)
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidLength, "Length is too big").with_offset(start))?;
+
+                Some(end)
+            }
+            Length::Indefinite => None,
+        };
+        if let (Some(end), Some(Some(parent_end))) = (end, self.entered.last()) {
+            if end > *parent_end {
+                return Err(Error::new(
+                    ErrorKind::InvalidLength,
+                    "Length exceeds the contents of the enclosing entity",
+                )
+                .with_offset(start));
+            }
+        }
+        self.entered.push(end);
+
+        Ok(Some(tag))
+    }
+
+    /// Reads the tag and length octets of the next entity.
+    ///
+    /// Returns `None` and leaves the entered entity if its contents end.
+    fn read_header(&mut self) -> Asn1Result<Option<(Tag, Length, Vec<u8>)>> {
+        if let Some(Some(end)) = self.entered.last() {
+            if self.offset == *end {
+                self.entered.pop();
+
+                return Ok(None);
+            }
+        }
+
+        let first = match self.try_read_byte()? {
+            Some(byte) => byte,
+            None if self.entered.is_empty() => return Ok(None),
+            None => return Err(self.eof()),
+        };
+
+        let mut raw = Vec::new();
+        if let Some(None) = self.entered.last() {
+            if self.read_end_of_contents(first, &mut raw)? {
+                self.entered.pop();
+
+                return Ok(None);
+            }
+        }
+
+        let (tag, length) = self.read_tag_and_len(first, &mut raw)?;
+
+        Ok(Some((tag, length, raw)))
+    }
+
+    /// Reads the rest of the tag octets and the length octets and decodes them
+    fn read_tag_and_len(&mut self, first: u8, raw: &mut Vec<u8>) -> Asn1Result<(Tag, Length)> {
+        let start = raw.len();
+        let offset = self.offset - 1;
+
+        raw.push(first);
+        if first & NUMBER_MASK == HIGH_TAG_NUMBER {
+            for _ in 0..MAX_SUBSEQUENT_OCTETS {
+                let byte = self.read_byte()?;
+                raw.push(byte);
+
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+        }
+
+        let length = self.read_byte()?;
+        raw.push(length);
+        // too long lengths are rejected by `read_len` without reading the subsequent octets
+        let octets = usize::from(length & 0x7f);
+        if length > INDEFINITE_LENGTH && octets <= USIZE_LEN {
+            self.read_to(raw, octets)?;
+        }
+
+        let mut reader = Reader::new(&raw[start..]);
+        reader.set_offset(offset);
+        let tag = read_tag(&mut reader)?;
+        let (length, _) = read_len(&mut reader)?;
+
+        Ok((tag, length))
+    }
+
+    /// Reads the contents octets (and the end-of-contents octets) of the entity
+    fn read_contents(&mut self, tag: Tag, length: Length, raw: &mut Vec<u8>) -> Asn1Result<()> {
+        match length {
+            Length::Definite(len) => return self.read_to(raw, len),
+            Length::Indefinite if tag.is_primitive() => {
+                return Err(Error::new(
+                    ErrorKind::InvalidLength,
+                    "Indefinite length is not allowed for primitive types",
+                )
+                .with_offset(self.offset - 1))
+            }
+            Length::Indefinite => {}
+        }
+
+        // amount of nested indefinite-length encodings that are not terminated yet
+        let mut depth = 0_usize;
+        loop {
+            let first = self.read_byte()?;
+            if self.read_end_of_contents(first, raw)? {
+                if depth == 0 {
+                    return Ok(());
+                }
+                depth -= 1;
+
+                continue;
+            }
+
+            match self.read_tag_and_len(first, raw)? {
+                (_, Length::Definite(len)) => self.read_to(raw, len)?,
+                (tag, Length::Indefinite) => {
+                    if tag.is_primitive() {
+                        return Err(Error::new(
+                            ErrorKind::InvalidLength,
+                            "Indefinite length is not allowed for primitive types",
+                        )
+                        .with_offset(self.offset - 1));
+                    }
+
+                    depth += 1;
+                    if depth > self.options.max_depth {
+                        return Err(Error::new(ErrorKind::LimitExceeded, "Maximum nesting depth exceeded")
+                            .with_offset(self.offset));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reads the end-of-contents octets if the first octet starts them
+    fn read_end_of_contents(&mut self, first: u8, raw: &mut Vec<u8>) -> Asn1Result<bool> {
+        if first != END_OF_CONTENTS[0] {
+            return Ok(false);
+        }
+
+        let second = self.read_byte()?;
+        if second != END_OF_CONTENTS[1] {
+            return Err(
+                Error::new(ErrorKind::InvalidLength, "Invalid end-of-contents octets").with_offset(self.offset - 2)
+            );
+        }
+        raw.extend_from_slice(&END_OF_CONTENTS);
+
+        Ok(true)
+    }
+
+    /// Checks that the entity read from `start` does not exceed the contents of the entered entity
+    fn check_entered_end(&self, start: usize) -> Asn1Result<()> {
+        match self.entered.last() {
+            Some(Some(end)) if self.offset > *end => Err(Error::new(
+                ErrorKind::InvalidLength,
+                "Length exceeds the contents of the enclosing entity",
+            )
+            .with_offset(start)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reads one byte. Returns `None` if the source ended.
+    ///
+    /// The source is not buffered by the decoder (see [StreamDecoder] docs)
+    fn try_read_byte(&mut self) -> Asn1Result<Option<u8>> {
+        let mut byte = [0];
+
+        loop {
+            match self.source.read(&mut byte) {
+                Ok(0) => return Ok(None),
+                Ok(_) => {
+                    self.offset += 1;
+
+                    return Ok(Some(byte[0]));
+                }
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(self.io_error(error)),
+            }
+        }
+    }
+
+    fn read_byte(&mut self) -> Asn1Result<u8> {
+        self.try_read_byte()?.ok_or_else(|| self.eof())
+    }
+
+    /// Appends `len` bytes of the source to the buffer. The buffer grows while the data is read, so the length
+    /// decoded from the untrusted input does not cause a huge allocation
+    fn read_to(&mut self, raw: &mut Vec<u8>, len: usize) -> Asn1Result<()> {
+        let read = (&mut self.source).take(u64::try_from(len)?).read_to_end(raw);
+        let read = read.map_err(|error| self.io_error(error))?;
+        self.offset += read;
+
+        if read != len {
+            return Err(self.eof());
+        }
+
+        Ok(())
+    }
+
+    fn eof(&self) -> Error {
+        Error::new(
+            ErrorKind::UnexpectedEof,
+            "Stream ended before the asn1 entity was fully read",
+        )
+        .with_offset(self.offset)
+    }
+
+    fn io_error(&self, error: io::Error) -> Error {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => self.eof(),
+            kind => Error::new(ErrorKind::Io, "Can not read from the stream")
+                .with_detail(format!("{:?}: {}", kind, error))
+                .with_offset(self.offset),
+        }
+    }
+}
+
+impl<R: Read> Iterator for StreamDecoder<R> {
+    type Item = Asn1Result<OwnedAsn1>;
+
+    /// Returns the next decoded entity. The iteration stops after the first error
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let entity = self.next_entity().transpose();
+        self.failed = matches!(entity, Some(Err(_)));
+
+        entity
+    }
+}
@@ -1,26 +1,35 @@
-use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 
-use crate::length::{len_size, write_len};
 use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawBytes, RawDataStorage};
 use crate::writer::Writer;
-use crate::{Asn1, Asn1Decoder, Asn1Encoder, Asn1Result, Asn1ValueDecoder, Error, MetaInfo, Tag, Taggable};
+use crate::{
+    Asn1, Asn1Decoder, Asn1Encoder, Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, MetaInfo, Tag,
+    Taggable, Tlv,
+};
 
 /// [BitString](https://www.oss.com/asn1/resources/asn1-made-simple/asn1-quick-reference/bitstring.html)
 ///
 /// ASN.1 BIT STRING type values are arbitrary length strings of bits.
 /// A BIT STRING value doesn't need to be an even multiple of eight bits.
+///
+/// BER allows the constructed encoding of the BIT STRING. In such a case, the value is split into segments
+/// and `octets` contains the unused bits amount of the last segment followed by the concatenation of segments bits.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BitString<'data> {
-    octets: Cow<'data, [u8]>,
+    octets: RawBytes<'data>,
     inner: Option<Box<Asn1<'data>>>,
+    segments: Option<Vec<BitStringSegment<'data>>>,
 }
 
 pub type OwnedBitString = BitString<'static>;
 
+/// Segment of the constructed [BitString]
+pub type BitStringSegment<'data> = Tlv<'data, BitString<'data>>;
+
 impl BitString<'_> {
-    pub const TAG: Tag = Tag(3);
+    pub const TAG: Tag = Tag::from_byte(3);
 
     /// Returns inner bits
     pub fn raw_bits(&self) -> &[u8] {
@@ -31,6 +40,13 @@ impl BitString<'_> {
         self.inner.as_ref().map(|i| i.as_ref())
     }
 
+    /// Returns segments of the constructed [BitString]
+    ///
+    /// Returns `None` if the primitive encoding is used.
+    pub fn segments(&self) -> Option<&[BitStringSegment<'_>]> {
+        self.segments.as_deref()
+    }
+
     pub fn bits_amount(&self) -> usize {
         (self.octets.as_ref().len() - 1) * 8 - usize::from(self.octets.as_ref()[0])
     }
@@ -40,39 +56,53 @@ impl BitString<'_> {
         let all_bits_amount = bits.len() * 8;
 
         if bits_amount > all_bits_amount {
-            return Err(Error::from("Too many bits"));
+            return Err(Error::new(ErrorKind::InvalidValue, "Too many bits"));
         }
 
         if all_bits_amount - bits_amount >= 8 {
-            return Err(Error::from("Too many unused bits"));
+            return Err(Error::new(ErrorKind::InvalidValue, "Too many unused bits"));
         }
 
         let unused_bits: u8 = (all_bits_amount - bits_amount).try_into()?;
         bits.insert(0, unused_bits);
 
         let inner = if !bits.is_empty() {
-            Asn1::decode_buff(&bits[1..]).ok().map(|mut asn1| {
-                asn1.clear_meta();
-                Box::new(asn1.to_owned_with_asn1(asn1.inner_asn1().to_owned()))
-            })
+            Asn1::decode_buff(&bits[1..])
+                .ok()
+                .filter(|asn1| asn1.meta().raw_bytes().len() == bits[1..].len())
+                .map(|mut asn1| {
+                    asn1.clear_meta();
+                    Box::new(asn1.to_shared())
+                })
         } else {
             None
         };
 
         Ok(BitString {
-            octets: Cow::Owned(bits),
+            octets: RawBytes::from(bits),
             inner,
+            segments: None,
         })
     }
 
     /// Returns owned version of the [BitString]
     pub fn to_owned(&self) -> OwnedBitString {
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedBitString {
         BitString {
-            octets: self.octets.to_vec().into(),
+            octets: storage.store_bytes(&self.octets),
             inner: self
                 .inner
                 .as_ref()
-                .map(|inner| Box::new(inner.to_owned_with_asn1(inner.inner_asn1().to_owned()))),
+                .map(|inner| Box::new(inner.to_owned_in(storage, inner.inner_asn1().to_owned_in(storage)))),
+            segments: self.segments.as_ref().map(|segments| {
+                segments
+                    .iter()
+                    .map(|segment| segment.to_owned_in(storage, segment.inner_asn1().to_owned_in(storage)))
+                    .collect()
+            }),
         }
     }
 }
@@ -80,63 +110,143 @@ impl BitString<'_> {
 // we assume here that firs vector byte contains amount of unused bytes
 impl From<Vec<u8>> for BitString<'_> {
     fn from(data: Vec<u8>) -> Self {
-        let inner = Asn1::decode_buff(&data)
-            .ok()
-            .map(|asn1| Box::new(asn1.to_owned_with_asn1(asn1.inner_asn1().to_owned())));
+        let inner = Asn1::decode_buff(&data).ok().map(|asn1| Box::new(asn1.to_shared()));
         Self {
-            octets: Cow::Owned(data),
+            octets: RawBytes::from(data),
             inner,
+            segments: None,
         }
     }
 }
 
 impl Taggable for BitString<'_> {
     fn tag(&self) -> Tag {
-        Self::TAG
+        if self.segments.is_some() {
+            Self::TAG.to_constructed()
+        } else {
+            Self::TAG
+        }
     }
 }
 
-impl<'data> Asn1ValueDecoder<'data> for BitString<'data> {
-    fn decode(_: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
-        let data = reader.read_remaining();
+impl<'data> BitString<'data> {
+    /// Replaces the encapsulated asn1 entity. The bits are re-encoded from it using the primitive form
+    pub fn set_inner(&mut self, inner: Asn1<'data>) -> Asn1Result<()> {
+        self.inner = Some(Box::new(inner));
 
-        let inner = if !data.is_empty() {
-            let mut inner_reader = Reader::new(&data[1..]);
-            inner_reader.set_next_id(reader.next_id());
-            inner_reader.set_offset(reader.full_offset() - data.len());
-            let mut inner = Asn1::decode(&mut inner_reader).ok().map(Box::new);
+        self.encode_inner()
+    }
 
-            if !inner_reader.empty() && inner.is_some() {
-                inner = None;
+    pub(crate) fn inner_mut(&mut self) -> Option<&mut Asn1<'data>> {
+        self.inner.as_deref_mut()
+    }
+
+    /// Re-encodes the bits from the encapsulated asn1 entity after it was changed
+    pub(crate) fn encode_inner(&mut self) -> Asn1Result<()> {
+        if let Some(inner) = self.inner.as_ref() {
+            let mut writer = Writer::growable();
+            // the encapsulated entity takes whole octets, so there are no unused bits
+            writer.write_byte(0)?;
+            inner.encode(&mut writer)?;
+            self.octets = RawBytes::from(writer.into_vec()?);
+            self.segments = None;
+        }
+
+        Ok(())
+    }
+
+    fn decode_segments(reader: &mut Reader<'data>) -> Asn1Result<Self> {
+        // the first byte is the amount of unused bits in the last segment
+        let mut octets = Vec::from([0]);
+        let mut segments = Vec::new();
+
+        while !reader.empty() {
+            if octets[0] != 0 {
+                return Err(Error::new(
+                    ErrorKind::InvalidValue,
+                    "Only the last BitString segment can contain unused bits",
+                )
+                .with_offset(reader.full_offset()));
             }
 
-            reader.set_next_id(inner_reader.next_id());
+            let segment = BitStringSegment::decode(reader)?;
+            let segment_octets = segment.inner_asn1().raw_bits();
+            if segment_octets.is_empty() {
+                return Err(
+                    Error::new(ErrorKind::InvalidValue, "BitString segment can not be empty")
+                        .with_offset(segment.meta().tag_position()),
+                );
+            }
 
-            inner
+            octets[0] = segment_octets[0];
+            octets.extend_from_slice(&segment_octets[1..]);
+            segments.push(segment);
+        }
+
+        Ok(Self {
+            octets: RawBytes::from(octets),
+            inner: None,
+            segments: Some(segments),
+        })
+    }
+}
+
+impl<'data> Asn1ValueDecoder<'data> for BitString<'data> {
+    fn decode(tag: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
+        if tag.is_constructed() {
+            return Self::decode_segments(reader);
+        }
+
+        let data = reader.read_remaining();
+
+        let inner = if !data.is_empty() && reader.options().decode_encapsulated {
+            let offset = reader.full_offset() - data.len();
+
+            Asn1::decode_encapsulated(reader, &data[1..], offset)?
         } else {
             None
         };
 
         Ok(Self {
-            octets: Cow::Borrowed(data),
+            octets: RawBytes::from(data),
             inner,
+            segments: None,
         })
     }
 
     fn compare_tags(tag: Tag) -> bool {
-        Self::TAG == tag
+        Self::TAG == tag || Self::TAG.to_constructed() == tag
+    }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
     }
 }
 
-impl Asn1Encoder for BitString<'_> {
-    fn needed_buf_size(&self) -> usize {
-        let data_len = self.octets.len();
-        1 /* tag */ + len_size(data_len) + data_len
+impl Asn1ValueEncoder for BitString<'_> {
+    fn value_len(&self) -> usize {
+        match &self.segments {
+            Some(segments) => segments.iter().map(|segment| segment.needed_buf_size()).sum(),
+            None => self.octets.len(),
+        }
+    }
+
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        match &self.segments {
+            Some(segments) => segments.iter().try_for_each(|segment| segment.encode(writer)),
+            None => writer.write_slice(&self.octets),
+        }
     }
+}
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        writer.write_byte(Self::TAG.into())?;
-        write_len(self.octets.len(), writer)?;
-        writer.write_slice(&self.octets)
+impl MetaInfo for BitString<'_> {
+    fn clear_meta(&mut self) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.clear_meta();
+        }
+
+        if let Some(segments) = self.segments.as_mut() {
+            segments.iter_mut().for_each(|segment| segment.clear_meta());
+        }
     }
 }
@@ -1,40 +1,49 @@
-use alloc::borrow::Cow;
 use alloc::vec::Vec;
 
-use crate::length::{len_size, write_len};
 use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawBytes, RawDataStorage};
 use crate::writer::Writer;
-use crate::{Asn1Encoder, Asn1Result, Asn1ValueDecoder, Tag, Taggable};
+use crate::{Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, Tag, Taggable};
 
 /// [BmpString](https://www.oss.com/asn1/resources/asn1-made-simple/asn1-quick-reference/bmpstring.html)
 ///
 /// The ASN.1 BMPString type contains UNICODE characters. They are two-byte characters, and are not recommended for use unless properly subtyped.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct BmpString<'data>(Cow<'data, [u8]>);
+pub struct BmpString<'data>(RawBytes<'data>);
 
 pub type OwnedBmpString = BmpString<'static>;
 
 impl BmpString<'_> {
-    pub const TAG: Tag = Tag(30);
+    pub const TAG: Tag = Tag::from_byte(30);
 
     /// Returns inner raw [BmpString] data
     pub fn raw_data(&self) -> &[u8] {
         &self.0
     }
 
+    pub fn set_string(&mut self, value: &str) {
+        *self = OwnedBmpString::from(value);
+    }
+
     /// Returns owned version of the [BmpString]
     pub fn to_owned(&self) -> OwnedBmpString {
-        BmpString(self.0.to_vec().into())
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedBmpString {
+        BmpString(storage.store_bytes(&self.0))
     }
 
     pub fn new_owned(data: Vec<u8>) -> OwnedBmpString {
-        BmpString(Cow::Owned(data))
+        BmpString(RawBytes::from(data))
     }
 }
 
 impl From<&str> for OwnedBmpString {
     fn from(value: &str) -> Self {
-        Self(Cow::Owned(value.encode_utf16().flat_map(|c| c.to_le_bytes()).collect()))
+        Self(RawBytes::from(
+            value.encode_utf16().flat_map(|c| c.to_le_bytes()).collect::<Vec<_>>(),
+        ))
     }
 }
 
@@ -49,26 +58,27 @@ impl<'data> Asn1ValueDecoder<'data> for BmpString<'data> {
         let data = reader.remaining();
 
         if data.len() % 2 == 1 {
-            return Err("Invalid BmpString".into());
+            return Err(Error::new(ErrorKind::InvalidValue, "Invalid BmpString"));
         }
 
-        Ok(Self(Cow::Borrowed(data)))
+        Ok(Self(RawBytes::from(data)))
     }
 
     fn compare_tags(tag: Tag) -> bool {
         Self::TAG == tag
     }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
+    }
 }
 
-impl Asn1Encoder for BmpString<'_> {
-    fn needed_buf_size(&self) -> usize {
-        let data_len = self.0.len();
-        1 /* tag */ + len_size(data_len) + data_len
+impl Asn1ValueEncoder for BmpString<'_> {
+    fn value_len(&self) -> usize {
+        self.0.len()
     }
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        writer.write_byte(Self::TAG.into())?;
-        write_len(self.0.len(), writer)?;
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
         writer.write_slice(&self.0)
     }
 }
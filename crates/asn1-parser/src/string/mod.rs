@@ -1,70 +1,84 @@
 mod bit_string;
 mod bmp_string;
 mod octet_string;
+mod universal_string;
 mod validators;
 
-use alloc::borrow::Cow;
 use alloc::string::String;
+use alloc::vec::Vec;
 use core::str::from_utf8;
 
-pub use bit_string::{BitString, OwnedBitString};
+pub use bit_string::{BitString, BitStringSegment, OwnedBitString};
 pub use bmp_string::{BmpString, OwnedBmpString};
-pub use octet_string::{OctetString, OwnedOctetString};
+pub use octet_string::{OctetString, OctetStringSegment, OwnedOctetString};
+pub use universal_string::{OwnedUniversalString, UniversalString};
 use validators::{validate_general, validate_ia5, validate_printable, validate_utf8};
 
-use crate::length::{len_size, write_len};
 use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawBytes, RawDataStorage};
 use crate::string::validators::{validate_numeric, validate_visible};
 use crate::writer::Writer;
-use crate::{Asn1Encoder, Asn1Result, Asn1ValueDecoder, Tag};
+use crate::{Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, Tag, Taggable};
 
+/// UTF-8 string value. The bytes are validated when the value is created, so they are always valid UTF-8
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Utf8Value<'data, const TAG: u8>(Cow<'data, str>);
+struct Utf8Value<'data, const TAG: u8>(RawBytes<'data>);
 
 type OwnedUtf8Value<const TAG: u8> = Utf8Value<'static, TAG>;
 
 impl<const TAG: u8> Utf8Value<'_, TAG> {
     pub fn as_str(&self) -> &str {
-        self.0.as_ref()
+        from_utf8(&self.0).unwrap_or_default()
     }
 
     pub fn as_bytes(&self) -> &[u8] {
-        self.0.as_bytes()
+        &self.0
+    }
+
+    fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedUtf8Value<TAG> {
+        Utf8Value(storage.store_bytes(&self.0))
     }
 }
 
 impl<const TAG: u8> From<String> for OwnedUtf8Value<TAG> {
     fn from(value: String) -> Self {
-        Self(Cow::Owned(value))
+        Self(RawBytes::from(value.into_bytes()))
     }
 }
 
 impl<'data, const TAG: u8> From<&'data str> for Utf8Value<'data, TAG> {
     fn from(value: &'data str) -> Self {
-        Self(Cow::Borrowed(value))
+        Self(RawBytes::from(value.as_bytes()))
     }
 }
 
 impl<'data, const TAG: u8> Asn1ValueDecoder<'data> for Utf8Value<'data, TAG> {
     fn decode(_: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
-        Ok(Self(Cow::Borrowed(from_utf8(reader.remaining())?)))
+        Ok(Self(RawBytes::from(from_utf8(reader.remaining())?.as_bytes())))
     }
 
     fn compare_tags(tag: Tag) -> bool {
-        tag.0 == TAG
+        tag == Tag::from_byte(TAG)
+    }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(Tag::from_byte(TAG))
     }
 }
 
-impl<const TAG: u8> Asn1Encoder for Utf8Value<'_, TAG> {
-    fn needed_buf_size(&self) -> usize {
-        let data_len = self.0.len();
-        1 /* tag */ + len_size(data_len) + data_len
+impl<const TAG: u8> Taggable for Utf8Value<'_, TAG> {
+    fn tag(&self) -> Tag {
+        Tag::from_byte(TAG)
+    }
+}
+
+impl<const TAG: u8> Asn1ValueEncoder for Utf8Value<'_, TAG> {
+    fn value_len(&self) -> usize {
+        self.0.len()
     }
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        writer.write_byte(TAG)?;
-        write_len(self.0.len(), writer)?;
-        writer.write_slice(self.0.as_bytes())
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        writer.write_slice(&self.0)
     }
 }
 
@@ -74,3 +88,8 @@ impl_utf8_asn1!(PrintableString, 19, validate_printable);
 impl_utf8_asn1!(IA5String, 22, validate_ia5);
 impl_utf8_asn1!(VisibleString, 26, validate_visible);
 impl_utf8_asn1!(GeneralString, 27, validate_general);
+
+impl_byte_string_asn1!(ObjectDescriptor, 7);
+impl_byte_string_asn1!(TeletexString, 20);
+impl_byte_string_asn1!(VideotexString, 21);
+impl_byte_string_asn1!(GraphicString, 25);
@@ -1,27 +1,33 @@
-use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 
 use crate::asn1::Asn1;
-use crate::length::{len_size, write_len};
 use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawBytes, RawDataStorage};
 use crate::writer::Writer;
-use crate::{Asn1Decoder, Asn1Encoder, Asn1Result, Asn1ValueDecoder, MetaInfo, Tag, Taggable};
+use crate::{Asn1Decoder, Asn1Encoder, Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, MetaInfo, Tag, Taggable, Tlv};
 
 /// [OctetString](https://www.oss.com/asn1/resources/asn1-made-simple/asn1-quick-reference/octetstring.html)
 ///
 /// The ASN.1 OCTET STRING type contains arbitrary strings of octets. This type is very similar to BIT STRING,
 /// except that all values must be an integral number of eight bits.
+///
+/// BER allows the constructed encoding of the OCTET STRING. In such a case, the value is split into segments
+/// and `octets` contains the concatenation of them.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OctetString<'data> {
-    octets: Cow<'data, [u8]>,
+    octets: RawBytes<'data>,
     inner: Option<Box<Asn1<'data>>>,
+    segments: Option<Vec<OctetStringSegment<'data>>>,
 }
 
 pub type OwnedOctetString = OctetString<'static>;
 
+/// Segment of the constructed [OctetString]
+pub type OctetStringSegment<'data> = Tlv<'data, OctetString<'data>>;
+
 impl OctetString<'_> {
-    pub const TAG: Tag = Tag(4);
+    pub const TAG: Tag = Tag::from_byte(4);
 
     /// Returns inner octets
     pub fn octets(&self) -> &[u8] {
@@ -32,90 +38,166 @@ impl OctetString<'_> {
         self.inner.as_ref().map(|i| i.as_ref())
     }
 
+    /// Returns segments of the constructed [OctetString]
+    ///
+    /// Returns `None` if the primitive encoding is used.
+    pub fn segments(&self) -> Option<&[OctetStringSegment<'_>]> {
+        self.segments.as_deref()
+    }
+
     /// Returns owned version of the [OctetString]
     pub fn to_owned(&self) -> OwnedOctetString {
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedOctetString {
         OctetString {
-            octets: self.octets.to_vec().into(),
+            octets: storage.store_bytes(&self.octets),
             inner: self
                 .inner
                 .as_ref()
-                .map(|inner| Box::new(inner.to_owned_with_asn1(inner.inner_asn1().to_owned()))),
+                .map(|inner| Box::new(inner.to_owned_in(storage, inner.inner_asn1().to_owned_in(storage)))),
+            segments: self.segments.as_ref().map(|segments| {
+                segments
+                    .iter()
+                    .map(|segment| segment.to_owned_in(storage, segment.inner_asn1().to_owned_in(storage)))
+                    .collect()
+            }),
         }
     }
 
     pub fn new_owned(octets: Vec<u8>) -> OwnedOctetString {
-        let inner = Asn1::decode_buff(&octets).ok().map(|mut asn1| {
-            asn1.clear_meta();
-            Box::new(asn1.to_owned_with_asn1(asn1.inner_asn1().to_owned()))
-        });
+        let inner = Asn1::decode_buff(&octets)
+            .ok()
+            .filter(|asn1| asn1.meta().raw_bytes().len() == octets.len())
+            .map(|mut asn1| {
+                asn1.clear_meta();
+                Box::new(asn1.to_shared())
+            });
 
         OwnedOctetString {
-            octets: Cow::Owned(octets),
+            octets: RawBytes::from(octets),
             inner,
+            segments: None,
         }
     }
 }
 
 impl From<Vec<u8>> for OwnedOctetString {
     fn from(data: Vec<u8>) -> Self {
-        let inner = Asn1::decode_buff(&data)
-            .ok()
-            .map(|asn1| Box::new(asn1.to_owned_with_asn1(asn1.inner_asn1().to_owned())));
+        let inner = Asn1::decode_buff(&data).ok().map(|asn1| Box::new(asn1.to_shared()));
         Self {
-            octets: Cow::Owned(data),
+            octets: RawBytes::from(data),
             inner,
+            segments: None,
         }
     }
 }
 
-impl<'data> Asn1ValueDecoder<'data> for OctetString<'data> {
-    fn decode(_: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
-        let data = reader.read_remaining();
+impl<'data> OctetString<'data> {
+    /// Replaces the encapsulated asn1 entity. The octets are re-encoded from it using the primitive form
+    pub fn set_inner(&mut self, inner: Asn1<'data>) -> Asn1Result<()> {
+        self.inner = Some(Box::new(inner));
 
-        let mut inner_reader = Reader::new(data);
-        inner_reader.set_next_id(reader.next_id());
-        inner_reader.set_offset(reader.full_offset() - data.len());
-        let mut inner = Asn1::decode(&mut inner_reader).ok().map(Box::new);
+        self.encode_inner()
+    }
+
+    pub(crate) fn inner_mut(&mut self) -> Option<&mut Asn1<'data>> {
+        self.inner.as_deref_mut()
+    }
 
-        if !inner_reader.empty() && inner.is_some() {
-            inner = None;
+    /// Re-encodes the octets from the encapsulated asn1 entity after it was changed
+    pub(crate) fn encode_inner(&mut self) -> Asn1Result<()> {
+        if let Some(inner) = self.inner.as_ref() {
+            self.octets = RawBytes::from(inner.encode_to_vec()?);
+            self.segments = None;
         }
 
-        reader.set_next_id(inner_reader.next_id());
+        Ok(())
+    }
+
+    fn decode_segments(reader: &mut Reader<'data>) -> Asn1Result<Self> {
+        let mut octets = Vec::new();
+        let mut segments = Vec::new();
+
+        while !reader.empty() {
+            let segment = OctetStringSegment::decode(reader)?;
+            octets.extend_from_slice(segment.inner_asn1().octets());
+            segments.push(segment);
+        }
 
         Ok(Self {
-            octets: Cow::Borrowed(data),
+            octets: RawBytes::from(octets),
+            inner: None,
+            segments: Some(segments),
+        })
+    }
+}
+
+impl<'data> Asn1ValueDecoder<'data> for OctetString<'data> {
+    fn decode(tag: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
+        if tag.is_constructed() {
+            return Self::decode_segments(reader);
+        }
+
+        let data = reader.read_remaining();
+
+        let inner = if reader.options().decode_encapsulated {
+            let offset = reader.full_offset() - data.len();
+
+            Asn1::decode_encapsulated(reader, data, offset)?
+        } else {
+            None
+        };
+
+        Ok(Self {
+            octets: RawBytes::from(data),
             inner,
+            segments: None,
         })
     }
 
     fn compare_tags(tag: Tag) -> bool {
-        Self::TAG == tag
+        Self::TAG == tag || Self::TAG.to_constructed() == tag
+    }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
     }
 }
 
 impl Taggable for OctetString<'_> {
     fn tag(&self) -> Tag {
-        Self::TAG
+        if self.segments.is_some() {
+            Self::TAG.to_constructed()
+        } else {
+            Self::TAG
+        }
     }
 }
 
-impl Asn1Encoder for OctetString<'_> {
-    fn needed_buf_size(&self) -> usize {
-        let data_len = self.octets.len();
-
-        1 /* tag */ + len_size(data_len) + data_len
+impl Asn1ValueEncoder for OctetString<'_> {
+    fn value_len(&self) -> usize {
+        match &self.segments {
+            Some(segments) => segments.iter().map(|segment| segment.needed_buf_size()).sum(),
+            None => self.octets.len(),
+        }
     }
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        writer.write_byte(Self::TAG.into())?;
-        write_len(self.octets.len(), writer)?;
-        writer.write_slice(&self.octets)
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        match &self.segments {
+            Some(segments) => segments.iter().try_for_each(|segment| segment.encode(writer)),
+            None => writer.write_slice(&self.octets),
+        }
     }
 }
 
 impl MetaInfo for OctetString<'_> {
     fn clear_meta(&mut self) {
         self.inner = None;
+
+        if let Some(segments) = self.segments.as_mut() {
+            segments.iter_mut().for_each(|segment| segment.clear_meta());
+        }
     }
 }
@@ -0,0 +1,101 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawBytes, RawDataStorage};
+use crate::writer::Writer;
+use crate::{Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, Tag, Taggable};
+
+/// [UniversalString](https://www.oss.com/asn1/resources/asn1-made-simple/asn1-quick-reference/universalstring.html)
+///
+/// The ASN.1 UniversalString type contains UCS-4 characters. Every character is encoded using four big-endian bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniversalString<'data>(RawBytes<'data>);
+
+pub type OwnedUniversalString = UniversalString<'static>;
+
+impl UniversalString<'_> {
+    pub const TAG: Tag = Tag::from_byte(28);
+
+    /// Returns inner raw [UniversalString] data
+    pub fn raw_data(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns decoded [UniversalString] data
+    pub fn string(&self) -> String {
+        self.0
+            .chunks(4)
+            .map(|bytes| {
+                u32::from_be_bytes(bytes.try_into().unwrap_or_default())
+                    .try_into()
+                    .unwrap_or(char::REPLACEMENT_CHARACTER)
+            })
+            .collect()
+    }
+
+    pub fn set_string(&mut self, value: &str) {
+        *self = OwnedUniversalString::from(value);
+    }
+
+    /// Returns owned version of the [UniversalString]
+    pub fn to_owned(&self) -> OwnedUniversalString {
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedUniversalString {
+        UniversalString(storage.store_bytes(&self.0))
+    }
+}
+
+impl From<&str> for OwnedUniversalString {
+    fn from(value: &str) -> Self {
+        Self(RawBytes::from(
+            value
+                .chars()
+                .flat_map(|c| u32::from(c).to_be_bytes())
+                .collect::<Vec<_>>(),
+        ))
+    }
+}
+
+impl Taggable for UniversalString<'_> {
+    fn tag(&self) -> Tag {
+        Self::TAG
+    }
+}
+
+impl<'data> Asn1ValueDecoder<'data> for UniversalString<'data> {
+    fn decode(_: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
+        let offset = reader.full_offset();
+        let data = reader.read_remaining();
+
+        let is_valid = data.len() % 4 == 0
+            && data
+                .chunks(4)
+                .all(|bytes| char::from_u32(u32::from_be_bytes(bytes.try_into().unwrap_or_default())).is_some());
+        if !is_valid {
+            return Err(Error::new(ErrorKind::InvalidValue, "Invalid UniversalString").with_offset(offset));
+        }
+
+        Ok(Self(RawBytes::from(data)))
+    }
+
+    fn compare_tags(tag: Tag) -> bool {
+        Self::TAG == tag
+    }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
+    }
+}
+
+impl Asn1ValueEncoder for UniversalString<'_> {
+    fn value_len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        writer.write_slice(&self.0)
+    }
+}
@@ -1,32 +1,246 @@
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub struct Tag(pub(crate) u8);
+use core::fmt;
+
+use crate::reader::Reader;
+use crate::writer::Writer;
+use crate::{Asn1Result, Error, ErrorKind};
+
+/// Bits of the first identifier octet that encode the tag class
+const CLASS_MASK: u8 = 0xc0;
+/// Bit of the first identifier octet that indicates the constructed encoding
+const CONSTRUCTED_BIT: u8 = 0x20;
+/// Bits of the first identifier octet that encode the tag number (low-tag-number form)
+pub(crate) const NUMBER_MASK: u8 = 0x1f;
+/// Value of the tag number bits that indicates the high-tag-number form
+pub(crate) const HIGH_TAG_NUMBER: u8 = 0x1f;
+
+/// Max amount of subsequent identifier octets. `u32` tag number can take at most 5 base-128 digits.
+pub(crate) const MAX_SUBSEQUENT_OCTETS: usize = 5;
+
+/// [Tag class](https://www.oss.com/asn1/resources/asn1-made-simple/asn1-quick-reference/asn1-tags.html)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum TagClass {
+    Universal,
+    Application,
+    ContextSpecific,
+    Private,
+}
+
+impl TagClass {
+    const fn from_byte(byte: u8) -> Self {
+        match byte & CLASS_MASK {
+            0x00 => TagClass::Universal,
+            0x40 => TagClass::Application,
+            0x80 => TagClass::ContextSpecific,
+            _ => TagClass::Private,
+        }
+    }
+
+    const fn bits(self) -> u8 {
+        match self {
+            TagClass::Universal => 0x00,
+            TagClass::Application => 0x40,
+            TagClass::ContextSpecific => 0x80,
+            TagClass::Private => 0xc0,
+        }
+    }
+}
+
+/// asn1 tag (identifier octets)
+///
+/// Tag consists of the class, the constructed bit, and the tag number.
+/// Tag numbers greater than or equal to 31 are encoded using the high-tag-number form (X.690 8.1.2.4).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Tag {
+    class: TagClass,
+    constructed: bool,
+    number: u32,
+}
 
 impl Tag {
+    /// Creates a new [Tag] from its parts
+    pub const fn new(class: TagClass, constructed: bool, number: u32) -> Self {
+        Self {
+            class,
+            constructed,
+            number,
+        }
+    }
+
+    /// Creates a new [Tag] from the single identifier octet (low-tag-number form)
+    pub const fn from_byte(byte: u8) -> Self {
+        Self {
+            class: TagClass::from_byte(byte),
+            constructed: byte & CONSTRUCTED_BIT == CONSTRUCTED_BIT,
+            number: (byte & NUMBER_MASK) as u32,
+        }
+    }
+
+    pub fn class(self) -> TagClass {
+        self.class
+    }
+
+    pub fn number(self) -> u32 {
+        self.number
+    }
+
+    pub fn is_universal(self) -> bool {
+        self.class == TagClass::Universal
+    }
+
     pub fn is_context_specific(self) -> bool {
-        self.0 & 0xc0 == 0x80
+        self.class == TagClass::ContextSpecific
     }
 
     pub fn is_application(self) -> bool {
-        self.0 & 0xc0 == 0x40
+        self.class == TagClass::Application
+    }
+
+    pub fn is_private(self) -> bool {
+        self.class == TagClass::Private
     }
 
     pub fn is_constructed(self) -> bool {
-        self.0 & 0x20 == 0x20
+        self.constructed
     }
 
     pub fn is_primitive(self) -> bool {
         !self.is_constructed()
     }
+
+    /// Returns the same tag but with the constructed bit set
+    pub const fn to_constructed(self) -> Self {
+        Self {
+            class: self.class,
+            constructed: true,
+            number: self.number,
+        }
+    }
+
+    /// Returns `true` if the tag number is encoded using the high-tag-number form
+    pub fn is_high_tag_number(self) -> bool {
+        self.number >= u32::from(HIGH_TAG_NUMBER)
+    }
+
+    fn first_octet(self) -> u8 {
+        let number_bits = if self.is_high_tag_number() {
+            HIGH_TAG_NUMBER
+        } else {
+            self.number as u8
+        };
+
+        self.class.bits() | if self.constructed { CONSTRUCTED_BIT } else { 0 } | number_bits
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.class {
+            TagClass::Universal => write!(f, "[UNIVERSAL {}]", self.number),
+            TagClass::Application => write!(f, "[APPLICATION {}]", self.number),
+            TagClass::ContextSpecific => write!(f, "[{}]", self.number),
+            TagClass::Private => write!(f, "[PRIVATE {}]", self.number),
+        }
+    }
 }
 
 impl From<u8> for Tag {
     fn from(tag: u8) -> Self {
-        Self(tag)
+        Self::from_byte(tag)
+    }
+}
+
+impl TryFrom<Tag> for u8 {
+    type Error = Error;
+
+    fn try_from(tag: Tag) -> Result<Self, Self::Error> {
+        if tag.is_high_tag_number() {
+            return Err(Error::new(
+                ErrorKind::InvalidTag,
+                "Tag can not be represented as a single byte",
+            ));
+        }
+
+        Ok(tag.first_octet())
+    }
+}
+
+/// Reads tag (identifier octets) from the reader
+pub fn read_tag(reader: &mut Reader) -> Asn1Result<Tag> {
+    let offset = reader.full_offset();
+    let first = reader.read_byte()?;
+
+    let mut tag = Tag::from_byte(first);
+    if first & NUMBER_MASK != HIGH_TAG_NUMBER {
+        return Ok(tag);
+    }
+
+    let mut number: u32 = 0;
+    for i in 0..MAX_SUBSEQUENT_OCTETS {
+        let byte = reader.read_byte()?;
+
+        if i == 0 && byte == 0x80 {
+            return Err(Error::new(
+                ErrorKind::InvalidTag,
+                "Invalid tag: first subsequent octet can not be 0x80",
+            )
+            .with_offset(offset));
+        }
+
+        number = number
+            .checked_mul(0x80)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidTag, "Tag number is too big").with_offset(offset))?
+            | u32::from(byte & 0x7f);
+
+        if byte & 0x80 == 0 {
+            if number < u32::from(HIGH_TAG_NUMBER) {
+                return Err(Error::new(
+                    ErrorKind::InvalidTag,
+                    "Invalid tag: tag number less than 31 must use one byte",
+                )
+                .with_offset(offset));
+            }
+
+            tag.number = number;
+            return Ok(tag);
+        }
     }
+
+    Err(Error::new(ErrorKind::InvalidTag, "Tag number is too big").with_offset(offset))
 }
 
-impl From<Tag> for u8 {
-    fn from(tag: Tag) -> Self {
-        tag.0
+/// Writes asn1 tag (identifier octets) into provided writer
+pub fn write_tag(tag: Tag, writer: &mut Writer) -> Asn1Result<()> {
+    writer.write_byte(tag.first_octet())?;
+
+    if !tag.is_high_tag_number() {
+        return Ok(());
     }
+
+    let mut buf = [0; MAX_SUBSEQUENT_OCTETS];
+    let octets_amount = tag_size(tag) - 1;
+    let mut number = tag.number;
+
+    for i in (0..octets_amount).rev() {
+        let continuation_bit = if i == octets_amount - 1 { 0 } else { 0x80 };
+        buf[i] = continuation_bit | (number & 0x7f) as u8;
+        number >>= 7;
+    }
+
+    writer.write_slice(&buf[0..octets_amount])
+}
+
+/// Returns how many bytes encoded tag will take
+pub fn tag_size(tag: Tag) -> usize {
+    if !tag.is_high_tag_number() {
+        return 1;
+    }
+
+    let significant_bits = 32 - tag.number.leading_zeros() as usize;
+
+    1 + (significant_bits + 6) / 7
 }
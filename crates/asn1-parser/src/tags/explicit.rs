@@ -1,42 +1,50 @@
 use alloc::vec::Vec;
 
 use crate::asn1::Asn1;
-use crate::length::{len_size, write_len};
 use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawDataStorage};
 use crate::writer::Writer;
-use crate::{Asn1Decoder, Asn1Encoder, Asn1Result, Asn1ValueDecoder, MetaInfo, Tag, Taggable};
+use crate::{Asn1Encoder, Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, MetaInfo, Tag, TagClass, Taggable};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExplicitTag<'data> {
-    tag: u8,
+    tag: Tag,
     inner: Vec<Asn1<'data>>,
 }
 
 pub type OwnedExplicitTag = ExplicitTag<'static>;
 
 impl<'data> ExplicitTag<'data> {
-    pub fn new(tag: u8, inner: Vec<Asn1<'data>>) -> Self {
+    pub fn new(tag_number: u32, inner: Vec<Asn1<'data>>) -> Self {
         Self {
-            tag: tag & 0x1f | 0xa0,
+            tag: Tag::new(TagClass::ContextSpecific, true, tag_number),
             inner,
         }
     }
 
-    pub fn tag_number(&self) -> u8 {
-        self.tag & 0x1f
+    pub fn tag_number(&self) -> u32 {
+        self.tag.number()
     }
 
     pub fn inner(&self) -> &[Asn1<'data>] {
         &self.inner
     }
 
+    pub fn inner_mut(&mut self) -> &mut [Asn1<'data>] {
+        &mut self.inner
+    }
+
     pub fn to_owned(&self) -> OwnedExplicitTag {
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedExplicitTag {
         OwnedExplicitTag {
             tag: self.tag,
             inner: self
                 .inner
                 .iter()
-                .map(|f| f.to_owned_with_asn1(f.inner_asn1().to_owned()))
+                .map(|f| f.to_owned_in(storage, f.inner_asn1().to_owned_in(storage)))
                 .collect(),
         }
     }
@@ -44,7 +52,7 @@ impl<'data> ExplicitTag<'data> {
 
 impl Taggable for ExplicitTag<'_> {
     fn tag(&self) -> Tag {
-        Tag(self.tag)
+        self.tag
     }
 }
 
@@ -53,10 +61,10 @@ impl<'data> Asn1ValueDecoder<'data> for ExplicitTag<'data> {
         let mut inner = Vec::new();
 
         while !reader.empty() {
-            inner.push(Asn1::decode(reader)?);
+            inner.push(Asn1::decode_node(reader)?);
         }
 
-        Ok(Self { tag: tag.0, inner })
+        Ok(Self { tag, inner })
     }
 
     fn compare_tags(tag: Tag) -> bool {
@@ -64,19 +72,12 @@ impl<'data> Asn1ValueDecoder<'data> for ExplicitTag<'data> {
     }
 }
 
-impl Asn1Encoder for ExplicitTag<'_> {
-    fn needed_buf_size(&self) -> usize {
-        let data_len = self.inner.iter().map(|f| f.needed_buf_size()).sum();
-
-        1 /* tag */ + len_size(data_len) + data_len
+impl Asn1ValueEncoder for ExplicitTag<'_> {
+    fn value_len(&self) -> usize {
+        self.inner.iter().map(|f| f.needed_buf_size()).sum()
     }
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        writer.write_byte(self.tag)?;
-
-        let data_len = self.inner.iter().map(|f| f.needed_buf_size()).sum();
-        write_len(data_len, writer)?;
-
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
         self.inner.iter().try_for_each(|f| f.encode(writer))
     }
 }
@@ -1,38 +1,42 @@
-use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 
 use crate::asn1::Asn1;
-use crate::length::{len_size, write_len};
 use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawBytes, RawDataStorage};
 use crate::writer::Writer;
-use crate::{Asn1Decoder, Asn1Encoder, Asn1Result, Asn1ValueDecoder, MetaInfo, Tag, Taggable};
+use crate::{
+    Asn1Decoder, Asn1Encoder, Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, MetaInfo, Tag, TagClass, Taggable,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ImplicitTag<'data> {
-    tag: u8,
-    octets: Cow<'data, [u8]>,
+    tag: Tag,
+    octets: RawBytes<'data>,
     inner: Option<Box<Asn1<'data>>>,
 }
 
 pub type OwnedImplicitTag = ImplicitTag<'static>;
 
 impl<'data> ImplicitTag<'data> {
-    pub fn new_owned(tag: u8, octets: Vec<u8>) -> Self {
-        let inner = Asn1::decode_buff(&octets).ok().map(|mut asn1| {
-            asn1.clear_meta();
-            Box::new(asn1.to_owned_with_asn1(asn1.inner_asn1().to_owned()))
-        });
+    pub fn new_owned(tag_number: u32, octets: Vec<u8>) -> Self {
+        let inner = Asn1::decode_buff(&octets)
+            .ok()
+            .filter(|asn1| asn1.meta().raw_bytes().len() == octets.len())
+            .map(|mut asn1| {
+                asn1.clear_meta();
+                Box::new(asn1.to_shared())
+            });
 
         Self {
-            tag,
-            octets: Cow::Owned(octets),
+            tag: Tag::new(TagClass::ContextSpecific, false, tag_number),
+            octets: RawBytes::from(octets),
             inner,
         }
     }
 
-    pub fn tag_number(&self) -> u8 {
-        self.tag & 0x1f
+    pub fn tag_number(&self) -> u32 {
+        self.tag.number()
     }
 
     pub fn inner_asn1(&self) -> Option<&Asn1<'data>> {
@@ -43,21 +47,45 @@ impl<'data> ImplicitTag<'data> {
         self.octets.as_ref()
     }
 
+    /// Replaces the encapsulated asn1 entity. The octets are re-encoded from it
+    pub fn set_inner(&mut self, inner: Asn1<'data>) -> Asn1Result<()> {
+        self.inner = Some(Box::new(inner));
+
+        self.encode_inner()
+    }
+
+    pub(crate) fn inner_mut(&mut self) -> Option<&mut Asn1<'data>> {
+        self.inner.as_deref_mut()
+    }
+
+    /// Re-encodes the octets from the encapsulated asn1 entity after it was changed
+    pub(crate) fn encode_inner(&mut self) -> Asn1Result<()> {
+        if let Some(inner) = self.inner.as_ref() {
+            self.octets = RawBytes::from(inner.encode_to_vec()?);
+        }
+
+        Ok(())
+    }
+
     pub fn to_owned(&self) -> OwnedImplicitTag {
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedImplicitTag {
         OwnedImplicitTag {
             tag: self.tag,
-            octets: self.octets.to_vec().into(),
+            octets: storage.store_bytes(&self.octets),
             inner: self
                 .inner
                 .as_ref()
-                .map(|inner| Box::new(inner.to_owned_with_asn1(inner.inner_asn1().to_owned()))),
+                .map(|inner| Box::new(inner.to_owned_in(storage, inner.inner_asn1().to_owned_in(storage)))),
         }
     }
 }
 
 impl Taggable for ImplicitTag<'_> {
     fn tag(&self) -> Tag {
-        Tag(self.tag)
+        self.tag
     }
 }
 
@@ -65,20 +93,17 @@ impl<'data> Asn1ValueDecoder<'data> for ImplicitTag<'data> {
     fn decode(tag: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
         let data = reader.read_remaining();
 
-        let mut inner_reader = Reader::new(data);
-        inner_reader.set_next_id(reader.next_id());
-        inner_reader.set_offset(reader.full_offset() - data.len());
-        let mut inner = Asn1::decode(&mut inner_reader).ok().map(Box::new);
+        let inner = if reader.options().decode_encapsulated {
+            let offset = reader.full_offset() - data.len();
 
-        if !inner_reader.empty() && inner.is_some() {
-            inner = None;
-        }
-
-        reader.set_next_id(inner_reader.next_id());
+            Asn1::decode_encapsulated(reader, data, offset)?
+        } else {
+            None
+        };
 
         Ok(Self {
-            tag: tag.0,
-            octets: Cow::Borrowed(data),
+            tag,
+            octets: RawBytes::from(data),
             inner,
         })
     }
@@ -88,16 +113,12 @@ impl<'data> Asn1ValueDecoder<'data> for ImplicitTag<'data> {
     }
 }
 
-impl Asn1Encoder for ImplicitTag<'_> {
-    fn needed_buf_size(&self) -> usize {
-        let data_len = self.octets.len();
-
-        1 /* tag */ + len_size(data_len) + data_len
+impl Asn1ValueEncoder for ImplicitTag<'_> {
+    fn value_len(&self) -> usize {
+        self.octets.len()
     }
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        writer.write_byte(self.tag)?;
-        write_len(self.octets.len(), writer)?;
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
         writer.write_slice(&self.octets)
     }
 }
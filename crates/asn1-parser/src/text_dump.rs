@@ -0,0 +1,392 @@
+//! Text dumps of the asn1 tree in the layouts of the well-known command line tools.
+//!
+//! Offsets and lengths are taken from the [RawAsn1EntityData](crate::RawAsn1EntityData) of every node, so the tree
+//! should be decoded from the input bytes. Trees constructed from scratch have zero offsets.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+use core::str::from_utf8;
+
+use num_bigint_dig::Sign;
+
+use crate::visitor::children;
+use crate::{node_value, Asn1, Asn1Entity, Asn1Type, Asn1Visitor, Tag, TagClass, VisitContext, VisitFlow};
+
+/// Amount of bytes in one hex line of the `dumpasn1` layout
+const DUMPASN1_HEX_LINE_LEN: usize = 16;
+
+/// Layout of the text dump
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum TextDumpStyle {
+    /// `openssl asn1parse -i` layout: offset, depth, header length, length, type, and value
+    #[default]
+    OpensslAsn1Parse,
+    /// `dumpasn1` layout: offset, length, and the indented structure with braces. Encapsulated asn1 entities are
+    /// dumped too
+    DumpAsn1,
+}
+
+/// Renders the asn1 tree as text using the provided layout. Every line ends with `\n`.
+///
+/// ```
+/// use asn1_parser::{text_dump, Asn1, Asn1Decoder, TextDumpStyle};
+///
+/// let asn1 = Asn1::decode_buff(&[0x30, 0x03, 0x02, 0x01, 0x05]).unwrap();
+///
+/// assert_eq!(
+///     text_dump(&asn1, TextDumpStyle::OpensslAsn1Parse),
+///     "    0:d=0  hl=2 l=   3 cons: SEQUENCE          \n    2:d=1  hl=2 l=   1 prim:  INTEGER           :05\n",
+/// );
+/// assert_eq!(
+///     text_dump(&asn1, TextDumpStyle::DumpAsn1),
+///     "   0    3: SEQUENCE {\n   2    1:   INTEGER 5\n         :   }\n",
+/// );
+/// ```
+pub fn text_dump<'a>(asn1: &'a Asn1<'a>, style: TextDumpStyle) -> String {
+    let mut dumper = TextDumper {
+        style,
+        output: String::new(),
+    };
+    dumper.visit(asn1);
+
+    dumper.output
+}
+
+struct TextDumper {
+    style: TextDumpStyle,
+    output: String,
+}
+
+impl<'a> Asn1Visitor<'a> for TextDumper {
+    fn enter_node(&mut self, node: &'a Asn1<'a>, context: &VisitContext<'_>) -> VisitFlow {
+        match self.style {
+            TextDumpStyle::OpensslAsn1Parse => {
+                self.asn1parse_line(node, context.depth());
+
+                // openssl does not parse the encapsulated asn1 entities without `-strparse`
+                if node.tag().is_primitive() {
+                    VisitFlow::SkipChildren
+                } else {
+                    VisitFlow::Continue
+                }
+            }
+            TextDumpStyle::DumpAsn1 => {
+                self.dumpasn1_line(node, context.depth());
+
+                VisitFlow::Continue
+            }
+        }
+    }
+
+    fn leave_node(&mut self, node: &'a Asn1<'a>, context: &VisitContext<'_>) {
+        let meta = node.meta();
+
+        match self.style {
+            TextDumpStyle::OpensslAsn1Parse if meta.is_indefinite_length() => {
+                let offset = meta.tag_position() + meta.raw_bytes().len() - meta.end_of_contents_bytes().len();
+                let _ = writeln!(
+                    self.output,
+                    "{:5}:d={:<2} hl=2 l={:4} prim: {:indent$}{:<18}",
+                    offset,
+                    context.depth() + 1,
+                    0,
+                    "",
+                    "EOC",
+                    indent = context.depth() + 1,
+                );
+            }
+            TextDumpStyle::DumpAsn1 if !children(node).is_empty() => {
+                let _ = writeln!(
+                    self.output,
+                    "{:>10} {:indent$}}}",
+                    ":",
+                    "",
+                    indent = dumpasn1_indent(context.depth() + 1)
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+impl TextDumper {
+    fn asn1parse_line(&mut self, node: &Asn1<'_>, depth: usize) {
+        let meta = node.meta();
+        let tag = node.tag();
+        let header_len = meta.tag_bytes().len() + meta.length_bytes().len();
+        let encoding = if tag.is_constructed() { "cons" } else { "prim" };
+
+        // writing into the string can not fail
+        let _ = write!(
+            self.output,
+            "{:5}:d={:<2} hl={} ",
+            meta.tag_position(),
+            depth,
+            header_len
+        );
+        let _ = if meta.is_indefinite_length() {
+            write!(self.output, "l=inf  {}: ", encoding)
+        } else {
+            write!(self.output, "l={:4} {}: ", meta.data_range().len(), encoding)
+        };
+        let _ = write!(self.output, "{:depth$}{:<18}", "", asn1parse_type_name(node));
+        if let Some(value) = asn1parse_value(node) {
+            self.output.push_str(&value);
+        }
+        self.output.push('\n');
+    }
+
+    fn dumpasn1_line(&mut self, node: &Asn1<'_>, depth: usize) {
+        let meta = node.meta();
+        let indent = dumpasn1_indent(depth);
+
+        let length = if meta.is_indefinite_length() {
+            "NDEF".to_string()
+        } else {
+            meta.data_range().len().to_string()
+        };
+        let _ = write!(
+            self.output,
+            "{:4} {:>4}: {:indent$}{}",
+            meta.tag_position(),
+            length,
+            "",
+            dumpasn1_type_name(node),
+        );
+
+        if !children(node).is_empty() {
+            let block = if node.tag().is_primitive() {
+                ", encapsulates {"
+            } else {
+                " {"
+            };
+            self.output.push_str(block);
+            self.output.push('\n');
+
+            return;
+        }
+
+        match dumpasn1_value(node) {
+            DumpAsn1Value::None => self.output.push('\n'),
+            DumpAsn1Value::Text(value) => {
+                let _ = writeln!(self.output, " {}", value);
+            }
+            DumpAsn1Value::Bytes(bytes) if bytes.len() <= DUMPASN1_HEX_LINE_LEN => {
+                let _ = writeln!(self.output, " {}", hex_bytes(&bytes));
+            }
+            DumpAsn1Value::Bytes(bytes) => {
+                self.output.push('\n');
+                for line in bytes.chunks(DUMPASN1_HEX_LINE_LEN) {
+                    let _ = writeln!(
+                        self.output,
+                        "{:>10} {:indent$}{}",
+                        ":",
+                        "",
+                        hex_bytes(line),
+                        indent = indent + 2
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn dumpasn1_indent(depth: usize) -> usize {
+    depth * 2
+}
+
+/// Type names used by `ASN1_tag2str`
+const OPENSSL_UNIVERSAL_NAMES: [&str; 31] = [
+    "EOC",
+    "BOOLEAN",
+    "INTEGER",
+    "BIT STRING",
+    "OCTET STRING",
+    "NULL",
+    "OBJECT",
+    "OBJECT DESCRIPTOR",
+    "EXTERNAL",
+    "REAL",
+    "ENUMERATED",
+    "EMBEDDED PDV",
+    "UTF8STRING",
+    "RELATIVE OID",
+    "<ASN1 14>",
+    "<ASN1 15>",
+    "SEQUENCE",
+    "SET",
+    "NUMERICSTRING",
+    "PRINTABLESTRING",
+    "T61STRING",
+    "VIDEOTEXSTRING",
+    "IA5STRING",
+    "UTCTIME",
+    "GENERALIZEDTIME",
+    "GRAPHICSTRING",
+    "VISIBLESTRING",
+    "GENERALSTRING",
+    "UNIVERSALSTRING",
+    "<ASN1 29>",
+    "BMPSTRING",
+];
+
+fn asn1parse_type_name(node: &Asn1<'_>) -> String {
+    if let Asn1Type::Unparsed(_) = node.inner_asn1() {
+        return "<UNPARSED>".to_string();
+    }
+
+    let tag = node.tag();
+    match tag.class() {
+        TagClass::Universal => OPENSSL_UNIVERSAL_NAMES
+            .get(tag.number() as usize)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| format!("<ASN1 {}>", tag.number())),
+        TagClass::Application => format!("appl [ {} ]", tag.number()),
+        TagClass::ContextSpecific => format!("cont [ {} ]", tag.number()),
+        TagClass::Private => format!("priv [ {} ]", tag.number()),
+    }
+}
+
+/// Returns the value suffix of the `asn1parse` line
+fn asn1parse_value(node: &Asn1<'_>) -> Option<String> {
+    let value = match node.inner_asn1() {
+        Asn1Type::Bool(boolean) => boolean.as_byte().to_string(),
+        Asn1Type::Integer(integer) => signed_hex(&integer.as_big_int()),
+        Asn1Type::Enumerated(enumerated) => signed_hex(&enumerated.value().as_big_int()),
+        Asn1Type::ObjectIdentifier(oid) => oid.format(),
+        Asn1Type::UtcTime(_) | Asn1Type::GeneralizedTime(_) => text_contents(node.inner_asn1())?,
+        Asn1Type::Utf8String(_)
+        | Asn1Type::NumericString(_)
+        | Asn1Type::PrintableString(_)
+        | Asn1Type::IA5String(_)
+        | Asn1Type::VisibleString(_)
+        | Asn1Type::TeletexString(_) => node_value(node)?,
+        Asn1Type::OctetString(octet_string) if octet_string.octets().is_empty() => return None,
+        Asn1Type::OctetString(octet_string) => match from_utf8(octet_string.octets()) {
+            Ok(text) if text.chars().all(|c| c.is_ascii_graphic() || c == ' ') => text.to_string(),
+            _ => return Some(format!("[HEX DUMP]:{}", hex_string(octet_string.octets()))),
+        },
+        _ => return None,
+    };
+
+    Some(format!(":{}", value))
+}
+
+/// Formats the integer as openssl does: the magnitude in hex with the optional minus sign
+fn signed_hex(value: &num_bigint_dig::BigInt) -> String {
+    let (sign, magnitude) = value.to_bytes_be();
+    let sign = if sign == Sign::Minus { "-" } else { "" };
+
+    format!("{}{}", sign, hex_string(&magnitude))
+}
+
+fn dumpasn1_type_name(node: &Asn1<'_>) -> String {
+    let tag = node.tag();
+    let name = match node.inner_asn1() {
+        Asn1Type::Unparsed(_) => "Unparsed data",
+        _ => match (tag.class(), tag.number()) {
+            (TagClass::Universal, 1) => "BOOLEAN",
+            (TagClass::Universal, 2) => "INTEGER",
+            (TagClass::Universal, 3) => "BIT STRING",
+            (TagClass::Universal, 4) => "OCTET STRING",
+            (TagClass::Universal, 5) => "NULL",
+            (TagClass::Universal, 6) => "OBJECT IDENTIFIER",
+            (TagClass::Universal, 7) => "ObjectDescriptor",
+            (TagClass::Universal, 9) => "REAL",
+            (TagClass::Universal, 10) => "ENUMERATED",
+            (TagClass::Universal, 12) => "UTF8String",
+            (TagClass::Universal, 13) => "RELATIVE-OID",
+            (TagClass::Universal, 16) => "SEQUENCE",
+            (TagClass::Universal, 17) => "SET",
+            (TagClass::Universal, 18) => "NumericString",
+            (TagClass::Universal, 19) => "PrintableString",
+            (TagClass::Universal, 20) => "TeletexString",
+            (TagClass::Universal, 21) => "VideotexString",
+            (TagClass::Universal, 22) => "IA5String",
+            (TagClass::Universal, 23) => "UTCTime",
+            (TagClass::Universal, 24) => "GeneralizedTime",
+            (TagClass::Universal, 25) => "GraphicString",
+            (TagClass::Universal, 26) => "VisibleString",
+            (TagClass::Universal, 27) => "GeneralString",
+            (TagClass::Universal, 28) => "UniversalString",
+            (TagClass::Universal, 30) => "BMPString",
+            _ => return dumpasn1_tag_name(tag),
+        },
+    };
+
+    name.to_string()
+}
+
+fn dumpasn1_tag_name(tag: Tag) -> String {
+    match tag.class() {
+        TagClass::Universal => format!("[UNIVERSAL {}]", tag.number()),
+        TagClass::Application => format!("[APPLICATION {}]", tag.number()),
+        TagClass::ContextSpecific => format!("[{}]", tag.number()),
+        TagClass::Private => format!("[PRIVATE {}]", tag.number()),
+    }
+}
+
+enum DumpAsn1Value {
+    None,
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+fn dumpasn1_value(node: &Asn1<'_>) -> DumpAsn1Value {
+    let asn1 = node.inner_asn1();
+
+    match asn1 {
+        Asn1Type::Null(_) | Asn1Type::Sequence(_) | Asn1Type::Set(_) => DumpAsn1Value::None,
+        Asn1Type::Bool(boolean) => DumpAsn1Value::Text(if boolean.value() { "TRUE" } else { "FALSE" }.to_string()),
+        Asn1Type::Integer(integer) if integer.raw_data().len() <= 4 => {
+            DumpAsn1Value::Text(integer.as_big_int().to_string())
+        }
+        Asn1Type::Integer(integer) => DumpAsn1Value::Bytes(integer.raw_data().to_vec()),
+        Asn1Type::Enumerated(enumerated) => DumpAsn1Value::Text(enumerated.value().as_big_int().to_string()),
+        Asn1Type::ObjectIdentifier(oid) => DumpAsn1Value::Text(format!("'{}'", oid.format().replace('.', " "))),
+        Asn1Type::RelativeOid(oid) => DumpAsn1Value::Text(format!("'{}'", oid.format().replace('.', " "))),
+        Asn1Type::UtcTime(_) | Asn1Type::GeneralizedTime(_) => match text_contents(asn1) {
+            Some(time) => DumpAsn1Value::Text(format!("'{}'", time)),
+            None => DumpAsn1Value::Bytes(asn1.contents()),
+        },
+        Asn1Type::BitString(_) if node.tag().is_primitive() => {
+            let contents = asn1.contents();
+            match contents.split_first() {
+                Some((0, bits)) => DumpAsn1Value::Bytes(bits.to_vec()),
+                Some((unused_bits, bits)) => {
+                    DumpAsn1Value::Text(format!("{} unused bits {}", unused_bits, hex_bytes(bits)))
+                }
+                None => DumpAsn1Value::None,
+            }
+        }
+        _ => match node_value(node) {
+            Some(text) => DumpAsn1Value::Text(format!("'{}'", text)),
+            None => DumpAsn1Value::Bytes(asn1.contents()),
+        },
+    }
+}
+
+fn text_contents(asn1: &Asn1Type<'_>) -> Option<String> {
+    from_utf8(&asn1.contents()).ok().map(ToString::to_string)
+}
+
+/// Formats bytes as uppercase hex without separators
+fn hex_string(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(hex, "{:02X}", byte);
+    }
+
+    hex
+}
+
+/// Formats bytes as uppercase hex separated by spaces
+fn hex_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
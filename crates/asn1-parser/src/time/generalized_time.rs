@@ -1,15 +1,13 @@
+use alloc::boxed::Box;
 use alloc::format;
 use alloc::string::String;
+use alloc::vec::Vec;
 use core::str::from_utf8;
 
-#[cfg(not(feature = "std"))]
-use num_traits::float::FloatCore;
-
 use super::{read_number, Day, Hour, Minute, Month};
-use crate::length::{len_size, write_len};
 use crate::reader::Reader;
 use crate::writer::Writer;
-use crate::{Asn1Encoder, Asn1Result, Asn1ValueDecoder, Error, Tag, Taggable};
+use crate::{Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, MetaInfo, Tag, Taggable};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Year(u16);
@@ -66,7 +64,7 @@ impl TryFrom<u8> for Second {
         if value < 60 {
             Ok(Self(value.into()))
         } else {
-            Err("invalid value".into())
+            Err(Error::new(ErrorKind::InvalidValue, "invalid value"))
         }
     }
 }
@@ -78,7 +76,7 @@ impl TryFrom<f32> for Second {
         if value < 60.0 {
             Ok(Self(value))
         } else {
-            Err("invalid value".into())
+            Err(Error::new(ErrorKind::InvalidValue, "invalid value"))
         }
     }
 }
@@ -114,7 +112,7 @@ impl TryFrom<u8> for LocalTimeDirection {
         match value {
             b'-' => Ok(Self::Minus),
             b'+' => Ok(Self::Plus),
-            _ => Err("invalid GeneralTime data".into()),
+            _ => Err(Error::new(ErrorKind::InvalidValue, "invalid GeneralTime data")),
         }
     }
 }
@@ -146,7 +144,17 @@ impl LocalTimeDiffFactor {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Decoded value that differs from the canonical encoding: the fraction with trailing zeros or more than three digits,
+/// or the local time without the time difference
+#[derive(Debug, Clone)]
+struct OriginalEncoding {
+    data: Vec<u8>,
+    /// Fields decoded from the data. The fields of [GeneralizedTime] are public, so they are compared with this copy
+    /// to find out whether the value was changed after decoding
+    decoded: Box<GeneralizedTime>,
+}
+
+#[derive(Debug, Clone)]
 pub struct GeneralizedTime {
     pub year: Year,
     pub month: Month,
@@ -155,10 +163,26 @@ pub struct GeneralizedTime {
     pub minute: Minute,
     pub second: Second,
     pub local_time: Option<LocalTimeDiffFactor>,
+    encoded: Option<OriginalEncoding>,
 }
 
+// The original encoding is not compared: different encodings of the same time are equal.
+impl PartialEq for GeneralizedTime {
+    fn eq(&self, other: &Self) -> bool {
+        self.year == other.year
+            && self.month == other.month
+            && self.day == other.day
+            && self.hour == other.hour
+            && self.minute == other.minute
+            && self.second == other.second
+            && self.local_time == other.local_time
+    }
+}
+
+impl Eq for GeneralizedTime {}
+
 impl GeneralizedTime {
-    pub const TAG: Tag = Tag(24);
+    pub const TAG: Tag = Tag::from_byte(24);
 
     pub fn new(
         year: Year,
@@ -177,17 +201,53 @@ impl GeneralizedTime {
             minute,
             second,
             local_time,
+            encoded: None,
+        }
+    }
+
+    /// Formats seconds with the millisecond precision. The fraction has no trailing zeros as DER requires
+    fn format_second(&self) -> String {
+        // fractions above 59.999 must not be rounded up to the invalid 60.000
+        let millis = ((self.second.as_ref() * 1000.0).round() as u16).min(59_999);
+
+        if millis % 1000 == 0 {
+            format!("{:02}", millis / 1000)
+        } else {
+            let formatted = format!("{:02}.{:03}", millis / 1000, millis % 1000);
+            formatted.trim_end_matches('0').into()
         }
     }
 
     fn calc_data_len(&self) -> usize {
-        let second_len = if self.second.as_ref().fract() > f32::EPSILON {
-            2 /* int part */ + 1 /* dot */ + 3 /* fract part */
+        let local_time_len = if self.local_time.is_some() {
+            LocalTimeDiffFactor::ENCODED_LEN
         } else {
-            2
+            1 /* 'Z' */
         };
 
-        2 /* year */ + 2 /* month */ + 2 /* day */ + 2 /* hour */ + 2 /* minute */ + second_len + LocalTimeDiffFactor::ENCODED_LEN
+        4 /* year */ + 2 /* month */ + 2 /* day */ + 2 /* hour */ + 2 /* minute */ + self.format_second().len() + local_time_len
+    }
+
+    /// Returns the decoded value if it is not canonical and still matches the fields
+    fn original_encoding(&self) -> Option<&[u8]> {
+        let encoded = self.encoded.as_ref()?;
+
+        (*encoded.decoded == *self).then_some(encoded.data.as_slice())
+    }
+
+    fn encode_canonical(&self, writer: &mut Writer) -> Asn1Result<()> {
+        self.year.to_writer(writer)?;
+        writer.write_slice(format!("{:02}", self.month.as_ref()).as_bytes())?;
+        writer.write_slice(format!("{:02}", self.day.as_ref()).as_bytes())?;
+        writer.write_slice(format!("{:02}", self.hour.as_ref()).as_bytes())?;
+        writer.write_slice(format!("{:02}", self.minute.as_ref()).as_bytes())?;
+        writer.write_slice(self.format_second().as_bytes())?;
+
+        if let Some(local_time) = self.local_time.as_ref() {
+            local_time.to_writer(writer)
+        } else {
+            writer.write_byte(b'Z')
+        }
     }
 }
 
@@ -199,6 +259,32 @@ impl Taggable for GeneralizedTime {
 
 impl<'data> Asn1ValueDecoder<'data> for GeneralizedTime {
     fn decode(_: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
+        let data = reader.remaining();
+        let mut generalized_time = Self::decode_fields(reader)?;
+
+        let mut canonical = Writer::growable();
+        generalized_time.encode_canonical(&mut canonical)?;
+        if canonical.into_vec()? != data {
+            generalized_time.encoded = Some(OriginalEncoding {
+                data: data.to_vec(),
+                decoded: Box::new(generalized_time.clone()),
+            });
+        }
+
+        Ok(generalized_time)
+    }
+
+    fn compare_tags(tag: Tag) -> bool {
+        Self::TAG == tag
+    }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
+    }
+}
+
+impl GeneralizedTime {
+    fn decode_fields(reader: &mut Reader<'_>) -> Asn1Result<Self> {
         let year = Year::from_reader(reader)?;
         let month = Month::try_from(read_number(reader)?)?;
         let day = Day::try_from(read_number(reader)?)?;
@@ -214,8 +300,9 @@ impl<'data> Asn1ValueDecoder<'data> for GeneralizedTime {
                 day,
                 hour,
                 minute,
-                second: second_int_part.try_into().unwrap(),
+                second: second_int_part.try_into()?,
                 local_time: None,
+                encoded: None,
             });
         }
 
@@ -226,8 +313,9 @@ impl<'data> Asn1ValueDecoder<'data> for GeneralizedTime {
                 day,
                 hour,
                 minute,
-                second: second_int_part.try_into().unwrap(),
+                second: second_int_part.try_into()?,
                 local_time: None,
+                encoded: None,
             }),
             '+' | '-' => Ok(Self {
                 year,
@@ -235,8 +323,9 @@ impl<'data> Asn1ValueDecoder<'data> for GeneralizedTime {
                 day,
                 hour,
                 minute,
-                second: second_int_part.try_into().unwrap(),
+                second: second_int_part.try_into()?,
                 local_time: Some(LocalTimeDiffFactor::from_reader(reader)?),
+                encoded: None,
             }),
             '.' => {
                 // sorry for this code
@@ -256,8 +345,9 @@ impl<'data> Asn1ValueDecoder<'data> for GeneralizedTime {
                             day,
                             hour,
                             minute,
-                            second: seconds.try_into().unwrap(),
+                            second: seconds.try_into()?,
                             local_time: None,
+                            encoded: None,
                         });
                     }
 
@@ -272,8 +362,9 @@ impl<'data> Asn1ValueDecoder<'data> for GeneralizedTime {
                         day,
                         hour,
                         minute,
-                        second: seconds.try_into().unwrap(),
+                        second: seconds.try_into()?,
                         local_time: None,
+                        encoded: None,
                     }),
                     '+' | '-' => Ok(Self {
                         year,
@@ -281,48 +372,42 @@ impl<'data> Asn1ValueDecoder<'data> for GeneralizedTime {
                         day,
                         hour,
                         minute,
-                        second: seconds.try_into().unwrap(),
+                        second: seconds.try_into()?,
                         local_time: Some(LocalTimeDiffFactor::from_reader(reader)?),
+                        encoded: None,
                     }),
-                    _ => Err("invalid GeneralTime data: invalid char after second frac part".into()),
+                    _ => Err(Error::new(
+                        ErrorKind::InvalidValue,
+                        "invalid GeneralTime data: invalid char after second frac part",
+                    )),
                 }
             }
-            _ => Err("invalid GeneralTime data: invalid char after second int part".into()),
+            _ => Err(Error::new(
+                ErrorKind::InvalidValue,
+                "invalid GeneralTime data: invalid char after second int part",
+            )),
         }
     }
-
-    fn compare_tags(tag: Tag) -> bool {
-        Self::TAG == tag
-    }
 }
 
-impl Asn1Encoder for GeneralizedTime {
-    fn needed_buf_size(&self) -> usize {
-        let value_len = self.calc_data_len();
-
-        1 /* tag */ + len_size(value_len) + value_len
+impl Asn1ValueEncoder for GeneralizedTime {
+    fn value_len(&self) -> usize {
+        match self.original_encoding() {
+            Some(encoded) => encoded.len(),
+            None => self.calc_data_len(),
+        }
     }
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        writer.write_byte(Self::TAG.into())?;
-        write_len(self.calc_data_len(), writer)?;
-
-        self.year.to_writer(writer)?;
-        writer.write_slice(format!("{:02}", self.month.as_ref()).as_bytes())?;
-        writer.write_slice(format!("{:02}", self.day.as_ref()).as_bytes())?;
-        writer.write_slice(format!("{:02}", self.hour.as_ref()).as_bytes())?;
-        writer.write_slice(format!("{:02}", self.minute.as_ref()).as_bytes())?;
-
-        if self.second.as_ref().fract() > f32::EPSILON {
-            writer.write_slice(format!("{:06.3}", self.second.as_ref()).as_bytes())?;
-        } else {
-            writer.write_slice(format!("{:02}", *self.second.as_ref() as u8).as_bytes())?;
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        match self.original_encoding() {
+            Some(encoded) => writer.write_slice(encoded),
+            None => self.encode_canonical(writer),
         }
+    }
+}
 
-        if let Some(local_time) = self.local_time.as_ref() {
-            local_time.to_writer(writer)
-        } else {
-            writer.write_byte(b'Z')
-        }
+impl MetaInfo for GeneralizedTime {
+    fn clear_meta(&mut self) {
+        self.encoded = None;
     }
 }
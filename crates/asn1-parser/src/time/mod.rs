@@ -1,11 +1,15 @@
 mod generalized_time;
+#[cfg(feature = "time")]
+mod timestamp;
 mod utc_time;
 
-pub use generalized_time::GeneralizedTime;
+pub use generalized_time::{
+    GeneralizedTime, LocalTimeDiffFactor, LocalTimeDirection, Second as GeneralizedSecond, Year as GeneralizedYear,
+};
 pub use utc_time::UtcTime;
 
 use crate::reader::Reader;
-use crate::Asn1Result;
+use crate::{Asn1Result, Error, ErrorKind};
 
 macro_rules! define_nt {
     ($name:ident) => {
@@ -31,7 +35,7 @@ macro_rules! define_nt {
                 if value < 100 {
                     Ok($name(value))
                 } else {
-                    Err("invalid value".into())
+                    Err(Error::new(ErrorKind::InvalidValue, "invalid value"))
                 }
             }
         }
@@ -51,8 +55,8 @@ fn read_number(reader: &mut Reader<'_>) -> Asn1Result<u8> {
     let f = char::from(reader.read_byte()?);
     let s = char::from(reader.read_byte()?);
 
-    if !f.is_numeric() || !s.is_numeric() {
-        return Err("invalid bytes for utctime".into());
+    if !f.is_ascii_digit() || !s.is_ascii_digit() {
+        return Err(Error::new(ErrorKind::InvalidValue, "invalid bytes for utctime"));
     }
 
     Ok((f as u8 - ASCII_SHIFT) * 10 + (s as u8 - ASCII_SHIFT))
@@ -0,0 +1,134 @@
+//! Conversions between asn1 times and [OffsetDateTime].
+//!
+//! Timestamps are normalised to UTC in both directions: the local time difference of GeneralizedTime is applied, and
+//! encoded times always end with `Z`. Fractional seconds are kept with the millisecond precision.
+
+use ::time::{Date, Month as CalendarMonth, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use super::generalized_time::{LocalTimeDirection, Second as GeneralizedSecond, Year as GeneralizedYear};
+use super::{Day, Hour, Minute, Month, Second, Year};
+use crate::{Asn1Result, Error, ErrorKind, GeneralizedTime, UtcTime};
+
+const MILLIS_IN_SECOND: f32 = 1000.0;
+
+fn date(year: u16, month: &Month, day: &Day) -> Asn1Result<Date> {
+    Ok(Date::from_calendar_date(
+        i32::from(year),
+        CalendarMonth::try_from(*month.as_ref())?,
+        *day.as_ref(),
+    )?)
+}
+
+impl TryFrom<&UtcTime> for OffsetDateTime {
+    type Error = Error;
+
+    /// Converts UTCTime into a timestamp. The century is chosen by [UtcTime::full_year]
+    fn try_from(utc_time: &UtcTime) -> Asn1Result<Self> {
+        let time = Time::from_hms(
+            *utc_time.hour.as_ref(),
+            *utc_time.minute.as_ref(),
+            utc_time
+                .second
+                .as_ref()
+                .map(|second| *second.as_ref())
+                .unwrap_or_default(),
+        )?;
+
+        Ok(PrimitiveDateTime::new(date(utc_time.full_year(), &utc_time.month, &utc_time.day)?, time).assume_utc())
+    }
+}
+
+impl TryFrom<OffsetDateTime> for UtcTime {
+    type Error = Error;
+
+    /// Converts a timestamp into UTCTime. Fails if the year is out of the 1950..=2049 range
+    fn try_from(timestamp: OffsetDateTime) -> Asn1Result<Self> {
+        let timestamp = timestamp.to_offset(UtcOffset::UTC);
+
+        if !(1950..2050).contains(&timestamp.year()) {
+            return Err(Error::new(
+                ErrorKind::InvalidValue,
+                "UTCTime can represent only years from 1950 to 2049",
+            ));
+        }
+
+        Ok(UtcTime::new(
+            Year::try_from(u8::try_from(timestamp.year() % 100)?)?,
+            Month::try_from(u8::from(timestamp.month()))?,
+            Day::try_from(timestamp.day())?,
+            Hour::try_from(timestamp.hour())?,
+            Minute::try_from(timestamp.minute())?,
+            Some(Second::try_from(timestamp.second())?),
+        ))
+    }
+}
+
+impl TryFrom<&GeneralizedTime> for OffsetDateTime {
+    type Error = Error;
+
+    /// Converts GeneralizedTime into a UTC timestamp. The local time without the time difference is treated as UTC
+    fn try_from(generalized_time: &GeneralizedTime) -> Asn1Result<Self> {
+        let seconds = *generalized_time.second.as_ref();
+        let millis = ((seconds.fract() * MILLIS_IN_SECOND).round() as u16).min(999);
+
+        let time = Time::from_hms_milli(
+            *generalized_time.hour.as_ref(),
+            *generalized_time.minute.as_ref(),
+            seconds.trunc() as u8,
+            millis,
+        )?;
+        let date = date(
+            *generalized_time.year.as_ref(),
+            &generalized_time.month,
+            &generalized_time.day,
+        )?;
+
+        let offset = match generalized_time.local_time.as_ref() {
+            Some(local_time) => {
+                let sign = match local_time.time_direction {
+                    LocalTimeDirection::Plus => 1,
+                    LocalTimeDirection::Minus => -1,
+                };
+
+                UtcOffset::from_hms(
+                    sign * i8::try_from(*local_time.hour.as_ref())?,
+                    sign * i8::try_from(*local_time.minute.as_ref())?,
+                    0,
+                )?
+            }
+            None => UtcOffset::UTC,
+        };
+
+        Ok(PrimitiveDateTime::new(date, time)
+            .assume_offset(offset)
+            .to_offset(UtcOffset::UTC))
+    }
+}
+
+impl TryFrom<OffsetDateTime> for GeneralizedTime {
+    type Error = Error;
+
+    /// Converts a timestamp into GeneralizedTime in UTC. Fails if the year is out of the 0..=9999 range
+    fn try_from(timestamp: OffsetDateTime) -> Asn1Result<Self> {
+        let timestamp = timestamp.to_offset(UtcOffset::UTC);
+
+        if !(0..=9999).contains(&timestamp.year()) {
+            return Err(Error::new(
+                ErrorKind::InvalidValue,
+                "GeneralizedTime can represent only years from 0 to 9999",
+            ));
+        }
+
+        let seconds = f32::from(timestamp.second()) + f32::from(timestamp.millisecond()) / MILLIS_IN_SECOND;
+
+        Ok(GeneralizedTime::new(
+            GeneralizedYear::new(u16::try_from(timestamp.year())?),
+            Month::try_from(u8::from(timestamp.month()))?,
+            Day::try_from(timestamp.day())?,
+            Hour::try_from(timestamp.hour())?,
+            Minute::try_from(timestamp.minute())?,
+            GeneralizedSecond::try_from(seconds)?,
+            None,
+        ))
+    }
+}
@@ -1,10 +1,9 @@
 use alloc::format;
 
 use super::{read_number, Day, Hour, Minute, Month, Second, Year};
-use crate::length::{len_size, write_len};
 use crate::reader::Reader;
 use crate::writer::Writer;
-use crate::{Asn1Encoder, Asn1Result, Asn1ValueDecoder, Tag, Taggable};
+use crate::{Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, Tag, Taggable};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UtcTime {
@@ -17,7 +16,7 @@ pub struct UtcTime {
 }
 
 impl UtcTime {
-    pub const TAG: Tag = Tag(23);
+    pub const TAG: Tag = Tag::from_byte(23);
 
     pub fn new(year: Year, month: Month, day: Day, hour: Hour, minute: Minute, second: Option<Second>) -> Self {
         Self {
@@ -30,6 +29,18 @@ impl UtcTime {
         }
     }
 
+    /// Returns the four-digit year. As RFC 5280 requires, two-digit years greater than or equal to 50 are
+    /// interpreted as 19YY, and less than 50 as 20YY
+    pub fn full_year(&self) -> u16 {
+        let year = u16::from(*self.year.as_ref());
+
+        if year >= 50 {
+            1900 + year
+        } else {
+            2000 + year
+        }
+    }
+
     fn calc_data_len(&self) -> usize {
         2 /* year */ + 2 /* month */ + 2 /* day */ + 2 /* hour */ + 2 /* minute */ + self.second.is_some().then_some(2).unwrap_or_default() + 1
         /* 'Z' */
@@ -52,7 +63,7 @@ impl<'data> Asn1ValueDecoder<'data> for UtcTime {
         };
 
         if reader.read_byte()? != b'Z' {
-            return Err("utctime value should end with 'Z'".into());
+            return Err(Error::new(ErrorKind::InvalidValue, "utctime value should end with 'Z'"));
         }
 
         Ok(utc_time)
@@ -61,6 +72,10 @@ impl<'data> Asn1ValueDecoder<'data> for UtcTime {
     fn compare_tags(tag: Tag) -> bool {
         Self::TAG == tag
     }
+
+    fn expected_tag() -> Option<Tag> {
+        Some(Self::TAG)
+    }
 }
 
 impl Taggable for UtcTime {
@@ -69,17 +84,12 @@ impl Taggable for UtcTime {
     }
 }
 
-impl Asn1Encoder for UtcTime {
-    fn needed_buf_size(&self) -> usize {
-        let value_len = self.calc_data_len();
-
-        1 /* tag */ + len_size(value_len) + value_len
+impl Asn1ValueEncoder for UtcTime {
+    fn value_len(&self) -> usize {
+        self.calc_data_len()
     }
 
-    fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        writer.write_byte(Self::TAG.into())?;
-        write_len(self.calc_data_len(), writer)?;
-
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
         writer.write_slice(format!("{:02}", self.year.as_ref()).as_bytes())?;
         writer.write_slice(format!("{:02}", self.month.as_ref()).as_bytes())?;
         writer.write_slice(format!("{:02}", self.day.as_ref()).as_bytes())?;
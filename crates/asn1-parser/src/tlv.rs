@@ -1,10 +1,14 @@
-use alloc::borrow::Cow;
-
-use crate::length::read_len;
+use crate::length::{
+    indefinite_len, len_size_with_octets, read_len, write_len_with_octets_and_value, Length, END_OF_CONTENTS,
+    INDEFINITE_LENGTH,
+};
 use crate::reader::{read_data, Reader};
+use crate::shared::RawDataStorage;
+use crate::tag::{read_tag, tag_size, write_tag};
 use crate::writer::Writer;
 use crate::{
-    Asn1Decoder, Asn1Encoder, Asn1Entity, Asn1Result, Asn1ValueDecoder, MetaInfo, RawAsn1EntityData, Tag, Taggable,
+    Asn1Decoder, Asn1Encoder, Asn1Entity, Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind,
+    ErrorPathNode, MetaInfo, RawAsn1EntityData, Tag, Taggable,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -25,10 +29,22 @@ impl<A> Tlv<'_, A> {
         &self.meta
     }
 
+    pub(crate) fn set_id(&mut self, id: u64) {
+        self.id = id;
+    }
+
     pub fn inner_asn1(&self) -> &A {
         &self.asn1
     }
 
+    pub fn inner_asn1_mut(&mut self) -> &mut A {
+        &mut self.asn1
+    }
+
+    pub fn into_inner_asn1(self) -> A {
+        self.asn1
+    }
+
     pub fn to_owned_with_asn1<B>(&self, asn1: B) -> OwnedTlv<B> {
         OwnedTlv {
             id: self.id,
@@ -36,6 +52,15 @@ impl<A> Tlv<'_, A> {
             asn1,
         }
     }
+
+    /// Returns owned version of the entity with the raw data kept in the provided storage
+    pub(crate) fn to_owned_in<B>(&self, storage: &impl RawDataStorage, asn1: B) -> OwnedTlv<B> {
+        OwnedTlv {
+            id: self.id,
+            meta: storage.store(&self.meta),
+            asn1,
+        }
+    }
 }
 
 impl<A: MetaInfo> MetaInfo for Tlv<'_, A> {
@@ -62,23 +87,93 @@ impl<'data, A: Asn1ValueDecoder<'data>> Asn1Decoder<'data> for Tlv<'data, A> {
     }
 
     fn decode(reader: &mut Reader<'data>) -> Asn1Result<Self> {
+        Self::decode_with(reader, A::compare_tags, A::expected_tag(), A::decode)
+    }
+}
+
+impl<'data, A> Tlv<'data, A> {
+    /// Decodes the asn1 entity using provided tag matcher and value decoder instead of the [Asn1ValueDecoder] ones.
+    pub(crate) fn decode_with(
+        reader: &mut Reader<'data>,
+        compare_tags: impl Fn(Tag) -> bool,
+        expected_tag: Option<Tag>,
+        decode_value: impl FnOnce(Tag, &mut Reader<'data>) -> Asn1Result<A>,
+    ) -> Asn1Result<Self> {
         let tag_position = reader.full_offset();
         let data_start = reader.position();
 
-        let tag = Tag(reader.read_byte()?);
+        reader.enter_node()?;
+        let tag = read_tag(reader)?;
+        if !compare_tags(tag) {
+            return Err(Error::unexpected_tag(expected_tag, tag).with_offset(tag_position));
+        }
 
+        let length_offset = reader.full_offset();
         let (len, len_range) = read_len(reader)?;
 
-        let (data, data_range) = read_data(reader, len)?;
-
-        let mut inner_reader = Reader::new(data);
-        inner_reader.set_next_id(reader.next_id());
-        inner_reader.set_offset(reader.full_offset() - data.len());
-        let asn1 = A::decode(tag, &mut inner_reader)?;
-
-        reader.set_next_id(inner_reader.next_id());
+        let data_offset = reader.full_offset();
+        let (data, data_range) = match len {
+            Length::Definite(len) if reader.is_lenient() && len > reader.remaining().len() => {
+                reader.push_diagnostic(
+                    Error::new(
+                        ErrorKind::UnexpectedEof,
+                        "Length exceeds the remaining data and was clamped",
+                    )
+                    .with_offset(length_offset),
+                );
+
+                read_data(reader, reader.remaining().len())?
+            }
+            Length::Definite(len) => read_data(reader, len)?,
+            Length::Indefinite => {
+                if tag.is_primitive() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidLength,
+                        "Indefinite length is not allowed for primitive types",
+                    )
+                    .with_offset(length_offset));
+                }
+
+                match indefinite_len(reader) {
+                    Ok(len) => {
+                        let data = read_data(reader, len)?;
+
+                        let end_of_contents_offset = reader.full_offset();
+                        if reader.read(END_OF_CONTENTS.len())? != END_OF_CONTENTS {
+                            return Err(Error::new(ErrorKind::InvalidLength, "Invalid end-of-contents octets")
+                                .with_offset(end_of_contents_offset));
+                        }
+
+                        data
+                    }
+                    Err(_) if reader.is_lenient() => {
+                        reader.push_diagnostic(
+                            Error::new(ErrorKind::InvalidLength, "Missing end-of-contents octets")
+                                .with_offset(length_offset),
+                        );
+
+                        read_data(reader, reader.remaining().len())?
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+        };
+
+        let node = ErrorPathNode {
+            tag,
+            offset: tag_position,
+        };
+
+        let mut inner_reader = reader.nested(data, data_offset);
+        inner_reader.set_lenient(reader.is_lenient());
+        let asn1 = decode_value(tag, &mut inner_reader).map_err(|error| error.in_node(node, data_offset))?;
+
+        reader.finish_nested(&inner_reader);
+        for diagnostic in inner_reader.take_diagnostics() {
+            reader.push_diagnostic(diagnostic.in_node(node, data_offset));
+        }
 
-        let raw_data = Cow::Borrowed(reader.data_in_range(data_start..data_range.end)?);
+        let raw_data = reader.data_in_range(data_start..reader.position())?.into();
         let length = (len_range.start - data_start)..(len_range.end - data_start);
         let data = (data_range.start - data_start)..(data_range.end - data_start);
 
@@ -95,12 +190,45 @@ impl<'data, A: Asn1ValueDecoder<'data>> Asn1Decoder<'data> for Tlv<'data, A> {
     }
 }
 
-impl<A: Asn1Encoder> Asn1Encoder for Tlv<'_, A> {
+impl<A: Taggable> Tlv<'_, A> {
+    /// Returns `true` if the decoded indefinite length form should be kept on encoding.
+    ///
+    /// Only constructed entities can use it. Constructed strings become primitive when their encapsulated entity
+    /// is replaced, so they are encoded using the definite length form then.
+    fn keeps_indefinite_length(&self) -> bool {
+        self.meta.is_indefinite_length() && self.asn1.tag().is_constructed()
+    }
+}
+
+impl<A: Asn1ValueEncoder> Asn1Encoder for Tlv<'_, A> {
     fn needed_buf_size(&self) -> usize {
-        self.asn1.needed_buf_size()
+        if self.keeps_indefinite_length() {
+            tag_size(self.asn1.tag()) + 1 /* indefinite length */ + self.asn1.value_len() + END_OF_CONTENTS.len()
+        } else if let Some(octets) = self.meta.non_minimal_length_octets() {
+            let value_len = self.asn1.value_len();
+
+            tag_size(self.asn1.tag()) + len_size_with_octets(value_len, octets) + value_len
+        } else {
+            self.asn1.needed_buf_size()
+        }
     }
 
     fn encode(&self, writer: &mut Writer) -> Asn1Result<()> {
-        self.asn1.encode(writer)
+        if self.keeps_indefinite_length() {
+            write_tag(self.asn1.tag(), writer)?;
+            writer.write_byte(INDEFINITE_LENGTH)?;
+            self.asn1.encode_value(writer)?;
+            writer.write_slice(&END_OF_CONTENTS)
+        } else if let Some(octets) = self.meta.non_minimal_length_octets() {
+            write_tag(self.asn1.tag(), writer)?;
+            write_len_with_octets_and_value(
+                writer,
+                octets,
+                || self.asn1.value_len(),
+                |writer| self.asn1.encode_value(writer),
+            )
+        } else {
+            self.asn1.encode(writer)
+        }
     }
 }
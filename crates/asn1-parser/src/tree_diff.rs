@@ -0,0 +1,219 @@
+//! Structural diff between two asn1 trees.
+//!
+//! Children of the constructed nodes (and the asn1 entities encapsulated into OCTET STRING, BIT STRING, and
+//! IMPLICIT tags) are matched using the longest common subsequence, so an inserted or removed field does not make
+//! all subsequent fields different.
+//!
+//! Nodes are compared by the raw data from their meta info, so the trees are not encoded again during the
+//! comparison. Only the nodes without raw data (e.g. built from scratch) are encoded.
+
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::visitor::children;
+use crate::{Asn1, Asn1Encoder, Asn1Entity, Asn1Result, Asn1Type};
+
+/// Children lists with more pairs than this limit are matched by their positions instead of the LCS
+const MAX_LCS_PAIRS: usize = 1 << 20;
+
+/// Kind of the difference between two asn1 trees
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Asn1ChangeKind {
+    /// The node is present only in the changed tree
+    Inserted,
+    /// The node is present only in the original tree
+    Removed,
+    /// The node has a different tag or value in the changed tree
+    Changed,
+}
+
+impl fmt::Display for Asn1ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Asn1ChangeKind::Inserted => "inserted",
+            Asn1ChangeKind::Removed => "removed",
+            Asn1ChangeKind::Changed => "changed",
+        })
+    }
+}
+
+/// Position of the changed node in one of the compared trees
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Asn1ChangedNode {
+    pub node_id: u64,
+    /// Absolute position of the node in the input data
+    pub offset: usize,
+}
+
+/// Difference between two asn1 trees
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asn1Change {
+    pub kind: Asn1ChangeKind,
+    /// The node in the original tree. It's `None` for inserted nodes
+    pub original: Option<Asn1ChangedNode>,
+    /// The node in the changed tree. It's `None` for removed nodes
+    pub changed: Option<Asn1ChangedNode>,
+}
+
+/// Compares two asn1 trees and returns inserted, removed, and changed nodes in the tree order.
+///
+/// Only the innermost differences are reported: if the field of the SEQUENCE is changed, then the SEQUENCE itself
+/// is not reported. Nodes with different tags are reported as changed without comparing their children.
+/// Fails if the node without raw data can not be encoded.
+pub fn diff_asn1(original: &Asn1<'_>, changed: &Asn1<'_>) -> Asn1Result<Vec<Asn1Change>> {
+    let mut changes = Vec::new();
+    diff_node(original, changed, &mut changes)?;
+
+    Ok(changes)
+}
+
+fn position(asn1: &Asn1<'_>) -> Option<Asn1ChangedNode> {
+    Some(Asn1ChangedNode {
+        node_id: asn1.id(),
+        offset: asn1.meta().tag_position(),
+    })
+}
+
+fn diff_node(original: &Asn1<'_>, changed: &Asn1<'_>, changes: &mut Vec<Asn1Change>) -> Asn1Result<()> {
+    let is_changed = if original.tag() != changed.tag() {
+        true
+    } else if has_children(original) && has_children(changed) {
+        diff_children(&children(original), &children(changed), changes)?;
+        false
+    } else {
+        encoding(original)? != encoding(changed)?
+    };
+
+    if is_changed {
+        changes.push(Asn1Change {
+            kind: Asn1ChangeKind::Changed,
+            original: position(original),
+            changed: position(changed),
+        });
+    }
+
+    Ok(())
+}
+
+fn diff_children(original: &[&Asn1<'_>], changed: &[&Asn1<'_>], changes: &mut Vec<Asn1Change>) -> Asn1Result<()> {
+    let original_encodings = original
+        .iter()
+        .map(|asn1| encoding(asn1))
+        .collect::<Asn1Result<Vec<_>>>()?;
+    let changed_encodings = changed
+        .iter()
+        .map(|asn1| encoding(asn1))
+        .collect::<Asn1Result<Vec<_>>>()?;
+    // 2 for equal nodes, 1 for nodes with the same tag, and 0 if the nodes can not be matched
+    let score = |i: usize, j: usize| {
+        if original_encodings[i] == changed_encodings[j] {
+            2
+        } else if original[i].tag() == changed[j].tag() {
+            1
+        } else {
+            0
+        }
+    };
+
+    for pair in match_children(original.len(), changed.len(), score) {
+        match pair {
+            (Some(i), Some(j)) => {
+                if original_encodings[i] != changed_encodings[j] {
+                    diff_node(original[i], changed[j], changes)?;
+                }
+            }
+            (Some(i), None) => changes.push(Asn1Change {
+                kind: Asn1ChangeKind::Removed,
+                original: position(original[i]),
+                changed: None,
+            }),
+            (None, Some(j)) => changes.push(Asn1Change {
+                kind: Asn1ChangeKind::Inserted,
+                original: None,
+                changed: position(changed[j]),
+            }),
+            (None, None) => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Matches the children using the LCS with the maximal total score. Returns pairs of matched indices in order.
+fn match_children(
+    original_len: usize,
+    changed_len: usize,
+    score: impl Fn(usize, usize) -> usize,
+) -> Vec<(Option<usize>, Option<usize>)> {
+    if original_len.saturating_mul(changed_len) > MAX_LCS_PAIRS {
+        return (0..original_len.max(changed_len))
+            .map(|i| {
+                (
+                    Some(i).filter(|i| *i < original_len),
+                    Some(i).filter(|i| *i < changed_len),
+                )
+            })
+            .collect();
+    }
+
+    // table[i][j] is the best score for original[i..] and changed[j..]
+    let width = changed_len + 1;
+    let mut table = vec![0; (original_len + 1) * width];
+    for i in (0..original_len).rev() {
+        for j in (0..changed_len).rev() {
+            let pair_score = score(i, j);
+            let matched = if pair_score > 0 {
+                pair_score + table[(i + 1) * width + j + 1]
+            } else {
+                0
+            };
+            table[i * width + j] = matched.max(table[(i + 1) * width + j]).max(table[i * width + j + 1]);
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < original_len && j < changed_len {
+        let pair_score = score(i, j);
+        if pair_score > 0 && table[i * width + j] == pair_score + table[(i + 1) * width + j + 1] {
+            pairs.push((Some(i), Some(j)));
+            i += 1;
+            j += 1;
+        } else if table[i * width + j] == table[(i + 1) * width + j] {
+            pairs.push((Some(i), None));
+            i += 1;
+        } else {
+            pairs.push((None, Some(j)));
+            j += 1;
+        }
+    }
+    pairs.extend((i..original_len).map(|i| (Some(i), None)));
+    pairs.extend((j..changed_len).map(|j| (None, Some(j))));
+
+    pairs
+}
+
+/// Returns `true` if the node value is compared by its children
+fn has_children(asn1: &Asn1<'_>) -> bool {
+    match asn1.inner_asn1() {
+        Asn1Type::Sequence(_) | Asn1Type::Set(_) | Asn1Type::ExplicitTag(_) | Asn1Type::ApplicationTag(_) => true,
+        Asn1Type::OctetString(octet_string) => octet_string.inner().is_some(),
+        Asn1Type::BitString(bit_string) => bit_string.inner().is_some(),
+        Asn1Type::ImplicitTag(implicit) => implicit.inner_asn1().is_some(),
+        Asn1Type::Unknown(unknown) => unknown.fields().is_some(),
+        _ => false,
+    }
+}
+
+/// Returns the bytes the node is compared by: raw data of the decoded node or the encoding of the built one
+fn encoding<'a>(asn1: &'a Asn1<'_>) -> Asn1Result<Cow<'a, [u8]>> {
+    let raw = asn1.meta().raw_bytes();
+
+    Ok(if raw.is_empty() {
+        Cow::Owned(asn1.encode_to_vec()?)
+    } else {
+        Cow::Borrowed(raw)
+    })
+}
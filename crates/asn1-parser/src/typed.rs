@@ -0,0 +1,117 @@
+//! Building blocks for the typed asn1 structures.
+//!
+//! The `asn1-derive` crate generates code that uses these functions to decode and encode
+//! OPTIONAL, DEFAULT, and EXPLICIT/IMPLICIT tagged fields.
+
+use crate::length::{len_size, write_len_and_value};
+use crate::reader::Reader;
+use crate::tag::{read_tag, tag_size, write_tag};
+use crate::writer::Writer;
+use crate::{
+    Asn1Decoder, Asn1Encoder, Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, Error, ErrorKind, Tag, TagClass, Tlv,
+};
+
+/// Returns the tag of the next asn1 entity without advancing the reader
+pub fn peek_tag(reader: &mut Reader) -> Asn1Result<Tag> {
+    let position = reader.position();
+    let tag = read_tag(reader);
+    reader.set_position(position);
+
+    tag
+}
+
+/// Returns the tag of the EXPLICIT tagged value: context-specific and constructed
+pub const fn explicit_tag(tag_number: u32) -> Tag {
+    Tag::new(TagClass::ContextSpecific, true, tag_number)
+}
+
+/// Checks if the tag is the IMPLICIT tag with provided number. The constructed bit is taken from the inner type.
+pub fn is_implicit_tag(tag: Tag, tag_number: u32) -> bool {
+    tag.is_context_specific() && tag.number() == tag_number
+}
+
+/// Decodes the EXPLICIT tagged value: the inner asn1 entity is wrapped into the context-specific tag
+pub fn decode_explicit<'data, T: Asn1Decoder<'data>>(reader: &mut Reader<'data>, tag_number: u32) -> Asn1Result<T> {
+    let tag = explicit_tag(tag_number);
+
+    let tlv = Tlv::decode_with(
+        reader,
+        |t| t == tag,
+        Some(tag),
+        |_, reader| {
+            let value = T::decode(reader)?;
+
+            if !reader.empty() {
+                return Err(
+                    Error::new(ErrorKind::TrailingData, "Data after the explicitly tagged value")
+                        .with_offset(reader.full_offset()),
+                );
+            }
+
+            Ok(value)
+        },
+    )?;
+
+    Ok(tlv.into_inner_asn1())
+}
+
+/// Returns the encoded length of the EXPLICIT tagged value
+pub fn explicit_len<T: Asn1Encoder>(tag_number: u32, value: &T) -> usize {
+    let value_len = value.needed_buf_size();
+
+    tag_size(explicit_tag(tag_number)) + len_size(value_len) + value_len
+}
+
+/// Encodes the value wrapped into the EXPLICIT context-specific tag
+pub fn encode_explicit<T: Asn1Encoder>(tag_number: u32, value: &T, writer: &mut Writer) -> Asn1Result<()> {
+    write_tag(explicit_tag(tag_number), writer)?;
+    write_len_and_value(writer, || value.needed_buf_size(), |writer| value.encode(writer))
+}
+
+/// Decodes the IMPLICIT tagged value: the context-specific tag replaces the tag of the inner type
+pub fn decode_implicit<'data, A: Asn1ValueDecoder<'data>>(
+    reader: &mut Reader<'data>,
+    tag_number: u32,
+) -> Asn1Result<Tlv<'data, A>> {
+    let expected_tag = Tag::new(
+        TagClass::ContextSpecific,
+        A::expected_tag().map(|tag| tag.is_constructed()).unwrap_or_default(),
+        tag_number,
+    );
+
+    Tlv::decode_with(
+        reader,
+        |tag| is_implicit_tag(tag, tag_number),
+        Some(expected_tag),
+        |tag, reader| A::decode(A::expected_tag().unwrap_or(tag), reader),
+    )
+}
+
+/// Returns the encoded length of the IMPLICIT tagged value
+pub fn implicit_len<A: Asn1ValueEncoder>(tag_number: u32, value: &Tlv<'_, A>) -> usize {
+    let value = value.inner_asn1();
+    let value_len = value.value_len();
+
+    tag_size(implicit_tag(tag_number, value)) + len_size(value_len) + value_len
+}
+
+/// Encodes the value using the IMPLICIT context-specific tag instead of its own one
+pub fn encode_implicit<A: Asn1ValueEncoder>(
+    tag_number: u32,
+    value: &Tlv<'_, A>,
+    writer: &mut Writer,
+) -> Asn1Result<()> {
+    let value = value.inner_asn1();
+
+    write_tag(implicit_tag(tag_number, value), writer)?;
+    write_len_and_value(writer, || value.value_len(), |writer| value.encode_value(writer))
+}
+
+/// Checks if both values have the same encoding. It is used to omit DEFAULT fields equal to their default value.
+pub fn encodes_equal<T: Asn1Encoder>(value: &T, other: &T) -> bool {
+    matches!((value.encode_to_vec(), other.encode_to_vec()), (Ok(value), Ok(other)) if value == other)
+}
+
+fn implicit_tag<A: Asn1ValueEncoder>(tag_number: u32, value: &A) -> Tag {
+    Tag::new(TagClass::ContextSpecific, value.tag().is_constructed(), tag_number)
+}
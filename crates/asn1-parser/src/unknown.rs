@@ -0,0 +1,170 @@
+use alloc::vec::Vec;
+
+use crate::asn1::{skip_encapsulated_error, Asn1, Asn1Type};
+use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawBytes, RawDataStorage};
+use crate::writer::Writer;
+use crate::{Asn1Decoder, Asn1Encoder, Asn1Result, Asn1ValueDecoder, Asn1ValueEncoder, MetaInfo, Tag, Taggable};
+
+/// asn1 entity with the tag that is not supported by the parser.
+///
+/// For example, PRIVATE class tags, APPLICATION class primitive tags, or unknown UNIVERSAL types.
+/// The tag and raw content octets are kept as is, so the entity is encoded exactly as it was decoded.
+/// If the tag is constructed, then the content octets are also decoded as a list of asn1 entities when possible.
+/// In this case, the entity is encoded from these entities like other constructed entities, so changes of them
+/// are encoded too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unknown<'data> {
+    tag: Tag,
+    octets: RawBytes<'data>,
+    fields: Option<Vec<Asn1<'data>>>,
+}
+
+pub type OwnedUnknown = Unknown<'static>;
+
+impl Unknown<'_> {
+    pub fn new_owned(tag: Tag, octets: Vec<u8>) -> OwnedUnknown {
+        let fields = if tag.is_constructed() {
+            decode_fields(&mut Reader::new(&octets))
+                .ok()
+                .map(|fields| fields.iter().map(|f| f.to_shared()).collect())
+        } else {
+            None
+        };
+
+        Unknown {
+            tag,
+            octets: RawBytes::from(octets),
+            fields,
+        }
+    }
+}
+
+impl<'data> Unknown<'data> {
+    /// Returns raw content octets
+    pub fn octets(&self) -> &[u8] {
+        &self.octets
+    }
+
+    /// Returns decoded inner asn1 entities.
+    ///
+    /// It's `None` if the tag is primitive or the content octets are not a valid list of asn1 entities.
+    pub fn fields(&self) -> Option<&[Asn1<'data>]> {
+        self.fields.as_deref()
+    }
+
+    /// Replaces inner asn1 entities. The content octets are re-encoded from them
+    pub fn set_fields(&mut self, fields: Vec<Asn1<'data>>) -> Asn1Result<()> {
+        self.fields = Some(fields);
+
+        self.encode_fields()
+    }
+
+    /// Returns decoded inner asn1 entities for changing. The raw content octets are not updated
+    pub fn fields_mut(&mut self) -> Option<&mut [Asn1<'data>]> {
+        self.fields.as_deref_mut()
+    }
+
+    /// Re-encodes the content octets from the inner asn1 entities after they were changed
+    pub(crate) fn encode_fields(&mut self) -> Asn1Result<()> {
+        if let Some(fields) = self.fields.as_ref() {
+            let mut writer = Writer::growable();
+            fields.iter().try_for_each(|field| field.encode(&mut writer))?;
+            self.octets = RawBytes::from(writer.into_vec()?);
+        }
+
+        Ok(())
+    }
+
+    pub fn to_owned(&self) -> OwnedUnknown {
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedUnknown {
+        Unknown {
+            tag: self.tag,
+            octets: storage.store_bytes(&self.octets),
+            fields: self.fields.as_ref().map(|fields| {
+                fields
+                    .iter()
+                    .map(|f| f.to_owned_in(storage, f.inner_asn1().to_owned_in(storage)))
+                    .collect()
+            }),
+        }
+    }
+}
+
+impl Taggable for Unknown<'_> {
+    fn tag(&self) -> Tag {
+        self.tag
+    }
+}
+
+impl<'data> Asn1ValueDecoder<'data> for Unknown<'data> {
+    fn decode(tag: Tag, reader: &mut Reader<'data>) -> Asn1Result<Self> {
+        let offset = reader.full_offset();
+        let data = reader.read_remaining();
+
+        let fields = if tag.is_constructed() {
+            let mut inner_reader = reader.encapsulated(data, offset);
+            let fields = decode_fields(&mut inner_reader);
+            reader.finish_nested(&inner_reader);
+
+            match fields {
+                Ok(fields) => Some(fields),
+                Err(error) => {
+                    skip_encapsulated_error(reader, error)?;
+
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        Ok(Self {
+            tag,
+            octets: RawBytes::from(data),
+            fields,
+        })
+    }
+
+    /// Only tags that are not supported by the specific asn1 types are decoded as the [Unknown] entity.
+    fn compare_tags(tag: Tag) -> bool {
+        !Asn1Type::is_supported_tag(tag)
+    }
+}
+
+impl Asn1ValueEncoder for Unknown<'_> {
+    fn value_len(&self) -> usize {
+        match self.fields.as_ref() {
+            Some(fields) => fields.iter().map(|f| f.needed_buf_size()).sum(),
+            None => self.octets.len(),
+        }
+    }
+
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        match self.fields.as_ref() {
+            Some(fields) => fields.iter().try_for_each(|f| f.encode(writer)),
+            None => writer.write_slice(&self.octets),
+        }
+    }
+}
+
+impl MetaInfo for Unknown<'_> {
+    fn clear_meta(&mut self) {
+        if let Some(fields) = self.fields.as_mut() {
+            fields.iter_mut().for_each(|f| f.clear_meta());
+        }
+    }
+}
+
+fn decode_fields<'data>(reader: &mut Reader<'data>) -> Asn1Result<Vec<Asn1<'data>>> {
+    let mut fields = Vec::new();
+
+    while !reader.empty() {
+        fields.push(Asn1::decode(reader)?);
+    }
+
+    Ok(fields)
+}
@@ -0,0 +1,89 @@
+use crate::length::{indefinite_len, read_len, Length, END_OF_CONTENTS};
+use crate::reader::Reader;
+use crate::shared::{CopiedRawData, RawBytes, RawDataStorage};
+use crate::tag::read_tag;
+use crate::writer::Writer;
+use crate::{Asn1Result, Asn1ValueEncoder, Tag, Taggable};
+
+/// Bytes that can not be decoded as an asn1 entity.
+///
+/// Such nodes are produced only by the lenient decoding. They keep the raw bytes as is
+/// and do not have tag and length octets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unparsed<'data> {
+    data: RawBytes<'data>,
+}
+
+pub type OwnedUnparsed = Unparsed<'static>;
+
+impl<'data> Unparsed<'data> {
+    /// Unparsed bytes do not have a tag, so the reserved universal tag 0 is used instead.
+    pub const TAG: Tag = Tag::from_byte(0);
+
+    pub fn new(data: &'data [u8]) -> Self {
+        Self {
+            data: RawBytes::from(data),
+        }
+    }
+
+    /// Returns unparsed bytes
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns owned version of the [Unparsed]
+    pub fn to_owned(&self) -> OwnedUnparsed {
+        self.to_owned_in(&CopiedRawData)
+    }
+
+    pub(crate) fn to_owned_in(&self, storage: &impl RawDataStorage) -> OwnedUnparsed {
+        Unparsed {
+            data: storage.store_bytes(&self.data),
+        }
+    }
+}
+
+impl Taggable for Unparsed<'_> {
+    fn tag(&self) -> Tag {
+        Self::TAG
+    }
+}
+
+impl Asn1ValueEncoder for Unparsed<'_> {
+    fn value_len(&self) -> usize {
+        self.data.len()
+    }
+
+    fn encode_value(&self, writer: &mut Writer) -> Asn1Result<()> {
+        writer.write_slice(&self.data)
+    }
+
+    fn header_len_for(&self, _: usize) -> usize {
+        0
+    }
+
+    fn encode_header(&self, _: &mut Writer) -> Asn1Result<()> {
+        Ok(())
+    }
+
+    fn encode_growable(&self, writer: &mut Writer) -> Asn1Result<()> {
+        self.encode_value(writer)
+    }
+}
+
+/// Returns how many bytes of the undecodable asn1 entity should be treated as unparsed.
+///
+/// If the tag and length octets are valid, then only the current entity is skipped. Otherwise, all the data is unparsed.
+pub(crate) fn unparsed_len(data: &[u8]) -> usize {
+    let mut reader = Reader::new(data);
+
+    let len = match read_tag(&mut reader).and_then(|_| read_len(&mut reader)) {
+        Ok((Length::Definite(len), _)) => reader.position().checked_add(len),
+        Ok((Length::Indefinite, _)) => indefinite_len(&reader)
+            .ok()
+            .map(|len| reader.position() + len + END_OF_CONTENTS.len()),
+        Err(_) => None,
+    };
+
+    len.map(|len| len.min(data.len())).unwrap_or(data.len())
+}
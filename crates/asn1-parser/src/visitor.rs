@@ -0,0 +1,252 @@
+//! Depth-first traversal of the asn1 tree.
+//!
+//! Implement [Asn1Visitor] (or [Asn1VisitorMut]) and override only the hooks you need: the traversal itself
+//! is provided by the [Asn1Visitor::visit] default method. Every node triggers the hooks in the following order:
+//! `enter_node`, `enter_<variant>`, hooks of the child nodes, `leave_<variant>`, `leave_node`.
+
+use alloc::vec::Vec;
+
+use crate::{
+    ApplicationTag, Asn1, Asn1Entity, Asn1Type, BitString, BmpString, Bool, Enumerated, ExplicitTag, GeneralString,
+    GeneralizedTime, GraphicString, IA5String, ImplicitTag, Integer, Null, NumericString, ObjectDescriptor,
+    ObjectIdentifier, OctetString, PrintableString, Real, RelativeOid, Sequence, Set, TeletexString, UniversalString,
+    Unknown, Unparsed, UtcTime, Utf8String, VideotexString, VisibleString,
+};
+
+/// Calls the `$callback` macro with the list of all [Asn1Type] variants, their types, and hook names
+macro_rules! for_each_variant {
+    ($lt:lifetime, $callback:ident! $args:tt) => {
+        $callback! {
+            $args
+            Sequence(Sequence<$lt>) sequence,
+            Set(Set<$lt>) set,
+            OctetString(OctetString<$lt>) octet_string,
+            Utf8String(Utf8String<$lt>) utf8_string,
+            BitString(BitString<$lt>) bit_string,
+            BmpString(BmpString<$lt>) bmp_string,
+            IA5String(IA5String<$lt>) ia5_string,
+            PrintableString(PrintableString<$lt>) printable_string,
+            GeneralString(GeneralString<$lt>) general_string,
+            NumericString(NumericString<$lt>) numeric_string,
+            VisibleString(VisibleString<$lt>) visible_string,
+            UniversalString(UniversalString<$lt>) universal_string,
+            TeletexString(TeletexString<$lt>) teletex_string,
+            VideotexString(VideotexString<$lt>) videotex_string,
+            GraphicString(GraphicString<$lt>) graphic_string,
+            ObjectDescriptor(ObjectDescriptor<$lt>) object_descriptor,
+            UtcTime(UtcTime) utc_time,
+            GeneralizedTime(GeneralizedTime) generalized_time,
+            Bool(Bool) bool,
+            Null(Null) null,
+            Integer(Integer<$lt>) integer,
+            Enumerated(Enumerated<$lt>) enumerated,
+            Real(Real<$lt>) real,
+            ObjectIdentifier(ObjectIdentifier) object_identifier,
+            RelativeOid(RelativeOid) relative_oid,
+            ExplicitTag(ExplicitTag<$lt>) explicit_tag,
+            ImplicitTag(ImplicitTag<$lt>) implicit_tag,
+            ApplicationTag(ApplicationTag<$lt>) application_tag,
+            Unknown(Unknown<$lt>) unknown,
+            Unparsed(Unparsed<$lt>) unparsed,
+        }
+    };
+}
+
+/// Generates the per-variant enter and leave hooks with empty default implementations
+macro_rules! variant_hooks {
+    ([$reference:tt $modifier:tt] $($variant:ident($ty:ty) $name:ident,)*) => {
+        paste::paste! {
+            $(
+                #[doc = concat!("Called when entering the [Asn1Type::", stringify!($variant), "] node")]
+                fn [<enter_ $name>](&mut self, _value: $reference $modifier $ty, _context: &VisitContext<'_>) {}
+
+                #[doc = concat!("Called when leaving the [Asn1Type::", stringify!($variant), "] node")]
+                fn [<leave_ $name>](&mut self, _value: $reference $modifier $ty, _context: &VisitContext<'_>) {}
+            )*
+        }
+    };
+}
+
+/// Calls the `$hook` (`enter` or `leave`) hook corresponding to the `$asn1` variant
+macro_rules! call_variant_hook {
+    ([$hook:ident, $visitor:expr, $asn1:expr, $context:expr] $($variant:ident($ty:ty) $name:ident,)*) => {
+        paste::paste! {
+            match $asn1 {
+                $(Asn1Type::$variant(value) => $visitor.[<$hook _ $name>](value, $context),)*
+            }
+        }
+    };
+}
+
+/// Tells the traversal how to proceed after entering the node
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum VisitFlow {
+    /// Visit the node children
+    #[default]
+    Continue,
+    /// Do not visit the node children. Leave hooks of the node are still called
+    SkipChildren,
+    /// Stop the traversal. No more hooks are called, including the leave hooks of the current node and its parents
+    Stop,
+}
+
+/// Position of the visited node in the tree
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VisitContext<'path> {
+    path: &'path [usize],
+    parents: &'path [u64],
+}
+
+impl VisitContext<'_> {
+    /// Returns the node depth. The root node depth is 0
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+
+    /// Returns child indices leading from the root to the node. It's empty for the root node
+    pub fn path(&self) -> &[usize] {
+        self.path
+    }
+
+    /// Returns ids of the parent nodes starting from the root
+    pub fn parents(&self) -> &[u64] {
+        self.parents
+    }
+
+    /// Returns id of the direct parent node
+    pub fn parent_id(&self) -> Option<u64> {
+        self.parents.last().copied()
+    }
+}
+
+/// Read-only depth-first visitor of the asn1 tree.
+///
+/// The traversal includes the asn1 entities encapsulated into OCTET STRING, BIT STRING, IMPLICIT and unknown tags.
+/// Visited nodes live as long as the tree, so the visitor can collect references to them.
+pub trait Asn1Visitor<'a> {
+    /// Called when entering any node before the per-variant hook
+    fn enter_node(&mut self, _node: &'a Asn1<'a>, _context: &VisitContext<'_>) -> VisitFlow {
+        VisitFlow::Continue
+    }
+
+    /// Called when leaving any node after the per-variant hook
+    fn leave_node(&mut self, _node: &'a Asn1<'a>, _context: &VisitContext<'_>) {}
+
+    for_each_variant!('a, variant_hooks! [&'a]);
+
+    /// Visits the tree starting from the root node
+    fn visit(&mut self, root: &'a Asn1<'a>) {
+        walk(self, root, &mut Vec::new(), &mut Vec::new());
+    }
+}
+
+/// Mutable depth-first visitor of the asn1 tree.
+///
+/// Only the nodes that are encoded from the tree are visited: fields of SEQUENCE, SET, EXPLICIT, APPLICATION,
+/// and unknown constructed tags. The asn1 entities encapsulated into OCTET STRING, BIT STRING, and other primitive values are skipped
+/// because they are encoded from the raw value octets, so changing them does not affect the encoding.
+pub trait Asn1VisitorMut {
+    /// Called when entering any node before the per-variant hook
+    fn enter_node(&mut self, _node: &mut Asn1<'_>, _context: &VisitContext<'_>) -> VisitFlow {
+        VisitFlow::Continue
+    }
+
+    /// Called when leaving any node after the per-variant hook
+    fn leave_node(&mut self, _node: &mut Asn1<'_>, _context: &VisitContext<'_>) {}
+
+    for_each_variant!('_, variant_hooks! [&mut]);
+
+    /// Visits the tree starting from the root node
+    fn visit_mut(&mut self, root: &mut Asn1<'_>) {
+        walk_mut(self, root, &mut Vec::new(), &mut Vec::new());
+    }
+}
+
+/// Returns `false` if the traversal is stopped
+fn walk<'a, V: Asn1Visitor<'a> + ?Sized>(
+    visitor: &mut V,
+    asn1: &'a Asn1<'a>,
+    path: &mut Vec<usize>,
+    parents: &mut Vec<u64>,
+) -> bool {
+    let context = VisitContext { path, parents };
+    let flow = visitor.enter_node(asn1, &context);
+    if flow == VisitFlow::Stop {
+        return false;
+    }
+    for_each_variant!('a, call_variant_hook! [enter, visitor, asn1.inner_asn1(), &context]);
+
+    if flow == VisitFlow::Continue {
+        parents.push(asn1.id());
+        for (index, child) in children(asn1).into_iter().enumerate() {
+            path.push(index);
+            if !walk(visitor, child, path, parents) {
+                return false;
+            }
+            path.pop();
+        }
+        parents.pop();
+    }
+
+    let context = VisitContext { path, parents };
+    for_each_variant!('a, call_variant_hook! [leave, visitor, asn1.inner_asn1(), &context]);
+    visitor.leave_node(asn1, &context);
+
+    true
+}
+
+/// Returns `false` if the traversal is stopped
+fn walk_mut<V: Asn1VisitorMut + ?Sized>(
+    visitor: &mut V,
+    asn1: &mut Asn1<'_>,
+    path: &mut Vec<usize>,
+    parents: &mut Vec<u64>,
+) -> bool {
+    let context = VisitContext { path, parents };
+    let flow = visitor.enter_node(asn1, &context);
+    if flow == VisitFlow::Stop {
+        return false;
+    }
+    for_each_variant!('_, call_variant_hook! [enter, visitor, asn1.inner_asn1_mut(), &context]);
+
+    if flow == VisitFlow::Continue {
+        parents.push(asn1.id());
+        let fields = match asn1.inner_asn1_mut() {
+            Asn1Type::Sequence(sequence) => sequence.fields_mut(),
+            Asn1Type::Set(set) => set.fields_mut(),
+            Asn1Type::ExplicitTag(explicit) => explicit.inner_mut(),
+            Asn1Type::ApplicationTag(application) => application.inner_mut(),
+            Asn1Type::Unknown(unknown) => unknown.fields_mut().unwrap_or_default(),
+            _ => &mut [],
+        };
+        for (index, child) in fields.iter_mut().enumerate() {
+            path.push(index);
+            if !walk_mut(visitor, child, path, parents) {
+                return false;
+            }
+            path.pop();
+        }
+        parents.pop();
+    }
+
+    let context = VisitContext { path, parents };
+    for_each_variant!('_, call_variant_hook! [leave, visitor, asn1.inner_asn1_mut(), &context]);
+    visitor.leave_node(asn1, &context);
+
+    true
+}
+
+/// Returns the child nodes including the asn1 entities encapsulated into OCTET STRING, BIT STRING, and IMPLICIT tags
+pub(crate) fn children<'a>(asn1: &'a Asn1<'_>) -> Vec<&'a Asn1<'a>> {
+    match asn1.inner_asn1() {
+        Asn1Type::Sequence(sequence) => sequence.fields().iter().collect(),
+        Asn1Type::Set(set) => set.fields().iter().collect(),
+        Asn1Type::ExplicitTag(explicit) => explicit.inner().iter().collect(),
+        Asn1Type::ApplicationTag(application) => application.inner().iter().collect(),
+        Asn1Type::ImplicitTag(implicit) => implicit.inner_asn1().into_iter().collect(),
+        Asn1Type::OctetString(octet_string) => octet_string.inner().into_iter().collect(),
+        Asn1Type::BitString(bit_string) => bit_string.inner().into_iter().collect(),
+        Asn1Type::Unknown(unknown) => unknown.fields().into_iter().flatten().collect(),
+        _ => Vec::new(),
+    }
+}
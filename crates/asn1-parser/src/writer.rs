@@ -1,25 +1,77 @@
-use crate::{Asn1Result, Error};
+use alloc::vec::Vec;
+
+use crate::length::{len_size, len_size_with_octets, write_len, write_len_with_octets};
+use crate::{Asn1Result, Error, ErrorKind};
+
+#[derive(Debug)]
+enum Buffer<'data> {
+    Slice(&'data mut [u8]),
+    Vec(Vec<u8>),
+}
+
+/// Length octets which are written after the contents octets are encoded
+#[derive(Debug)]
+struct DeferredLength {
+    /// Position of the length octets in the written data (without any deferred length octets)
+    position: usize,
+    length: usize,
+    /// Amount of the subsequent length octets of the long form. The minimal form is used if it's `None`
+    octets: Option<usize>,
+}
+
+impl DeferredLength {
+    fn size(&self) -> usize {
+        match self.octets {
+            Some(octets) => len_size_with_octets(self.length, octets),
+            None => len_size(self.length),
+        }
+    }
+}
+
+/// Marks the beginning of the contents octets with a deferred length
+#[derive(Debug)]
+pub(crate) struct LengthMark {
+    index: usize,
+    position: usize,
+    deferred_size: usize,
+}
 
 #[derive(Debug)]
 pub struct Writer<'data> {
     position: usize,
-    inner: &'data mut [u8],
+    inner: Buffer<'data>,
+    // deferred length octets in the order of their positions. Only growable writers have them
+    lengths: Vec<DeferredLength>,
+    // total size of the deferred length octets
+    deferred_size: usize,
 }
 
 impl<'data> Writer<'data> {
     pub fn new(data: &'data mut [u8]) -> Self {
         Self {
             position: 0,
-            inner: data,
+            inner: Buffer::Slice(data),
+            lengths: Vec::new(),
+            deferred_size: 0,
         }
     }
 
+    /// Returns `true` if the writer is backed by a vector and can not run out of space
+    pub fn is_growable(&self) -> bool {
+        matches!(self.inner, Buffer::Vec(_))
+    }
+
     pub fn write_byte(&mut self, byte: u8) -> Asn1Result<()> {
-        if self.position == self.inner.len() {
-            return Err(Error::from("Buffer is too small"));
-        }
+        match &mut self.inner {
+            Buffer::Slice(inner) => {
+                if self.position == inner.len() {
+                    return Err(Error::new(ErrorKind::BufferTooSmall, "Buffer is too small").with_offset(self.position));
+                }
 
-        self.inner[self.position] = byte;
+                inner[self.position] = byte;
+            }
+            Buffer::Vec(inner) => inner.push(byte),
+        }
         self.position += 1;
 
         Ok(())
@@ -27,13 +79,103 @@ impl<'data> Writer<'data> {
 
     pub fn write_slice(&mut self, slice: &[u8]) -> Asn1Result<()> {
         let slice_len = slice.len();
-        if self.position + slice_len > self.inner.len() {
-            return Err(Error::from("Buffer is too small"));
-        }
+        match &mut self.inner {
+            Buffer::Slice(inner) => {
+                if self.position + slice_len > inner.len() {
+                    return Err(Error::new(ErrorKind::BufferTooSmall, "Buffer is too small").with_offset(self.position));
+                }
 
-        self.inner[self.position..self.position + slice_len].copy_from_slice(slice);
+                inner[self.position..self.position + slice_len].copy_from_slice(slice);
+            }
+            Buffer::Vec(inner) => inner.extend_from_slice(slice),
+        }
         self.position += slice_len;
 
         Ok(())
     }
+
+    /// Reserves the length octets of the contents octets written after this call.
+    ///
+    /// The length is calculated and written in [Writer::end_len]. Only growable writers support it.
+    pub(crate) fn begin_len(&mut self) -> LengthMark {
+        self.defer_len(None)
+    }
+
+    /// Same as [Writer::begin_len], but the length is written in the long form with provided amount of
+    /// subsequent octets (see [write_len_with_octets])
+    pub(crate) fn begin_len_with_octets(&mut self, octets: usize) -> LengthMark {
+        self.defer_len(Some(octets))
+    }
+
+    fn defer_len(&mut self, octets: Option<usize>) -> LengthMark {
+        debug_assert!(self.is_growable(), "deferred length octets need a growable writer");
+
+        self.lengths.push(DeferredLength {
+            position: self.position,
+            length: 0,
+            octets,
+        });
+
+        LengthMark {
+            index: self.lengths.len() - 1,
+            position: self.position,
+            deferred_size: self.deferred_size,
+        }
+    }
+
+    /// Sets the length of the contents octets written since the corresponding [Writer::begin_len] call
+    pub(crate) fn end_len(&mut self, mark: LengthMark) {
+        // nested deferred length octets are the part of the contents octets too
+        let length = self.position - mark.position + self.deferred_size - mark.deferred_size;
+
+        let deferred = &mut self.lengths[mark.index];
+        deferred.length = length;
+        self.deferred_size += deferred.size();
+    }
+
+    /// Returns the written data. Deferred length octets are inserted in a single pass.
+    pub fn into_vec(self) -> Asn1Result<Vec<u8>> {
+        let data = match self.inner {
+            Buffer::Slice(inner) => return Ok(inner[..self.position].to_vec()),
+            Buffer::Vec(data) => data,
+        };
+
+        if self.lengths.is_empty() {
+            return Ok(data);
+        }
+
+        let mut writer = Writer {
+            inner: Buffer::Vec(Vec::with_capacity(data.len() + self.deferred_size)),
+            ..Writer::growable()
+        };
+        let mut start = 0;
+        for DeferredLength {
+            position,
+            length,
+            octets,
+        } in self.lengths
+        {
+            writer.write_slice(&data[start..position])?;
+            match octets {
+                Some(octets) => write_len_with_octets(length, octets, &mut writer)?,
+                None => write_len(length, &mut writer)?,
+            }
+            start = position;
+        }
+        writer.write_slice(&data[start..])?;
+
+        writer.into_vec()
+    }
+}
+
+impl Writer<'static> {
+    /// Creates a writer backed by a vector. The vector grows as needed.
+    pub fn growable() -> Self {
+        Self {
+            position: 0,
+            inner: Buffer::Vec(Vec::new()),
+            lengths: Vec::new(),
+            deferred_size: 0,
+        }
+    }
 }
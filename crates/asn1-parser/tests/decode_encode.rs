@@ -1,5 +1,17 @@
-use asn1_parser::{Asn1, Asn1Decoder, Asn1Encoder, Asn1Type, MetaInfo, ObjectIdentifier, Taggable};
+use std::ops::Range;
+use std::sync::Arc;
+
+use asn1_parser::{
+    canonicalize_der, check_der, diff_asn1, edit_node, find_all, node_value, set_node_value, text_dump, Asn1,
+    Asn1Builder, Asn1ChangeKind, Asn1Cursor, Asn1Decoder, Asn1Encoder, Asn1Entity, Asn1Path, Asn1Schema, Asn1Type,
+    Asn1Visitor, Asn1VisitorMut, BitString, Bool, DecodeOptions, DerViolationKind, ErrorKind, ErrorPathNode,
+    ExplicitTag, ImplicitTag, Integer, Length, MetaInfo, Null, ObjectIdentifier, OctetString, OwnedAsn1,
+    OwnedGeneralString, OwnedInteger, OwnedOctetString, OwnedReal, Sequence, Tag, TagClass, Taggable, TextDumpStyle,
+    Tlv, Unknown, Utf8String, VisitContext, VisitFlow, Writer,
+};
 use prop_strategies::any_asn1_type;
+use proptest::collection::vec;
+use proptest::prelude::any;
 use proptest::proptest;
 
 #[test]
@@ -11,13 +23,17 @@ fn asn1() {
         let mut buff = vec![0; buff_len];
 
         asn1.encode_buff(&mut buff).unwrap();
+        assert_eq!(asn1.encode_to_vec().unwrap(), buff);
 
         let mut decoded = Asn1::decode_buff(&buff).unwrap();
         let decoded_inner_asn1 = decoded.inner_asn1();
         let decoded_meta = decoded.meta();
 
         assert_eq!(decoded_inner_asn1.needed_buf_size(), buff_len);
-        assert_eq!(1 + decoded_meta.length_bytes().len() + decoded_meta.data_bytes().len(), buff_len);
+        assert_eq!(
+            decoded_meta.tag_bytes().len() + decoded_meta.length_bytes().len() + decoded_meta.data_bytes().len(),
+            buff_len
+        );
         assert_eq!(decoded_inner_asn1.tag(), asn1_tag);
         assert_eq!(decoded_meta.tag_position(), 0);
         assert_eq!(decoded_meta.raw_bytes(), buff);
@@ -28,6 +44,42 @@ fn asn1() {
     })
 }
 
+// the encoding time of the same structures is measured by `benches/encode.rs`
+#[test]
+fn encode_large() {
+    let node = |asn1| OwnedAsn1::new(0, Default::default(), asn1);
+
+    // deeply nested structure (like certificates chains wrapped into CMS)
+    let mut deep = node(Asn1Type::OctetString(OctetString::new_owned(vec![0xab; 1024])));
+    for i in 0..20 {
+        deep = if i % 2 == 0 {
+            node(Asn1Type::Sequence(Sequence::new(vec![deep])))
+        } else {
+            node(Asn1Type::ExplicitTag(ExplicitTag::new(0, vec![deep])))
+        };
+    }
+
+    // wide structure with a lot of small fields (like CRLs)
+    let wide = node(Asn1Type::Sequence(Sequence::new(
+        (0..100_000)
+            .map(|i: i64| {
+                node(Asn1Type::Sequence(Sequence::new(vec![
+                    node(Asn1Type::Integer(OwnedInteger::from(i * 1_000_003))),
+                    node(Asn1Type::OctetString(OctetString::new_owned(vec![0xcd; 20]))),
+                ])))
+            })
+            .collect(),
+    )));
+
+    for asn1 in [deep, wide] {
+        let mut buff = vec![0; asn1.needed_buf_size()];
+        asn1.encode_buff(&mut buff).unwrap();
+
+        assert_eq!(asn1.encode_to_vec().unwrap(), buff);
+        assert_eq!(Asn1::decode_buff(&buff).unwrap().meta().raw_bytes(), buff);
+    }
+}
+
 #[test]
 fn generalized_time() {
     let raw = [
@@ -78,10 +130,9 @@ fn utc_time() {
     println!("{:?}", asn1);
 }
 
-// TODO: bug. need to be fixed
 #[test]
 fn oi() {
-    let asn1 = Asn1Type::ObjectIdentifier(ObjectIdentifier::from(oid::ObjectIdentifier::try_from("2.29.1432919503.268680342.2607450773.2297838964.2800989460.3536442839.826751377.97234221.883516388.2427681722").unwrap()));
+    let asn1 = Asn1Type::ObjectIdentifier(ObjectIdentifier::try_from(oid::ObjectIdentifier::try_from("2.29.1432919503.268680342.2607450773.2297838964.2800989460.3536442839.826751377.97234221.883516388.2427681722").unwrap()).unwrap());
     println!("asn1: {:?}", asn1);
     let asn1_tag = asn1.tag();
 
@@ -133,3 +184,1893 @@ fn decode_utc() {
     let mut encoded = vec![0; asn1.needed_buf_size()];
     asn1.encode_buff(&mut encoded).expect("ASN1 encoding should not fail");
 }
+
+#[test]
+fn high_tag_number() {
+    // [APPLICATION 1000] { [31] { NULL } }
+    let raw = [0x7f, 0x87, 0x68, 0x05, 0xbf, 0x1f, 0x02, 0x05, 0x00];
+    let asn1 = Asn1::decode_buff(&raw).unwrap();
+
+    assert_eq!(asn1.inner_asn1().tag(), Tag::new(TagClass::Application, true, 1000));
+    assert_eq!(asn1.meta().tag_bytes(), &[0x7f, 0x87, 0x68]);
+    assert_eq!(asn1.meta().length_bytes(), &[0x05]);
+
+    let Asn1Type::ApplicationTag(application) = asn1.inner_asn1() else {
+        panic!("expected application tag");
+    };
+    assert_eq!(application.tag_number(), 1000);
+    assert_eq!(
+        application.inner()[0].inner_asn1().tag(),
+        Tag::new(TagClass::ContextSpecific, true, 31)
+    );
+
+    let mut encoded = vec![0; asn1.needed_buf_size()];
+    asn1.encode_buff(&mut encoded).unwrap();
+    assert_eq!(encoded, raw);
+
+    // tag number less than 31 encoded in the high-tag-number form
+    assert!(Asn1::decode_buff(&[0xbf, 0x1e, 0x02, 0x05, 0x00]).is_err());
+    // leading 0x80 subsequent octet
+    assert!(Asn1::decode_buff(&[0xbf, 0x80, 0x1f, 0x02, 0x05, 0x00]).is_err());
+}
+
+#[test]
+fn indefinite_length() {
+    #[rustfmt::skip]
+    let raw = [
+        // [0] (indefinite)
+        0xa0, 0x80,
+            // SEQUENCE (indefinite)
+            0x30, 0x80,
+                // INTEGER 5
+                0x02, 0x01, 0x05,
+                // constructed OCTET STRING (indefinite)
+                0x24, 0x80,
+                    0x04, 0x02, 0x01, 0x02,
+                    0x04, 0x01, 0x03,
+                0x00, 0x00,
+                // constructed BIT STRING
+                0x23, 0x08,
+                    0x03, 0x02, 0x00, 0xff,
+                    0x03, 0x02, 0x04, 0xf0,
+            0x00, 0x00,
+        0x00, 0x00,
+    ];
+    let mut asn1 = Asn1::decode_buff(&raw).unwrap();
+
+    assert!(asn1.meta().is_indefinite_length());
+    assert_eq!(asn1.meta().raw_bytes(), raw);
+    assert_eq!(asn1.meta().data_bytes(), &raw[2..raw.len() - 2]);
+
+    let Asn1Type::ExplicitTag(explicit) = asn1.inner_asn1() else {
+        panic!("expected explicit tag");
+    };
+    let Asn1Type::Sequence(sequence) = explicit.inner()[0].inner_asn1() else {
+        panic!("expected sequence");
+    };
+    let fields = sequence.fields();
+
+    let Asn1Type::OctetString(octet_string) = fields[1].inner_asn1() else {
+        panic!("expected octet string");
+    };
+    assert_eq!(octet_string.tag(), Tag::from_byte(0x24));
+    assert_eq!(octet_string.octets(), &[0x01, 0x02, 0x03]);
+    assert_eq!(octet_string.segments().unwrap().len(), 2);
+
+    let Asn1Type::BitString(bit_string) = fields[2].inner_asn1() else {
+        panic!("expected bit string");
+    };
+    assert!(!fields[2].meta().is_indefinite_length());
+    assert_eq!(bit_string.raw_bits(), &[0x04, 0xff, 0xf0]);
+    assert_eq!(bit_string.bits_amount(), 12);
+
+    let mut encoded = vec![0; asn1.needed_buf_size()];
+    asn1.encode_buff(&mut encoded).unwrap();
+    assert_eq!(encoded, raw);
+
+    // without meta information the definite length form is used
+    asn1.clear_meta();
+    let mut encoded = vec![0; asn1.needed_buf_size()];
+    asn1.encode_buff(&mut encoded).unwrap();
+    #[rustfmt::skip]
+    assert_eq!(encoded, [
+        0xa0, 0x18,
+            0x30, 0x16,
+                0x02, 0x01, 0x05,
+                0x24, 0x07,
+                    0x04, 0x02, 0x01, 0x02,
+                    0x04, 0x01, 0x03,
+                0x23, 0x08,
+                    0x03, 0x02, 0x00, 0xff,
+                    0x03, 0x02, 0x04, 0xf0,
+    ]);
+
+    // primitive types can not use the indefinite length form
+    assert!(Asn1::decode_buff(&[0x04, 0x80, 0x00, 0x00]).is_err());
+    // missing end-of-contents octets
+    assert!(Asn1::decode_buff(&[0x30, 0x80, 0x05, 0x00]).is_err());
+    // unused bits in the non-last bit string segment
+    assert!(Asn1::decode_buff(&[0x23, 0x08, 0x03, 0x02, 0x04, 0xf0, 0x03, 0x02, 0x00, 0xff]).is_err());
+}
+
+#[test]
+fn error_position() {
+    // SEQUENCE { INTEGER 5, BOOLEAN with invalid length }
+    let error = Asn1::decode_buff(&[0x30, 0x07, 0x02, 0x01, 0x05, 0x01, 0x02, 0xff, 0xff]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidValue);
+    assert_eq!(error.offset(), Some(7));
+    assert_eq!(
+        error.path(),
+        &[
+            ErrorPathNode {
+                tag: Tag::from_byte(0x30),
+                offset: 0,
+            },
+            ErrorPathNode {
+                tag: Tag::from_byte(0x01),
+                offset: 5,
+            },
+        ]
+    );
+    assert_eq!(
+        error.to_string(),
+        "invalid value: Bool data len should be equal to 1 at offset 7, path: [UNIVERSAL 16]@0 / [UNIVERSAL 1]@5"
+    );
+
+    // truncated data
+    let error = Asn1::decode_buff(&[0x30, 0x05, 0x02, 0x01]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(error.offset(), Some(2));
+    assert!(error.path().is_empty());
+
+    // constructed OCTET STRING with the INTEGER segment
+    let error = Asn1::decode_buff(&[0x24, 0x03, 0x02, 0x01, 0x05]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedTag);
+    assert_eq!(error.offset(), Some(2));
+    assert_eq!(error.expected_tag(), Some(Tag::from_byte(0x04)));
+    assert_eq!(error.actual_tag(), Some(Tag::from_byte(0x02)));
+    assert_eq!(error.path().len(), 1);
+}
+
+#[test]
+fn lenient_decoding() {
+    #[rustfmt::skip]
+    let raw = [
+        0x30, 0x0d,
+            // INTEGER 5
+            0x02, 0x01, 0x05,
+            // BOOLEAN with invalid length
+            0x01, 0x02, 0xff, 0xff,
+            // NULL
+            0x05, 0x00,
+            // truncated OCTET STRING
+            0x04, 0x10, 0x01, 0x02,
+    ];
+    assert!(Asn1::decode_buff(&raw).is_err());
+
+    let (asn1, diagnostics) = Asn1::decode_buff_lenient(&raw);
+
+    let Asn1Type::Sequence(sequence) = asn1.inner_asn1() else {
+        panic!("expected sequence");
+    };
+    let fields = sequence.fields();
+    assert_eq!(fields.len(), 4);
+
+    let Asn1Type::Unparsed(unparsed) = fields[1].inner_asn1() else {
+        panic!("expected unparsed bytes");
+    };
+    assert_eq!(unparsed.data(), &[0x01, 0x02, 0xff, 0xff]);
+    assert_eq!(fields[1].meta().tag_position(), 5);
+    assert!(matches!(fields[2].inner_asn1(), Asn1Type::Null(_)));
+    let Asn1Type::OctetString(octet_string) = fields[3].inner_asn1() else {
+        panic!("expected octet string");
+    };
+    assert_eq!(octet_string.octets(), &[0x01, 0x02]);
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].kind(), ErrorKind::InvalidValue);
+    assert_eq!(diagnostics[0].offset(), Some(7));
+    assert_eq!(diagnostics[0].path().len(), 2);
+    assert_eq!(diagnostics[1].kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(diagnostics[1].offset(), Some(12));
+    assert_eq!(diagnostics[1].path().len(), 1);
+
+    // unparsed bytes are encoded as is
+    let mut encoded = vec![0; asn1.needed_buf_size()];
+    asn1.encode_buff(&mut encoded).unwrap();
+    #[rustfmt::skip]
+    assert_eq!(encoded, [
+        0x30, 0x0d,
+            0x02, 0x01, 0x05,
+            0x01, 0x02, 0xff, 0xff,
+            0x05, 0x00,
+            0x04, 0x02, 0x01, 0x02,
+    ]);
+
+    // invalid tag
+    let (asn1, diagnostics) = Asn1::decode_buff_lenient(&[0xff]);
+    assert!(matches!(asn1.inner_asn1(), Asn1Type::Unparsed(_)));
+    assert_eq!(diagnostics.len(), 1);
+
+    // trailing data
+    let (asn1, diagnostics) = Asn1::decode_buff_lenient(&[0x05, 0x00, 0x01]);
+    assert!(matches!(asn1.inner_asn1(), Asn1Type::Null(_)));
+    assert_eq!(diagnostics[0].kind(), ErrorKind::TrailingData);
+    assert_eq!(diagnostics[0].offset(), Some(2));
+}
+
+#[test]
+fn der_violations() {
+    #[rustfmt::skip]
+    let raw = [
+        0x30, 0x81, 0x3e,
+            // BOOLEAN 0x01
+            0x01, 0x01, 0x01,
+            // INTEGER with redundant leading octet
+            0x02, 0x02, 0x00, 0x05,
+            // BIT STRING with non-zero padding bits
+            0x03, 0x02, 0x04, 0xf1,
+            // SET OF with unsorted elements
+            0x31, 0x06,
+                0x02, 0x01, 0x02,
+                0x02, 0x01, 0x01,
+            // constructed OCTET STRING
+            0x24, 0x80,
+                0x04, 0x01, 0x01,
+                0x00, 0x00,
+            // GeneralizedTime without 'Z'
+            0x18, 0x0e, b'2', b'0', b'2', b'3', b'0', b'1', b'0', b'1', b'0', b'0', b'0', b'0', b'0', b'0',
+            // GeneralizedTime with trailing zero fraction
+            0x18, 0x12,
+                b'2', b'0', b'2', b'3', b'0', b'1', b'0', b'1', b'0', b'0', b'0', b'0', b'0', b'0', b'.', b'5', b'0', b'Z',
+    ];
+    let asn1 = Asn1::decode_buff(&raw).unwrap();
+    let violations = check_der(&asn1);
+
+    let kinds = violations.iter().map(|v| v.kind).collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            DerViolationKind::NonMinimalLength,
+            DerViolationKind::InvalidBoolean,
+            DerViolationKind::NonMinimalInteger,
+            DerViolationKind::NonZeroPaddingBits,
+            DerViolationKind::UnsortedSet,
+            DerViolationKind::IndefiniteLength,
+            DerViolationKind::ConstructedString,
+            DerViolationKind::NonUtcTime,
+            DerViolationKind::TrailingZeroFraction,
+        ]
+    );
+    let offsets = violations.iter().map(|v| v.offset).collect::<Vec<_>>();
+    assert_eq!(offsets, [0, 3, 6, 10, 14, 22, 22, 29, 45]);
+
+    let Asn1Type::Sequence(sequence) = asn1.inner_asn1() else {
+        panic!("expected sequence");
+    };
+    assert_eq!(violations[1].node_id, sequence.fields()[0].id());
+
+    // constructed forms of other string types are decoded as unknown entities but reported too
+    for raw in [
+        &[0x36, 0x03, 0x16, 0x01, 0x61][..],
+        &[0x2c, 0x80, 0x0c, 0x01, 0x61, 0x00, 0x00],
+    ] {
+        let asn1 = Asn1::decode_buff(raw).unwrap();
+        assert!(matches!(asn1.inner_asn1(), Asn1Type::Unknown(_)));
+        assert!(check_der(&asn1)
+            .iter()
+            .any(|violation| violation.kind == DerViolationKind::ConstructedString));
+    }
+
+    // DER encoded data has no violations
+    let asn1 = Asn1::decode_buff(&[0x31, 0x06, 0x01, 0x01, 0xff, 0x02, 0x01, 0x01]).unwrap();
+    assert!(check_der(&asn1).is_empty());
+}
+
+#[test]
+fn additional_universal_types() {
+    #[rustfmt::skip]
+    let raw = [
+        0x30, 0x30,
+            // ENUMERATED 2
+            0x0a, 0x01, 0x02,
+            // REAL 0.15625 (binary: 5 * 2^-5)
+            0x09, 0x03, 0x80, 0xfb, 0x05,
+            // REAL 1.5 (decimal NR2)
+            0x09, 0x04, 0x02, b'1', b',', b'5',
+            // REAL -infinity
+            0x09, 0x01, 0x41,
+            // RELATIVE-OID 8571.3.2
+            0x0d, 0x04, 0xc2, 0x7b, 0x03, 0x02,
+            // UniversalString "hé"
+            0x1c, 0x08, 0x00, 0x00, 0x00, 0x68, 0x00, 0x00, 0x00, 0xe9,
+            // TeletexString with the non-utf8 byte
+            0x14, 0x03, b'a', 0xe9, b'b',
+            // VideotexString
+            0x15, 0x01, b'v',
+            // GraphicString
+            0x19, 0x01, b'g',
+            // ObjectDescriptor
+            0x07, 0x02, b'o', b'd',
+    ];
+    let asn1 = Asn1::decode_buff(&raw).unwrap();
+
+    let Asn1Type::Sequence(sequence) = asn1.inner_asn1() else {
+        panic!("expected sequence");
+    };
+    let fields = sequence.fields();
+
+    let Asn1Type::Enumerated(enumerated) = fields[0].inner_asn1() else {
+        panic!("expected enumerated");
+    };
+    assert_eq!(enumerated.raw_data(), &[0x02]);
+    let Asn1Type::Real(real) = fields[1].inner_asn1() else {
+        panic!("expected real");
+    };
+    assert_eq!(real.value(), 0.15625);
+    let Asn1Type::Real(real) = fields[2].inner_asn1() else {
+        panic!("expected real");
+    };
+    assert_eq!(real.value(), 1.5);
+    let Asn1Type::Real(real) = fields[3].inner_asn1() else {
+        panic!("expected real");
+    };
+    assert_eq!(real.value(), f64::NEG_INFINITY);
+    let Asn1Type::RelativeOid(relative_oid) = fields[4].inner_asn1() else {
+        panic!("expected relative oid");
+    };
+    assert_eq!(relative_oid.format(), "8571.3.2");
+    let Asn1Type::UniversalString(universal) = fields[5].inner_asn1() else {
+        panic!("expected universal string");
+    };
+    assert_eq!(universal.string(), "hé");
+    let Asn1Type::TeletexString(teletex) = fields[6].inner_asn1() else {
+        panic!("expected teletex string");
+    };
+    assert_eq!(teletex.string(), "aéb");
+    assert!(matches!(fields[7].inner_asn1(), Asn1Type::VideotexString(_)));
+    assert!(matches!(fields[8].inner_asn1(), Asn1Type::GraphicString(_)));
+    assert!(matches!(fields[9].inner_asn1(), Asn1Type::ObjectDescriptor(_)));
+
+    let mut encoded = vec![0; asn1.needed_buf_size()];
+    asn1.encode_buff(&mut encoded).unwrap();
+    assert_eq!(encoded, raw);
+
+    // DER encoding of the REAL value
+    let real = Asn1Type::Real(OwnedReal::from(0.15625));
+    let mut encoded = vec![0; real.needed_buf_size()];
+    real.encode_buff(&mut encoded).unwrap();
+    assert_eq!(encoded, [0x09, 0x03, 0x80, 0xfb, 0x05]);
+}
+
+#[test]
+fn unknown_tags() {
+    #[rustfmt::skip]
+    let raw = [
+        0x30, 0x11,
+            // [PRIVATE 1] primitive
+            0xc1, 0x02, 0xab, 0xcd,
+            // [PRIVATE 2] constructed { INTEGER 5, NULL }
+            0xe2, 0x05,
+                0x02, 0x01, 0x05,
+                0x05, 0x00,
+            // [PRIVATE 3] constructed with invalid inner data
+            0xe3, 0x01, 0xff,
+            // [APPLICATION 4] primitive
+            0x44, 0x01, 0x01,
+    ];
+    let asn1 = Asn1::decode_buff(&raw).unwrap();
+
+    let Asn1Type::Sequence(sequence) = asn1.inner_asn1() else {
+        panic!("expected sequence");
+    };
+    let fields = sequence.fields();
+
+    let Asn1Type::Unknown(unknown) = fields[0].inner_asn1() else {
+        panic!("expected unknown");
+    };
+    assert_eq!(unknown.tag(), Tag::new(TagClass::Private, false, 1));
+    assert_eq!(unknown.octets(), &[0xab, 0xcd]);
+    assert!(unknown.fields().is_none());
+
+    let Asn1Type::Unknown(unknown) = fields[1].inner_asn1() else {
+        panic!("expected unknown");
+    };
+    assert_eq!(unknown.tag(), Tag::new(TagClass::Private, true, 2));
+    let inner = unknown.fields().unwrap();
+    assert_eq!(inner.len(), 2);
+    assert!(matches!(inner[0].inner_asn1(), Asn1Type::Integer(_)));
+    assert_eq!(inner[0].meta().tag_position(), 8);
+    assert!(matches!(inner[1].inner_asn1(), Asn1Type::Null(_)));
+
+    let Asn1Type::Unknown(unknown) = fields[2].inner_asn1() else {
+        panic!("expected unknown");
+    };
+    assert!(unknown.fields().is_none());
+    assert_eq!(unknown.octets(), &[0xff]);
+
+    let Asn1Type::Unknown(unknown) = fields[3].inner_asn1() else {
+        panic!("expected unknown");
+    };
+    assert_eq!(unknown.tag(), Tag::new(TagClass::Application, false, 4));
+
+    let mut encoded = vec![0; asn1.needed_buf_size()];
+    asn1.encode_buff(&mut encoded).unwrap();
+    assert_eq!(encoded, raw);
+
+    // supported tags are not decoded as unknown entities
+    let unknown = Tlv::<Unknown>::decode_buff(&[0xc1, 0x01, 0x00]).unwrap();
+    assert_eq!(unknown.inner_asn1().tag(), Tag::new(TagClass::Private, false, 1));
+    let error = Tlv::<Unknown>::decode_buff(&[0x02, 0x01, 0x00]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedTag);
+    assert_eq!(error.actual_tag(), Some(Integer::TAG));
+}
+
+#[test]
+fn signed_integer() {
+    let raw = [0x02, 0x01, 0x80];
+    let asn1 = Asn1::decode_buff(&raw).unwrap();
+    let Asn1Type::Integer(integer) = asn1.inner_asn1() else {
+        panic!("expected integer");
+    };
+    assert!(integer.is_negative());
+    assert_eq!(integer.as_big_int().to_string(), "-128");
+    assert_eq!(integer.as_big_uint().to_string(), "128");
+    assert_eq!(i32::try_from(integer).unwrap(), -128);
+    assert_eq!(u64::try_from(integer).unwrap_err().kind(), ErrorKind::InvalidValue);
+
+    assert_eq!(OwnedInteger::from(-128_i32).raw_data(), &[0x80]);
+    assert_eq!(OwnedInteger::from(-129_i64).raw_data(), &[0xff, 0x7f]);
+    assert_eq!(OwnedInteger::from(128_i32).raw_data(), &[0x00, 0x80]);
+    assert_eq!(OwnedInteger::from(0_i64).raw_data(), &[0x00]);
+    assert_eq!(OwnedInteger::from(-1_i64).raw_data(), &[0xff]);
+    assert_eq!(
+        OwnedInteger::from(u64::MAX).raw_data(),
+        &[0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]
+    );
+    assert_eq!(OwnedInteger::from(0x7f_u64).raw_data(), &[0x7f]);
+
+    let big = OwnedInteger::from(u64::MAX);
+    assert_eq!(u64::try_from(&big).unwrap(), u64::MAX);
+    assert!(i64::try_from(&big).is_err());
+    assert_eq!(OwnedInteger::from(&big.as_big_int()), big);
+    assert_eq!(OwnedInteger::from(&big.as_big_uint()), big);
+    assert_eq!(
+        OwnedInteger::from(&(-big.as_big_int())).raw_data(),
+        &[0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01]
+    );
+}
+
+const KERBEROS_SCHEMA: &str = r#"
+KerberosV5Spec2 {
+    iso(1) identified-organization(3) dod(6) internet(1) security(5) kerberosV5(2) modules(4) krb5spec2(2)
+} DEFINITIONS EXPLICIT TAGS ::= BEGIN
+
+Int32 ::= INTEGER (-2147483648..2147483647) -- signed values representable in 32 bits
+UInt32 ::= INTEGER (0..4294967295)
+KerberosString ::= GeneralString (IA5String)
+Realm ::= KerberosString
+
+PrincipalName ::= SEQUENCE {
+    name-type   [0] Int32,
+    name-string [1] SEQUENCE OF KerberosString
+}
+
+/* tickets are always application tagged */
+Ticket ::= [APPLICATION 1] SEQUENCE {
+    tkt-vno  [0] INTEGER (5),
+    realm    [1] Realm,
+    sname    [2] PrincipalName,
+    enc-part [3] EncryptedData -- EncTicketPart
+}
+
+EncryptedData ::= SEQUENCE {
+    etype  [0] Int32 -- EncryptionType --,
+    kvno   [1] UInt32 OPTIONAL,
+    cipher [2] OCTET STRING -- ciphertext
+}
+
+id-krb5 OBJECT IDENTIFIER ::= { iso(1) identified-organization(3) dod(6) internet(1) security(5) kerberosV5(2) }
+
+END
+"#;
+
+#[test]
+fn schema_annotation() {
+    let schema = Asn1Schema::parse(KERBEROS_SCHEMA).unwrap();
+    assert_eq!(
+        schema.type_names().collect::<Vec<_>>(),
+        [
+            "Int32",
+            "UInt32",
+            "KerberosString",
+            "Realm",
+            "PrincipalName",
+            "Ticket",
+            "EncryptedData"
+        ]
+    );
+
+    let raw = [
+        0x61, 0x31, 0x30, 0x2f, 0xa0, 0x03, 0x02, 0x01, 0x05, 0xa1, 0x06, 0x1b, 0x04, 0x54, 0x45, 0x53, 0x54, 0xa2,
+        0x11, 0x30, 0x0f, 0xa0, 0x03, 0x02, 0x01, 0x01, 0xa1, 0x08, 0x30, 0x06, 0x1b, 0x04, 0x75, 0x73, 0x65, 0x72,
+        0xa3, 0x0d, 0x30, 0x0b, 0xa0, 0x03, 0x02, 0x01, 0x12, 0xa2, 0x04, 0x04, 0x02, 0xab, 0xcd,
+    ];
+    let asn1 = Asn1::decode_buff(&raw).unwrap();
+    let annotations = schema.annotate("Ticket", &asn1);
+
+    let labels = annotations
+        .values()
+        .map(|annotation| (annotation.path.as_str(), annotation.type_name.as_str()))
+        .collect::<Vec<_>>();
+    for label in [
+        ("", "Ticket"),
+        ("", "SEQUENCE"),
+        ("tkt-vno", "[0]"),
+        ("tkt-vno", "INTEGER"),
+        ("realm", "Realm"),
+        ("sname", "PrincipalName"),
+        ("sname.name-type", "Int32"),
+        ("sname.name-string", "SEQUENCE OF"),
+        ("sname.name-string[0]", "KerberosString"),
+        ("enc-part.etype", "Int32"),
+        ("enc-part.cipher", "OCTET STRING"),
+    ] {
+        assert!(labels.contains(&label), "missing {:?}", label);
+    }
+    // every node of the ticket is matched
+    assert_eq!(annotations.len(), 19);
+    assert_eq!(annotations[&asn1.id()].type_name, "Ticket");
+
+    let schema = Asn1Schema::parse(
+        "Module DEFINITIONS AUTOMATIC TAGS ::= BEGIN Pair ::= SEQUENCE { a INTEGER, b CHOICE { x BOOLEAN, y NULL } } END",
+    )
+    .unwrap();
+    let asn1 = Asn1::decode_buff(&[0x30, 0x07, 0x80, 0x01, 0x05, 0xa1, 0x02, 0x81, 0x00]).unwrap();
+    let labels = schema
+        .annotate("Pair", &asn1)
+        .into_values()
+        .map(|annotation| (annotation.path, annotation.type_name))
+        .collect::<Vec<_>>();
+    for (path, type_name) in [("", "Pair"), ("a", "INTEGER"), ("b", "[1]"), ("b", "CHOICE (y)")] {
+        assert!(labels.contains(&(path.to_owned(), type_name.to_owned())));
+    }
+
+    // recursive CHOICEs that do not match the node are checked in linear time
+    let schema = Asn1Schema::parse(
+        "Module DEFINITIONS ::= BEGIN A ::= CHOICE { a B, b B, c INTEGER } B ::= CHOICE { x A, y A } END",
+    )
+    .unwrap();
+    let asn1 = Asn1::decode_buff(&[0x05, 0x00]).unwrap();
+    assert!(schema.annotate("A", &asn1).is_empty());
+    let asn1 = Asn1::decode_buff(&[0x02, 0x01, 0x05]).unwrap();
+    assert_eq!(schema.annotate("A", &asn1)[&asn1.id()].type_name, "A (c)");
+
+    let error = Asn1Schema::parse("Foo ::= SEQUENCE { a INTEGER").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidSchema);
+    assert_eq!(error.offset(), Some(28));
+}
+
+#[test]
+fn encode_to_vec() {
+    // [0] { SEQUENCE { OCTET STRING (300 bytes), [APPLICATION 1] (indefinite) { NULL } }, INTEGER }
+    let mut raw = vec![0xa0, 0x82, 0x01, 0x3d, 0x30, 0x82, 0x01, 0x36, 0x04, 0x82, 0x01, 0x2c];
+    raw.extend_from_slice(&[0xab; 300]);
+    raw.extend_from_slice(&[0x61, 0x80, 0x05, 0x00, 0x00, 0x00, 0x02, 0x01, 0x01]);
+    let asn1 = Asn1::decode_buff(&raw).unwrap();
+    assert_eq!(asn1.encode_to_vec().unwrap(), raw);
+
+    // unparsed data has neither tag nor length octets
+    let (asn1, diagnostics) = Asn1::decode_buff_lenient(&[0x30, 0x04, 0x05, 0x00, 0xff, 0x05]);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(asn1.encode_to_vec().unwrap(), [0x30, 0x04, 0x05, 0x00, 0xff, 0x05]);
+
+    // nested structure: length octets change their size on the way up
+    let mut asn1 = OwnedAsn1::new(
+        0,
+        Default::default(),
+        Asn1Type::OctetString(OctetString::new_owned(vec![1; 120])),
+    );
+    for _ in 0..16 {
+        asn1 = OwnedAsn1::new(0, Default::default(), Asn1Type::Sequence(Sequence::new(vec![asn1])));
+    }
+    let mut buff = vec![0; asn1.needed_buf_size()];
+    asn1.encode_buff(&mut buff).unwrap();
+    assert_eq!(asn1.encode_to_vec().unwrap(), buff);
+
+    let mut writer = Writer::growable();
+    asn1.encode(&mut writer).unwrap();
+    Null.encode(&mut writer).unwrap();
+    let encoded = writer.into_vec().unwrap();
+    assert_eq!(&encoded[..buff.len()], buff);
+    assert_eq!(&encoded[buff.len()..], [0x05, 0x00]);
+}
+
+#[test]
+fn builder() {
+    let asn1 = Asn1Builder::sequence()
+        .push(Asn1Builder::from(OwnedInteger::from(5)).explicit(0))
+        .unwrap()
+        .push(Asn1Builder::from(OctetString::new_owned(vec![1, 2])).implicit(1))
+        .unwrap()
+        .push(Asn1Builder::set().push(Bool::from(true)).unwrap().implicit(2))
+        .unwrap()
+        .push(Asn1Builder::from(Null).application(3))
+        .unwrap()
+        .extend([Utf8String::from("hi"), Utf8String::from("ok")])
+        .unwrap()
+        .build();
+
+    let encoded = asn1.encode_to_vec().unwrap();
+    assert_eq!(
+        encoded,
+        [
+            0x30, 0x1a, 0xa0, 0x03, 0x02, 0x01, 0x05, 0x81, 0x02, 0x01, 0x02, 0xa2, 0x03, 0x01, 0x01, 0xff, 0x63, 0x02,
+            0x05, 0x00, 0x0c, 0x02, 0x68, 0x69, 0x0c, 0x02, 0x6f, 0x6b,
+        ]
+    );
+
+    let Asn1Type::Sequence(sequence) = asn1.inner_asn1() else {
+        panic!("expected sequence");
+    };
+    let mut ids = sequence.fields().iter().map(|field| field.id()).collect::<Vec<_>>();
+    ids.push(asn1.id());
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 7);
+
+    let mut decoded = Asn1::decode_buff(&encoded).unwrap();
+    decoded.clear_meta();
+    let mut asn1 = asn1;
+    asn1.clear_meta();
+    assert_eq!(decoded, asn1);
+
+    // SEQUENCE { OCTET STRING { INTEGER } }
+    let value = Asn1::decode_buff(&[0x30, 0x06, 0x04, 0x04, 0x02, 0x02, 0x01, 0x00])
+        .unwrap()
+        .to_shared();
+    let mut asn1 = Asn1Builder::sequence()
+        .push(value.clone())
+        .unwrap()
+        .push(Asn1Builder::from(value).implicit(4))
+        .unwrap()
+        .build();
+    // nodes of the pushed values and the encapsulated entities get fresh ids too
+    let mut ids = find_all(&asn1, |_| true)
+        .iter()
+        .map(|node| node.id())
+        .collect::<Vec<_>>();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 7);
+    // the constructed IMPLICIT value is built in the same way as it is decoded
+    let Asn1Type::Sequence(sequence) = asn1.inner_asn1() else {
+        panic!("expected sequence");
+    };
+    assert!(matches!(sequence.fields()[1].inner_asn1(), Asn1Type::ExplicitTag(_)));
+    let encoded = asn1.encode_to_vec().unwrap();
+    let mut decoded = Asn1::decode_buff(&encoded).unwrap();
+    decoded.clear_meta();
+    asn1.clear_meta();
+    assert_eq!(decoded, asn1);
+
+    let error = Asn1Builder::from(Null).push(Null).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidValue);
+
+    let oid = ObjectIdentifier::try_from("1.2.840.113549.1.1.11").unwrap();
+    assert_eq!(oid.format(), "1.2.840.113549.1.1.11");
+    assert!(ObjectIdentifier::try_from("1.2.abc").is_err());
+}
+
+#[test]
+fn query() {
+    let encoded = Asn1Builder::sequence()
+        .push(OwnedInteger::from(5))
+        .unwrap()
+        .push(
+            Asn1Builder::explicit_tag(3)
+                .push(
+                    Asn1Builder::sequence()
+                        .push(OwnedInteger::from(7))
+                        .unwrap()
+                        .push(ObjectIdentifier::try_from("2.5.4.3").unwrap())
+                        .unwrap(),
+                )
+                .unwrap(),
+        )
+        .unwrap()
+        .push(
+            Asn1Builder::set()
+                .push(ObjectIdentifier::try_from("2.5.4.3").unwrap())
+                .unwrap()
+                .push(Utf8String::from("a.b"))
+                .unwrap(),
+        )
+        .unwrap()
+        .push(OctetString::new_owned(vec![0x02, 0x01, 0x09]))
+        .unwrap()
+        .build()
+        .encode_to_vec()
+        .unwrap();
+    let asn1 = Asn1::decode_buff(&encoded).unwrap();
+
+    let find = |path: &str| {
+        Asn1Path::parse(path)
+            .unwrap()
+            .find(&asn1)
+            .into_iter()
+            .map(|node| node_value(node).unwrap_or_default())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(find("0.0"), ["5"]);
+    assert_eq!(find("seq/ctx[3]/seq/int"), ["7"]);
+    assert_eq!(find("0.[3].0.1"), ["2.5.4.3"]);
+    assert_eq!(find("seq/set/*"), ["2.5.4.3", "a.b"]);
+    assert_eq!(find("**/oid=2.5.4.3").len(), 2);
+    assert_eq!(find("**/utf8=a.b"), ["a.b"]);
+    assert_eq!(find("**/**/int"), ["5", "9", "7"]);
+    assert_eq!(find("0/octet/int"), ["9"]);
+    assert_eq!(find("0/*").len(), 4);
+    assert_eq!(find("**").len(), 11);
+    assert!(find("0.5").is_empty());
+    assert!(find("set").is_empty());
+
+    let oids = find_all(&asn1, |node| node.inner_asn1().tag() == ObjectIdentifier::TAG);
+    assert_eq!(oids.len(), 2);
+
+    let error = Asn1Path::parse("seq/foo").unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidPath);
+    assert_eq!(error.offset(), Some(4));
+    assert_eq!(Asn1Path::parse("").unwrap_err().kind(), ErrorKind::InvalidPath);
+    assert_eq!(Asn1Path::parse("**=1").unwrap_err().kind(), ErrorKind::InvalidPath);
+    assert_eq!(Asn1Path::parse("0/ctx[x]").unwrap_err().offset(), Some(2));
+}
+
+#[test]
+fn visitor() {
+    #[derive(Default)]
+    struct Stats<'a> {
+        events: Vec<String>,
+        paths: std::collections::HashMap<u64, Vec<usize>>,
+        integers: usize,
+        max_depth: usize,
+        oids: Vec<&'a Asn1<'a>>,
+    }
+
+    impl<'a> Asn1Visitor<'a> for Stats<'a> {
+        fn enter_node(&mut self, node: &'a Asn1<'a>, context: &VisitContext<'_>) -> VisitFlow {
+            self.events.push(format!("enter {:?}", context.path()));
+            if let Some(parent_id) = context.parent_id() {
+                assert_eq!(self.paths[&parent_id], context.path()[..context.depth() - 1]);
+            }
+            self.paths.insert(node.id(), context.path().to_vec());
+            self.max_depth = self.max_depth.max(context.depth());
+            if context.depth() > 0 && node.inner_asn1().tag() == Null::TAG {
+                return VisitFlow::Stop;
+            }
+
+            VisitFlow::Continue
+        }
+
+        fn leave_node(&mut self, _node: &'a Asn1<'a>, context: &VisitContext<'_>) {
+            self.events.push(format!("leave {:?}", context.path()));
+        }
+
+        fn enter_integer(&mut self, _value: &'a Integer<'a>, _context: &VisitContext<'_>) {
+            self.integers += 1;
+        }
+
+        fn enter_object_identifier(&mut self, _value: &'a ObjectIdentifier, context: &VisitContext<'_>) {
+            assert_eq!(context.parents().len(), context.depth());
+        }
+
+        fn leave_explicit_tag(&mut self, explicit: &'a ExplicitTag<'a>, _context: &VisitContext<'_>) {
+            self.oids.extend(
+                explicit
+                    .inner()
+                    .iter()
+                    .filter(|node| node.inner_asn1().tag() == ObjectIdentifier::TAG),
+            );
+        }
+    }
+
+    let encoded = Asn1Builder::sequence()
+        .push(OwnedInteger::from(5))
+        .unwrap()
+        .push(Asn1Builder::from(ObjectIdentifier::try_from("2.5.4.3").unwrap()).explicit(1))
+        .unwrap()
+        .push(OctetString::new_owned(vec![0x02, 0x01, 0x09]))
+        .unwrap()
+        .push(Null)
+        .unwrap()
+        .push(OwnedInteger::from(6))
+        .unwrap()
+        .build()
+        .encode_to_vec()
+        .unwrap();
+    let asn1 = Asn1::decode_buff(&encoded).unwrap();
+
+    let mut stats = Stats::default();
+    stats.visit(&asn1);
+
+    assert_eq!(
+        stats.events,
+        [
+            "enter []",
+            "enter [0]",
+            "leave [0]",
+            "enter [1]",
+            "enter [1, 0]",
+            "leave [1, 0]",
+            "leave [1]",
+            "enter [2]",
+            "enter [2, 0]",
+            "leave [2, 0]",
+            "leave [2]",
+            "enter [3]",
+        ]
+    );
+    // the traversal is stopped on NULL, so the last INTEGER is not visited
+    assert_eq!(stats.integers, 2);
+    assert_eq!(stats.max_depth, 2);
+    assert_eq!(stats.oids.len(), 1);
+
+    struct Increment;
+
+    impl Asn1VisitorMut for Increment {
+        fn enter_node(&mut self, node: &mut Asn1<'_>, _context: &VisitContext<'_>) -> VisitFlow {
+            if node.inner_asn1().tag() == OctetString::TAG {
+                VisitFlow::SkipChildren
+            } else {
+                VisitFlow::Continue
+            }
+        }
+
+        fn leave_integer(&mut self, integer: &mut Integer<'_>, _context: &VisitContext<'_>) {
+            *integer = OwnedInteger::from(i64::try_from(&*integer).unwrap() + 1);
+        }
+    }
+
+    let mut asn1 = asn1.to_owned_with_asn1(asn1.inner_asn1().to_owned());
+    Increment.visit_mut(&mut asn1);
+
+    let integers = find_all(&asn1, |node| node.inner_asn1().tag() == OwnedInteger::TAG)
+        .into_iter()
+        .map(|node| node_value(node).unwrap())
+        .collect::<Vec<_>>();
+    // the encapsulated INTEGER is not visited by the mutable visitor
+    assert_eq!(integers, ["6", "9", "7"]);
+
+    // fields of the unknown constructed tags are visited: [PRIVATE 1] { INTEGER }
+    let mut asn1 = Asn1::decode_buff(&[0x30, 0x05, 0xe1, 0x03, 0x02, 0x01, 0x07]).unwrap();
+    Increment.visit_mut(&mut asn1);
+    assert_eq!(
+        asn1.encode_to_vec().unwrap(),
+        [0x30, 0x05, 0xe1, 0x03, 0x02, 0x01, 0x08]
+    );
+}
+
+#[test]
+fn tree_diff() {
+    let tree = |serial: i64, extra: Option<&str>, name: &str| {
+        let mut names = Asn1Builder::set().push(Utf8String::from("common")).unwrap();
+        if let Some(extra) = extra {
+            names = names.push(Utf8String::from(extra.to_owned())).unwrap();
+        }
+        names = names.push(Utf8String::from(name.to_owned())).unwrap();
+
+        Asn1Builder::sequence()
+            .push(OwnedInteger::from(serial))
+            .unwrap()
+            .push(names)
+            .unwrap()
+            .push(OctetString::new_owned(vec![0x01, 0x01, 0xff]))
+            .unwrap()
+            .build()
+            .encode_to_vec()
+            .unwrap()
+    };
+
+    let original = tree(1, None, "alice");
+    let original = Asn1::decode_buff(&original).unwrap();
+    assert!(diff_asn1(&original, &original).unwrap().is_empty());
+
+    let changed = tree(2, Some("inserted"), "alice");
+    let changed = Asn1::decode_buff(&changed).unwrap();
+    let changes = diff_asn1(&original, &changed).unwrap();
+    let kinds = changes.iter().map(|change| change.kind).collect::<Vec<_>>();
+    assert_eq!(kinds, [Asn1ChangeKind::Changed, Asn1ChangeKind::Inserted]);
+
+    // serial number
+    assert_eq!(changes[0].original.unwrap().offset, 2);
+    assert_eq!(changes[0].changed.unwrap().offset, 2);
+    // the inserted name does not shift the last one
+    assert_eq!(changes[1].original, None);
+    assert_eq!(changes[1].changed.unwrap().offset, 15);
+
+    let changes = diff_asn1(&changed, &original).unwrap();
+    assert_eq!(changes[1].kind, Asn1ChangeKind::Removed);
+    assert_eq!(changes[1].changed, None);
+
+    // encapsulated BOOLEAN: true -> false
+    let changed = Asn1Builder::sequence()
+        .push(OwnedInteger::from(1))
+        .unwrap()
+        .push(
+            Asn1Builder::set()
+                .push(Utf8String::from("common"))
+                .unwrap()
+                .push(Utf8String::from("bob"))
+                .unwrap(),
+        )
+        .unwrap()
+        .push(OctetString::new_owned(vec![0x01, 0x01, 0x00]))
+        .unwrap()
+        .build()
+        .encode_to_vec()
+        .unwrap();
+    let changed = Asn1::decode_buff(&changed).unwrap();
+    let changes = diff_asn1(&original, &changed).unwrap();
+    assert_eq!(changes.len(), 2);
+    assert!(changes.iter().all(|change| change.kind == Asn1ChangeKind::Changed));
+    assert_eq!(changes[1].original.unwrap().offset, 24);
+
+    let changes = diff_asn1(&original, &Asn1::decode_buff(&[0x05, 0x00]).unwrap()).unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].original.unwrap().node_id, original.id());
+
+    // built trees have no raw data, so their nodes are compared by the encoding
+    let built = |serial: i64| {
+        Asn1Builder::sequence()
+            .push(OwnedInteger::from(serial))
+            .unwrap()
+            .push(Null)
+            .unwrap()
+            .build()
+    };
+    assert!(diff_asn1(&built(1), &built(1)).unwrap().is_empty());
+    let changes = diff_asn1(&built(1), &built(2)).unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].original.unwrap().node_id, 2);
+}
+
+#[test]
+fn text_dump_layouts() {
+    let raw = [
+        48, 87, 1, 1, 255, 1, 1, 0, 160, 17, 12, 15, 84, 98, 101, 66, 101, 115, 116, 84, 118, 97, 114, 121, 110, 107,
+        97, 161, 60, 48, 58, 5, 0, 164, 9, 4, 7, 48, 5, 160, 3, 1, 1, 255, 164, 7, 3, 5, 0, 64, 129, 0, 16, 164, 34,
+        108, 32, 48, 30, 160, 2, 5, 0, 161, 24, 30, 22, 0, 67, 0, 101, 0, 114, 0, 116, 0, 105, 0, 102, 0, 105, 0, 99,
+        0, 97, 0, 116, 0, 101,
+    ];
+    let asn1 = Asn1::decode_buff(&raw).unwrap();
+
+    // `openssl asn1parse -i -inform DER` output
+    let expected = [
+        "    0:d=0  hl=2 l=  87 cons: SEQUENCE          \n",
+        "    2:d=1  hl=2 l=   1 prim:  BOOLEAN           :255\n",
+        "    5:d=1  hl=2 l=   1 prim:  BOOLEAN           :0\n",
+        "    8:d=1  hl=2 l=  17 cons:  cont [ 0 ]        \n",
+        "   10:d=2  hl=2 l=  15 prim:   UTF8STRING        :TbeBestTvarynka\n",
+        "   27:d=1  hl=2 l=  60 cons:  cont [ 1 ]        \n",
+        "   29:d=2  hl=2 l=  58 cons:   SEQUENCE          \n",
+        "   31:d=3  hl=2 l=   0 prim:    NULL              \n",
+        "   33:d=3  hl=2 l=   9 cons:    cont [ 4 ]        \n",
+        "   35:d=4  hl=2 l=   7 prim:     OCTET STRING      [HEX DUMP]:3005A0030101FF\n",
+        "   44:d=3  hl=2 l=   7 cons:    cont [ 4 ]        \n",
+        "   46:d=4  hl=2 l=   5 prim:     BIT STRING        \n",
+        "   53:d=3  hl=2 l=  34 cons:    cont [ 4 ]        \n",
+        "   55:d=4  hl=2 l=  32 cons:     appl [ 12 ]       \n",
+        "   57:d=5  hl=2 l=  30 cons:      SEQUENCE          \n",
+        "   59:d=6  hl=2 l=   2 cons:       cont [ 0 ]        \n",
+        "   61:d=7  hl=2 l=   0 prim:        NULL              \n",
+        "   63:d=6  hl=2 l=  24 cons:       cont [ 1 ]        \n",
+        "   65:d=7  hl=2 l=  22 prim:        BMPSTRING         \n",
+    ]
+    .concat();
+    assert_eq!(text_dump(&asn1, TextDumpStyle::OpensslAsn1Parse), expected);
+
+    let raw = [
+        48, 23, 2, 2, 255, 127, 19, 3, 97, 98, 99, 4, 3, 1, 2, 3, 10, 1, 3, 48, 128, 5, 0, 0, 0,
+    ];
+    let asn1 = Asn1::decode_buff(&raw).unwrap();
+
+    let expected = [
+        "   0   23: SEQUENCE {\n",
+        "   2    2:   INTEGER -129\n",
+        "   6    3:   PrintableString 'abc'\n",
+        "  11    3:   OCTET STRING 01 02 03\n",
+        "  16    1:   ENUMERATED 3\n",
+        "  19 NDEF:   SEQUENCE {\n",
+        "  21    0:     NULL\n",
+        "         :     }\n",
+        "         :   }\n",
+    ]
+    .concat();
+    assert_eq!(text_dump(&asn1, TextDumpStyle::DumpAsn1), expected);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_json() {
+    let raw = [
+        48, 87, 1, 1, 255, 1, 1, 0, 160, 17, 12, 15, 84, 98, 101, 66, 101, 115, 116, 84, 118, 97, 114, 121, 110, 107,
+        97, 161, 60, 48, 58, 5, 0, 164, 9, 4, 7, 48, 5, 160, 3, 1, 1, 255, 164, 7, 3, 5, 0, 64, 129, 0, 16, 164, 34,
+        108, 32, 48, 30, 160, 2, 5, 0, 161, 24, 30, 22, 0, 67, 0, 101, 0, 114, 0, 116, 0, 105, 0, 102, 0, 105, 0, 99,
+        0, 97, 0, 116, 0, 101,
+    ];
+    let asn1 = Asn1::decode_buff(&raw).unwrap();
+
+    let json = serde_json::to_string(&asn1).unwrap();
+    let deserialized: OwnedAsn1 = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.encode_to_vec().unwrap(), raw);
+
+    let asn1 = Asn1Builder::sequence()
+        .push(OwnedInteger::from(-129))
+        .unwrap()
+        .push(ObjectIdentifier::try_from("1.2.840.113549.1.1.11").unwrap())
+        .unwrap()
+        .push(Asn1Builder::from(Utf8String::from("tbt")).implicit(1))
+        .unwrap()
+        .push(Null)
+        .unwrap()
+        .build();
+    let json = serde_json::to_value(&asn1).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "type": "Sequence",
+            "tag": { "class": "universal", "constructed": true, "number": 16 },
+            "offset": 0,
+            "children": [
+                {
+                    "type": "Integer",
+                    "tag": { "class": "universal", "constructed": false, "number": 2 },
+                    "offset": 0,
+                    "value": "-129",
+                },
+                {
+                    "type": "ObjectIdentifier",
+                    "tag": { "class": "universal", "constructed": false, "number": 6 },
+                    "offset": 0,
+                    "value": "1.2.840.113549.1.1.11",
+                },
+                {
+                    "type": "ImplicitTag",
+                    "tag": { "class": "context_specific", "constructed": false, "number": 1 },
+                    "offset": 0,
+                    "value": "746274",
+                },
+                {
+                    "type": "Null",
+                    "tag": { "class": "universal", "constructed": false, "number": 5 },
+                    "offset": 0,
+                },
+            ],
+        })
+    );
+
+    // offsets of the deserialized tree point to the re-encoded data
+    let deserialized: OwnedAsn1 = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized.encode_to_vec().unwrap(), asn1.encode_to_vec().unwrap());
+    let oid = &find_all(&deserialized, |node| node.tag() == ObjectIdentifier::TAG)[0];
+    assert_eq!(oid.meta().tag_position(), 6);
+
+    let error = serde_json::from_str::<OwnedAsn1>(
+        r#"{ "tag": { "class": "universal", "constructed": false, "number": 19 }, "value": "not@printable" }"#,
+    )
+    .unwrap_err();
+    assert!(error.to_string().contains("invalid value"));
+    assert!(serde_json::from_str::<OwnedAsn1>(
+        r#"{ "tag": { "class": "universal", "constructed": false, "number": 2 }, "value": "0x10" }"#
+    )
+    .is_err());
+}
+
+#[cfg(feature = "oid-registry")]
+#[test]
+fn oid_registry() {
+    use asn1_parser::OidRegistry;
+
+    let mut registry = OidRegistry::new();
+
+    let info = registry
+        .get_oid(&ObjectIdentifier::try_from("1.2.840.113549.1.1.11").unwrap())
+        .unwrap();
+    assert_eq!(info.name(), "sha256WithRSAEncryption");
+    assert_eq!(info.description(), "SHA-256 with RSA encryption (PKCS #1)");
+    assert_eq!(registry.get("1.3.6.1.5.5.2").unwrap().name(), "spnego");
+    assert_eq!(registry.get_by_name("secp384r1").unwrap().oid(), "1.3.132.0.34");
+    assert!(registry.get("1.2.3.4.5").is_none());
+    assert!(registry.get_by_name("unknownName").is_none());
+
+    // short names and object identifiers of the built-in database are unique
+    let builtin = registry.iter().cloned().collect::<Vec<_>>();
+    for (i, info) in builtin.iter().enumerate() {
+        assert!(ObjectIdentifier::try_from(info.oid()).is_ok(), "{}", info.oid());
+        assert!(
+            builtin[i + 1..]
+                .iter()
+                .all(|other| other.oid() != info.oid() && other.name() != info.name()),
+            "{} is duplicated",
+            info.name()
+        );
+    }
+
+    registry
+        .register("1.3.6.1.4.1.55555.1", "customAttribute", "Custom attribute")
+        .unwrap();
+    assert_eq!(registry.get("1.3.6.1.4.1.55555.1").unwrap().name(), "customAttribute");
+    assert_eq!(
+        registry.get_by_name("customAttribute").unwrap().oid(),
+        "1.3.6.1.4.1.55555.1"
+    );
+
+    // custom entries override the built-in ones
+    registry.register("2.5.4.3", "cn", "Common name").unwrap();
+    assert_eq!(registry.get("2.5.4.3").unwrap().name(), "cn");
+    assert_eq!(registry.get_by_name("cn").unwrap().oid(), "2.5.4.3");
+    assert!(registry.get_by_name("commonName").is_none());
+    assert_eq!(registry.iter().count(), builtin.len() + 1);
+
+    registry.register("2.5.4.3", "commonName", "Common name").unwrap();
+    assert!(registry.get_by_name("cn").is_none());
+
+    // replacing the entry keeps its old name if the name is registered for another object identifier
+    registry.register("1.2.3.4", "x", "First").unwrap();
+    registry.register("1.2.3.5", "x", "Second").unwrap();
+    registry.register("1.2.3.4", "y", "Third").unwrap();
+    assert_eq!(registry.get_by_name("x").unwrap().oid(), "1.2.3.5");
+    assert_eq!(registry.get_by_name("y").unwrap().oid(), "1.2.3.4");
+
+    assert!(registry.register("not an oid", "invalid", "Invalid").is_err());
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_conversions() {
+    use asn1_parser::{GeneralizedTime, UtcTime};
+    use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+    let timestamp = |year, month, day, hour, minute, second| {
+        PrimitiveDateTime::new(
+            Date::from_calendar_date(year, month, day).unwrap(),
+            Time::from_hms(hour, minute, second).unwrap(),
+        )
+        .assume_utc()
+    };
+    let decode = |raw: &[u8]| Asn1::decode_buff(raw).unwrap().inner_asn1().to_owned();
+
+    // RFC 5280 two-digit years
+    let Asn1Type::UtcTime(utc_time) = decode(b"\x17\x0d491231235959Z") else {
+        panic!("UtcTime expected")
+    };
+    assert_eq!(utc_time.full_year(), 2049);
+    assert_eq!(
+        OffsetDateTime::try_from(&utc_time).unwrap(),
+        timestamp(2049, Month::December, 31, 23, 59, 59)
+    );
+    let Asn1Type::UtcTime(utc_time) = decode(b"\x17\x0b5001010000Z") else {
+        panic!("UtcTime expected")
+    };
+    assert_eq!(
+        OffsetDateTime::try_from(&utc_time).unwrap(),
+        timestamp(1950, Month::January, 1, 0, 0, 0)
+    );
+    let Asn1Type::UtcTime(utc_time) = decode(b"\x17\x0d230230000000Z") else {
+        panic!("UtcTime expected")
+    };
+    assert!(OffsetDateTime::try_from(&utc_time).is_err());
+
+    // the local time difference is normalised to UTC
+    let Asn1Type::GeneralizedTime(generalized_time) = decode(b"\x18\x1519851106210627.3-0500") else {
+        panic!("GeneralizedTime expected")
+    };
+    let converted = OffsetDateTime::try_from(&generalized_time).unwrap();
+    assert_eq!(converted.offset(), UtcOffset::UTC);
+    assert_eq!(
+        converted,
+        timestamp(1985, Month::November, 7, 2, 6, 27)
+            .replace_millisecond(300)
+            .unwrap()
+    );
+
+    let local = timestamp(2024, Month::March, 1, 1, 30, 0).replace_offset(UtcOffset::from_hms(3, 0, 0).unwrap());
+    let utc_time = UtcTime::try_from(local).unwrap();
+    assert_eq!(utc_time.encode_to_vec().unwrap(), b"\x17\x0d240229223000Z");
+    assert_eq!(OffsetDateTime::try_from(&utc_time).unwrap(), local);
+    assert!(UtcTime::try_from(timestamp(2050, Month::January, 1, 0, 0, 0)).is_err());
+
+    let generalized_time = GeneralizedTime::try_from(converted).unwrap();
+    assert!(generalized_time.local_time.is_none());
+    assert_eq!(OffsetDateTime::try_from(&generalized_time).unwrap(), converted);
+    assert!(GeneralizedTime::try_from(timestamp(-1, Month::January, 1, 0, 0, 0)).is_err());
+
+    // the builder chooses the time type by the year
+    let asn1 = Asn1Builder::time(timestamp(2049, Month::December, 31, 23, 59, 59))
+        .unwrap()
+        .build();
+    assert!(matches!(asn1.inner_asn1(), Asn1Type::UtcTime(_)));
+    let asn1 = Asn1Builder::time(timestamp(2050, Month::January, 1, 0, 0, 0))
+        .unwrap()
+        .build();
+    assert!(matches!(asn1.inner_asn1(), Asn1Type::GeneralizedTime(_)));
+}
+
+fn check_reencoding(raw: &[u8]) {
+    let Ok(asn1) = Asn1::decode_buff(raw) else {
+        return;
+    };
+    assert!(raw.starts_with(asn1.meta().raw_bytes()));
+
+    let encoded = asn1.encode_to_vec().unwrap();
+    assert_eq!(asn1.needed_buf_size(), encoded.len());
+    assert_eq!(asn1.meta().raw_bytes(), encoded);
+
+    let decoded = Asn1::decode_buff(&encoded).unwrap();
+    assert_eq!(decoded.meta().raw_bytes(), encoded);
+    assert_eq!(decoded.encode_to_vec().unwrap(), encoded);
+}
+
+#[test]
+fn decode_arbitrary_bytes() {
+    // non-ASCII digits, the 60th second, and the fraction rounded up to the 60th second
+    assert!(Asn1::decode_buff(b"\x17\x0d\xb2\xb20101000000Z").is_err());
+    assert!(Asn1::decode_buff(b"\x18\x0f20240101000060Z").is_err());
+    check_reencoding(b"\x18\x1420240101000059.9999Z");
+
+    proptest!(|(raw in vec(any::<u8>(), 0..256))| {
+        check_reencoding(&raw);
+    });
+
+    // corrupted valid encodings reach deeper into the decoders than random bytes
+    proptest!(|(asn1 in any_asn1_type(), position in any::<usize>(), byte in any::<u8>())| {
+        let mut raw = asn1.encode_to_vec().unwrap();
+        let position = position % raw.len();
+        raw[position] = byte;
+
+        check_reencoding(&raw);
+    });
+}
+
+#[test]
+fn length_edge_cases() {
+    // the length does not fit into the remaining data
+    let error = Asn1::decode_buff(&[0x04, 0x88, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    assert!(Asn1::decode_buff(&[0x30, 0x84, 0xff, 0xff, 0xff, 0xf0, 0x05, 0x00]).is_err());
+    // too many length octets
+    let error =
+        Asn1::decode_buff(&[0x04, 0x89, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00]).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidLength);
+    assert_eq!(error.offset(), Some(1));
+    assert!(Asn1::decode_buff(&[0x04, 0xff]).is_err());
+    // truncated length octets
+    assert_eq!(
+        Asn1::decode_buff(&[0x04, 0x82, 0x01]).unwrap_err().kind(),
+        ErrorKind::UnexpectedEof
+    );
+    assert_eq!(Asn1::decode_buff(&[0x04]).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    // the long form of short lengths is accepted
+    let asn1 = Asn1::decode_buff(&[0x04, 0x81, 0x01, 0xaa]).unwrap();
+    assert_eq!(asn1.meta().length_bytes(), [0x81, 0x01]);
+    check_reencoding(&[0x04, 0x81, 0x01, 0xaa]);
+
+    // moderate nesting
+    let mut asn1 = OwnedAsn1::new(0, Default::default(), Asn1Type::Null(Null));
+    for _ in 0..64 {
+        asn1 = OwnedAsn1::new(0, Default::default(), Asn1Type::Sequence(Sequence::new(vec![asn1])));
+    }
+    let encoded = asn1.encode_to_vec().unwrap();
+    assert_eq!(encoded.len(), asn1.needed_buf_size());
+    let mut decoded = Asn1::decode_buff(&encoded).unwrap();
+    decoded.clear_meta();
+    asn1.clear_meta();
+    assert_eq!(decoded.inner_asn1(), asn1.inner_asn1());
+}
+
+#[test]
+fn decode_limits() {
+    // SEQUENCE { SEQUENCE { SEQUENCE { NULL } } }
+    let raw = [0x30, 0x06, 0x30, 0x04, 0x30, 0x02, 0x05, 0x00];
+    let options = DecodeOptions {
+        max_depth: 2,
+        ..Default::default()
+    };
+    let error = Asn1::decode_buff_with_options(&raw, options).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::LimitExceeded);
+    assert_eq!(error.offset(), Some(6));
+    assert_eq!(error.path().len(), 3);
+    assert!(Asn1::decode_buff_with_options(&raw[2..], options).is_ok());
+
+    // thousands of nesting levels fail instead of overflowing the stack
+    let mut raw = vec![0x05, 0x00];
+    for _ in 0..10_000 {
+        let mut wrapped = vec![0x30];
+        let len = raw.len().to_be_bytes();
+        wrapped.push(0x80 | len.len() as u8);
+        wrapped.extend_from_slice(&len);
+        wrapped.append(&mut raw);
+        raw = wrapped;
+    }
+    assert_eq!(Asn1::decode_buff(&raw).unwrap_err().kind(), ErrorKind::LimitExceeded);
+
+    // SEQUENCE { NULL, NULL, NULL }
+    let raw = [0x30, 0x06, 0x05, 0x00, 0x05, 0x00, 0x05, 0x00];
+    let options = DecodeOptions {
+        max_nodes: 3,
+        ..Default::default()
+    };
+    let error = Asn1::decode_buff_with_options(&raw, options).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::LimitExceeded);
+    assert_eq!(error.offset(), Some(6));
+    assert!(Asn1::decode_buff_with_options(
+        &raw,
+        DecodeOptions {
+            max_nodes: 4,
+            ..options
+        }
+    )
+    .is_ok());
+    // typed decoders honour the limits too
+    let options = DecodeOptions {
+        max_nodes: 0,
+        ..Default::default()
+    };
+    assert!(Tlv::<Null>::decode_buff_with_options(&[0x05, 0x00], options).is_err());
+
+    // OCTET STRING { NULL }
+    let raw = [0x04, 0x02, 0x05, 0x00];
+    let asn1 = Asn1::decode_buff(&raw).unwrap();
+    let Asn1Type::OctetString(octet_string) = asn1.inner_asn1() else {
+        panic!("expected octet string");
+    };
+    assert!(octet_string.inner().is_some());
+
+    let options = DecodeOptions {
+        decode_encapsulated: false,
+        ..Default::default()
+    };
+    let asn1 = Asn1::decode_buff_with_options(&raw, options).unwrap();
+    let Asn1Type::OctetString(octet_string) = asn1.inner_asn1() else {
+        panic!("expected octet string");
+    };
+    assert!(octet_string.inner().is_none());
+    // every encapsulation level is one level deeper: OCTET STRING { OCTET STRING { NULL } }
+    let nested = [0x04, 0x04, 0x04, 0x02, 0x05, 0x00];
+    let options = DecodeOptions {
+        max_depth: 2,
+        ..Default::default()
+    };
+    let asn1 = Asn1::decode_buff_with_options(&nested, options).unwrap();
+    let Asn1Type::OctetString(octet_string) = asn1.inner_asn1() else {
+        panic!("expected octet string");
+    };
+    let Some(Asn1Type::OctetString(inner)) = octet_string.inner().map(|inner| inner.inner_asn1()) else {
+        panic!("expected encapsulated octet string");
+    };
+    assert!(inner.inner().is_some());
+    // exceeded limits are not hidden by the encapsulated entities decoding
+    let options = DecodeOptions {
+        max_depth: 0,
+        ..Default::default()
+    };
+    let error = Asn1::decode_buff_with_options(&raw, options).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::LimitExceeded);
+    assert_eq!(error.offset(), Some(2));
+    // [PRIVATE 1] { NULL }
+    let error = Asn1::decode_buff_with_options(&[0xe1, 0x02, 0x05, 0x00], options).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::LimitExceeded);
+    // in the lenient mode, they are reported and the encapsulated entities stay undecoded
+    let (asn1, diagnostics) = Asn1::decode_buff_lenient_with_options(&raw, options);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].kind(), ErrorKind::LimitExceeded);
+    let Asn1Type::OctetString(octet_string) = asn1.inner_asn1() else {
+        panic!("expected octet string");
+    };
+    assert!(octet_string.inner().is_none());
+
+    // the rest of the constructed entity becomes unparsed in the lenient mode
+    // SEQUENCE { SEQUENCE { NULL }, NULL }
+    let raw = [0x30, 0x06, 0x30, 0x02, 0x05, 0x00, 0x05, 0x00];
+    let options = DecodeOptions {
+        max_nodes: 2,
+        ..Default::default()
+    };
+    let (asn1, diagnostics) = Asn1::decode_buff_lenient_with_options(&raw, options);
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|error| error.kind() == ErrorKind::LimitExceeded));
+    let Asn1Type::Sequence(sequence) = asn1.inner_asn1() else {
+        panic!("expected sequence");
+    };
+    assert_eq!(sequence.fields().len(), 2);
+    let Asn1Type::Unparsed(unparsed) = sequence.fields()[1].inner_asn1() else {
+        panic!("expected unparsed bytes");
+    };
+    assert_eq!(unparsed.data(), &[0x05, 0x00]);
+    assert_eq!(asn1.encode_to_vec().unwrap(), raw);
+}
+
+#[test]
+fn exact_reencoding() {
+    // non-minimal long-form lengths are kept
+    let raw = [
+        0x30, 0x84, 0x00, 0x00, 0x00, 0x07, 0x04, 0x82, 0x00, 0x03, 0x01, 0x02, 0x03,
+    ];
+    let mut asn1 = Asn1::decode_buff(&raw).unwrap();
+    assert_eq!(asn1.encode_to_vec().unwrap(), raw);
+    let mut buf = [0; 13];
+    asn1.encode_buff(&mut buf).unwrap();
+    assert_eq!(buf, raw);
+    asn1.clear_meta();
+    assert_eq!(
+        asn1.encode_to_vec().unwrap(),
+        [0x30, 0x05, 0x04, 0x03, 0x01, 0x02, 0x03]
+    );
+
+    // edited values keep the length octets amount while the length fits into them
+    for (raw, expected_header) in [
+        // the new length fits into the original length octets
+        (
+            &[0x30, 0x82, 0x00, 0x03, 0x02, 0x01, 0x05][..],
+            [0x30, 0x82, 0x01, 0x30],
+        ),
+        // the new length does not fit, so the minimal form is used
+        (&[0x30, 0x81, 0x03, 0x02, 0x01, 0x05][..], [0x30, 0x82, 0x01, 0x30]),
+    ] {
+        let mut asn1 = Asn1::decode_buff(raw).unwrap();
+        let Asn1Type::Sequence(sequence) = asn1.inner_asn1_mut() else {
+            panic!("expected sequence");
+        };
+        sequence.fields_mut()[0] = OwnedAsn1::new(
+            0,
+            Default::default(),
+            Asn1Type::Integer(OwnedInteger::from(vec![0x01; 300])),
+        );
+
+        let encoded = asn1.encode_to_vec().unwrap();
+        assert_eq!(encoded.len(), asn1.needed_buf_size());
+        assert_eq!(encoded[..4], expected_header);
+        assert_eq!(encoded[4..8], [0x02, 0x82, 0x01, 0x2c]);
+    }
+
+    // BER values are kept as they are
+    for raw in [
+        &[0x01, 0x01, 0x01][..],
+        b"\x18\x1220240101000000.50Z",
+        b"\x18\x0e20240101000000",
+    ] {
+        assert_eq!(Asn1::decode_buff(raw).unwrap().encode_to_vec().unwrap(), raw);
+    }
+    // values are compared by the decoded value, not by the encoding
+    assert_eq!(Bool::from_byte(0x01), Bool::from(true));
+    assert_eq!(
+        Asn1::decode_buff(b"\x18\x1220240101000000.50Z").unwrap().inner_asn1(),
+        Asn1::decode_buff(b"\x18\x1120240101000000.5Z").unwrap().inner_asn1()
+    );
+    assert_eq!(
+        Asn1::decode_buff(&[0x09, 0x03, 0x80, 0x00, 0x02]).unwrap().inner_asn1(),
+        Asn1::decode_buff(&[0x09, 0x03, 0x80, 0x01, 0x01]).unwrap().inner_asn1()
+    );
+    // OBJECT IDENTIFIER subidentifiers with redundant leading octets can not be re-encoded, so they are rejected
+    assert!(Asn1::decode_buff(&[0x06, 0x03, 0x2a, 0x80, 0x01]).is_err());
+
+    // SET { GeneralizedTime, OCTET STRING (constructed, indefinite), BOOLEAN, NULL, BIT STRING, INTEGER }
+    let mut raw = vec![0x31, 0x81, 0x2c, 0x18, 0x81, 0x12];
+    raw.extend_from_slice(b"20240101000000.50Z");
+    raw.extend_from_slice(&[0x24, 0x80, 0x04, 0x01, 0xaa, 0x04, 0x01, 0xbb, 0x00, 0x00]);
+    raw.extend_from_slice(&[
+        0x01, 0x01, 0x01, 0x05, 0x00, 0x03, 0x02, 0x01, 0xff, 0x02, 0x02, 0x00, 0x05,
+    ]);
+    let mut asn1 = Asn1::decode_buff(&raw).unwrap();
+    assert_eq!(asn1.encode_to_vec().unwrap(), raw);
+    assert!(!check_der(&asn1).is_empty());
+
+    canonicalize_der(&mut asn1);
+    let mut expected = vec![
+        0x31, 0x23, 0x01, 0x01, 0xff, 0x02, 0x01, 0x05, 0x03, 0x02, 0x01, 0xfe, 0x04, 0x02, 0xaa, 0xbb, 0x05, 0x00,
+        0x18, 0x11,
+    ];
+    expected.extend_from_slice(b"20240101000000.5Z");
+    let encoded = asn1.encode_to_vec().unwrap();
+    assert_eq!(encoded, expected);
+    assert_eq!(check_der(&Asn1::decode_buff(&encoded).unwrap()), []);
+
+    // fields of unknown constructed tags are canonicalized and their content octets are re-encoded
+    let mut asn1 = Asn1::decode_buff(&[0xe1, 0x84, 0x00, 0x00, 0x00, 0x03, 0x01, 0x01, 0x01]).unwrap();
+    canonicalize_der(&mut asn1);
+    let Asn1Type::Unknown(unknown) = asn1.inner_asn1() else {
+        panic!("expected unknown");
+    };
+    assert_eq!(unknown.octets(), [0x01, 0x01, 0xff]);
+    assert_eq!(asn1.encode_to_vec().unwrap(), [0xe1, 0x03, 0x01, 0x01, 0xff]);
+
+    // constructed forms of other string types are merged into the primitive form
+    for (raw, expected) in [
+        (&[0x36, 0x03, 0x16, 0x01, 0x61][..], &[0x16, 0x01, 0x61][..]),
+        (&[0x2c, 0x80, 0x0c, 0x01, 0x61, 0x00, 0x00], &[0x0c, 0x01, 0x61]),
+        // nested segments in the OCTET STRING form (X.690 8.23.6)
+        (
+            &[
+                0x36, 0x80, 0x36, 0x06, 0x04, 0x01, 0x61, 0x04, 0x01, 0x62, 0x04, 0x01, 0x63, 0x00, 0x00,
+            ],
+            &[0x16, 0x03, 0x61, 0x62, 0x63],
+        ),
+    ] {
+        let mut asn1 = Asn1::decode_buff(raw).unwrap();
+        canonicalize_der(&mut asn1);
+        let encoded = asn1.encode_to_vec().unwrap();
+        assert_eq!(encoded, expected);
+        let mut decoded = Asn1::decode_buff(&encoded).unwrap();
+        assert_eq!(check_der(&decoded), []);
+        decoded.clear_meta();
+        assert_eq!(asn1, decoded);
+    }
+
+    // SET OF is sorted by encodings
+    let mut asn1 = Asn1::decode_buff(&[0x31, 0x06, 0x02, 0x01, 0x02, 0x02, 0x01, 0x01]).unwrap();
+    canonicalize_der(&mut asn1);
+    assert_eq!(
+        asn1.encode_to_vec().unwrap(),
+        [0x31, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02]
+    );
+}
+
+#[test]
+fn tree_editing() {
+    // APPLICATION 1 { SEQUENCE { [0] INTEGER, [1] GeneralString, [2] OCTET STRING { SEQUENCE { [0] INTEGER } } } }
+    fn token(etype: i64, realm: &str, encapsulated: i64) -> Vec<u8> {
+        let encapsulated = Asn1Builder::sequence()
+            .push(Asn1Builder::from(OwnedInteger::from(encapsulated)).explicit(0))
+            .unwrap()
+            .build()
+            .encode_to_vec()
+            .unwrap();
+
+        Asn1Builder::sequence()
+            .push(Asn1Builder::from(OwnedInteger::from(etype)).explicit(0))
+            .unwrap()
+            .push(Asn1Builder::from(OwnedGeneralString::from(realm.to_owned())).explicit(1))
+            .unwrap()
+            .push(Asn1Builder::from(OwnedOctetString::from(encapsulated)).explicit(2))
+            .unwrap()
+            .application(1)
+            .build()
+            .encode_to_vec()
+            .unwrap()
+    }
+
+    fn id_of(asn1: &Asn1<'_>, path: &str) -> u64 {
+        Asn1Path::parse(path).unwrap().find(asn1)[0].id()
+    }
+
+    let raw = token(5, "REALM", 17);
+    let mut asn1 = Asn1::decode_buff(&raw).unwrap();
+    let etype = id_of(&asn1, "app[1]/seq/[0]/int");
+    let realm = id_of(&asn1, "app[1]/seq/[1]/general");
+    let encapsulated = id_of(&asn1, "app[1]/seq/[2]/octet/seq/[0]/int");
+
+    assert!(edit_node(&mut asn1, etype, |node| set_node_value(node, "-128")).unwrap());
+    assert!(edit_node(&mut asn1, realm, |node| set_node_value(node, "EXAMPLE.COM")).unwrap());
+    // the OCTET STRING is re-encoded after the encapsulated entity is changed
+    assert!(edit_node(&mut asn1, encapsulated, |node| set_node_value(node, "65536")).unwrap());
+    assert!(!edit_node(&mut asn1, u64::MAX, |_| Ok(())).unwrap());
+    assert!(edit_node(&mut asn1, etype, |node| set_node_value(node, "five")).is_err());
+
+    let encoded = asn1.encode_to_vec().unwrap();
+    assert_eq!(encoded.len(), asn1.needed_buf_size());
+    assert_eq!(encoded, token(-128, "EXAMPLE.COM", 65536));
+
+    // text values of the primitive types
+    for (raw, value, expected) in [
+        (&b"\x01\x01\x00"[..], "true", &b"\x01\x01\xff"[..]),
+        (b"\x0a\x01\x01", "300", b"\x0a\x02\x01\x2c"),
+        (b"\x06\x03\x2a\x86\x48", "2.5.4.3", b"\x06\x03\x55\x04\x03"),
+        (b"\x0d\x01\x01", "1.300", b"\x0d\x03\x01\x82\x2c"),
+        (b"\x13\x01a", "Test Name", b"\x13\x09Test Name"),
+        (b"\x14\x01a", "caf\u{e9}", b"\x14\x04caf\xe9"),
+        (b"\x17\x0d240101000000Z", "250606120000Z", b"\x17\x0d250606120000Z"),
+        (
+            b"\x18\x0f20240101000000Z",
+            "20240101000000.50Z",
+            b"\x18\x1220240101000000.50Z",
+        ),
+    ] {
+        let mut asn1 = Asn1::decode_buff(raw).unwrap();
+        set_node_value(&mut asn1, value).unwrap();
+        assert_eq!(asn1.encode_to_vec().unwrap(), expected);
+        assert_eq!(Asn1::decode_buff(expected).unwrap().inner_asn1(), asn1.inner_asn1());
+    }
+    for (raw, value) in [
+        (&b"\x01\x01\x00"[..], "yes"),
+        (b"\x13\x01a", "user@example.com"),
+        (b"\x14\x01a", "\u{20ac}"),
+        (b"\x17\x0d240101000000Z", "240101000000Z+"),
+        (b"\x05\x00", ""),
+    ] {
+        assert!(set_node_value(&mut Asn1::decode_buff(raw).unwrap(), value).is_err());
+    }
+
+    // encapsulated entities of primitive nodes
+    let inner = || Asn1::decode_buff(&[0x02, 0x01, 0x07]).unwrap();
+    let mut octet_string = OctetString::from(vec![0x01]);
+    octet_string.set_inner(inner()).unwrap();
+    assert_eq!(octet_string.octets(), [0x02, 0x01, 0x07]);
+    let mut bit_string = BitString::from_raw_vec(3, vec![0xe0]).unwrap();
+    bit_string.set_inner(inner()).unwrap();
+    assert_eq!(bit_string.raw_bits(), [0x00, 0x02, 0x01, 0x07]);
+    let mut implicit = ImplicitTag::new_owned(0, vec![0x01]);
+    implicit.set_inner(inner()).unwrap();
+    assert_eq!(implicit.octets(), [0x02, 0x01, 0x07]);
+
+    // the segmented BER OCTET STRING becomes primitive, so the indefinite length is not kept
+    let raw = [0x24, 0x80, 0x04, 0x01, 0x05, 0x04, 0x01, 0x00, 0x00, 0x00];
+    let mut asn1 = Asn1::decode_buff(&raw).unwrap();
+    assert_eq!(asn1.encode_to_vec().unwrap(), raw);
+    let Asn1Type::OctetString(octet_string) = asn1.inner_asn1_mut() else {
+        panic!("expected octet string");
+    };
+    octet_string.set_inner(inner()).unwrap();
+    let encoded = asn1.encode_to_vec().unwrap();
+    assert_eq!(encoded, [0x04, 0x03, 0x02, 0x01, 0x07]);
+    assert_eq!(encoded.len(), asn1.needed_buf_size());
+    let decoded = Asn1::decode_buff(&encoded).unwrap();
+    assert_eq!(decoded.encode_to_vec().unwrap(), encoded);
+    let Asn1Type::OctetString(octet_string) = decoded.inner_asn1() else {
+        panic!("expected octet string");
+    };
+    assert_eq!(
+        octet_string.inner().map(|inner| inner.encode_to_vec().unwrap()),
+        Some(vec![0x02, 0x01, 0x07])
+    );
+}
+
+#[test]
+fn lazy_cursor() {
+    let raw = [
+        0x30, 0x80, // SEQUENCE with the indefinite length
+        0x04, 0x05, 0x30, 0x03, 0x02, 0x01, 0x07, // OCTET STRING with the encapsulated SEQUENCE
+        0x0c, 0x02, b'o', b'k', // UTF8String
+        0x00, 0x00, // end-of-contents
+        0x02, 0x01, 0x05, // INTEGER after the SEQUENCE
+    ];
+
+    let mut cursor = Asn1Cursor::new(&raw);
+    let sequence = cursor.next().unwrap().unwrap();
+    assert_eq!(sequence.tag(), Sequence::TAG);
+    assert_eq!(sequence.length(), Length::Indefinite);
+    assert_eq!(sequence.tag_position(), 0);
+    assert_eq!(sequence.header_len(), 2);
+    assert_eq!(sequence.raw_bytes(), &raw[0..15]);
+    assert_eq!(sequence.data_bytes(), &raw[2..13]);
+    assert_eq!(cursor.offset(), 15);
+
+    let mut fields = sequence.children();
+    let octet_string = fields.next().unwrap().unwrap();
+    assert_eq!(octet_string.tag(), OctetString::TAG);
+    assert_eq!(octet_string.length(), Length::Definite(5));
+    assert_eq!(octet_string.tag_position(), 2);
+    assert_eq!(octet_string.length_bytes(), &[0x05]);
+
+    // the encapsulated entities are read from the primitive entity contents
+    let encapsulated = octet_string.children().next().unwrap().unwrap();
+    assert_eq!(encapsulated.tag_position(), 4);
+    let integer = encapsulated.children().next().unwrap().unwrap();
+    assert_eq!(integer.tag_position(), 6);
+
+    let decoded: Asn1 = integer.decode().unwrap();
+    assert_eq!(decoded.meta().tag_position(), 6);
+    assert!(matches!(decoded.inner_asn1(), Asn1Type::Integer(integer) if integer.raw_data() == [7]));
+    let decoded: Tlv<Utf8String> = fields.next().unwrap().unwrap().decode().unwrap();
+    assert_eq!(decoded.inner_asn1().string(), "ok");
+    assert_eq!(decoded.meta().tag_position(), 9);
+    assert!(fields.next().is_none());
+
+    let integer = cursor.next().unwrap().unwrap();
+    assert_eq!(integer.tag(), Integer::TAG);
+    assert_eq!(integer.tag_position(), 15);
+    assert!(cursor.is_empty());
+    assert!(cursor.next().is_none());
+
+    // the whole tree is decoded from the header as well
+    assert_eq!(
+        sequence.decode::<Asn1>().unwrap(),
+        Asn1::decode_buff(&raw[0..15]).unwrap()
+    );
+
+    let mut cursor = Asn1Cursor::new(&[0x30, 0x05, 0x02, 0x01]);
+    assert_eq!(cursor.next().unwrap().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert!(cursor.next().is_none());
+
+    let mut cursor = Asn1Cursor::new(&[0x04, 0x80, 0x00, 0x00]);
+    assert_eq!(cursor.next_header().unwrap_err().kind(), ErrorKind::InvalidLength);
+    assert_eq!(cursor.offset(), 0);
+}
+
+#[test]
+fn shared_owned_tree() {
+    struct SharedBuffers(Vec<Option<Arc<[u8]>>>);
+
+    impl<'a> Asn1Visitor<'a> for SharedBuffers {
+        fn enter_node(&mut self, node: &'a Asn1<'a>, _: &VisitContext<'_>) -> VisitFlow {
+            self.0.push(node.meta().raw_data.shared_buffer().cloned());
+
+            VisitFlow::Continue
+        }
+    }
+
+    // contents octets kept by the decoded values
+    struct Payloads(Vec<Range<*const u8>>);
+
+    impl<'a> Asn1Visitor<'a> for Payloads {
+        fn enter_node(&mut self, node: &'a Asn1<'a>, _: &VisitContext<'_>) -> VisitFlow {
+            let payload = match node.inner_asn1() {
+                Asn1Type::OctetString(octet_string) => octet_string.octets(),
+                Asn1Type::Integer(integer) => integer.raw_data(),
+                Asn1Type::Utf8String(utf8_string) => utf8_string.raw_data(),
+                _ => return VisitFlow::Continue,
+            };
+            self.0.push(payload.as_ptr_range());
+
+            VisitFlow::Continue
+        }
+    }
+
+    let raw = [
+        0x30, 0x12, 0x04, 0x05, 0x30, 0x03, 0x02, 0x01, 0x07, 0xa0, 0x04, 0x0c, 0x02, b'o', b'k', 0x01, 0x01, 0xff,
+        0x05, 0x00,
+    ];
+    let asn1 = Asn1::decode_buff(&raw).unwrap();
+
+    let shared = asn1.to_shared();
+    assert_eq!(shared, asn1);
+    assert_eq!(shared.encode_to_vec().unwrap(), raw);
+
+    let mut buffers = SharedBuffers(Vec::new());
+    buffers.visit(&shared);
+    // root, OCTET STRING with the encapsulated SEQUENCE and INTEGER, EXPLICIT tag with UTF8String, BOOLEAN, and NULL
+    assert_eq!(buffers.0.len(), 8);
+    let root_buffer = shared.meta().raw_data.shared_buffer().unwrap();
+    assert_eq!(root_buffer.as_ref(), raw);
+    assert!(buffers
+        .0
+        .iter()
+        .all(|buffer| buffer.as_ref().is_some_and(|buffer| Arc::ptr_eq(buffer, root_buffer))));
+    let root_range = root_buffer.as_ptr_range();
+    let mut payloads = Payloads(Vec::new());
+    payloads.visit(&shared);
+    assert_eq!(payloads.0.len(), 3);
+    assert!(payloads
+        .0
+        .iter()
+        .all(|payload| root_range.start <= payload.start && payload.end <= root_range.end));
+
+    // owned copies of the shared tree keep pointing into the same buffer
+    let copy = shared.to_owned_with_asn1(shared.inner_asn1().to_owned());
+    let mut buffers = SharedBuffers(Vec::new());
+    buffers.visit(&copy);
+    assert!(buffers
+        .0
+        .iter()
+        .all(|buffer| buffer.as_ref().is_some_and(|buffer| Arc::ptr_eq(buffer, root_buffer))));
+    let mut payloads = Payloads(Vec::new());
+    payloads.visit(&copy);
+    assert!(payloads
+        .0
+        .iter()
+        .all(|payload| root_range.start <= payload.start && payload.end <= root_range.end));
+
+    // nodes that are not a part of the decoded data get their own buffer
+    let mut edited = asn1.clone();
+    edit_node(&mut edited, asn1.id(), |node| {
+        if let Asn1Type::Sequence(sequence) = node.inner_asn1_mut() {
+            sequence.fields_mut()[2] = Asn1::new(0, Default::default(), Asn1Type::Null(Null));
+        }
+
+        Ok(())
+    })
+    .unwrap();
+    let shared = edited.to_shared();
+    assert_eq!(shared, edited);
+    assert!(shared.meta().raw_data.shared_buffer().is_some());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_decoding() {
+    use std::io;
+
+    use asn1_parser::StreamDecoder;
+
+    struct FailingSource;
+
+    impl io::Read for FailingSource {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "failure"))
+        }
+    }
+
+    let messages: [&[u8]; 3] = [
+        &[0x30, 0x06, 0x02, 0x01, 0x01, 0x01, 0x01, 0xff],
+        // high tag number and the indefinite length
+        &[0x7f, 0x81, 0x00, 0x80, 0x30, 0x80, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00],
+        &[0x04, 0x81, 0x03, 0x02, 0x01, 0x07],
+    ];
+    let stream = messages.concat();
+
+    let decoded = StreamDecoder::new(stream.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(decoded.len(), messages.len());
+    let mut offset = 0;
+    for (asn1, message) in decoded.iter().zip(messages) {
+        assert_eq!(asn1.meta().tag_position(), offset);
+        assert_eq!(asn1.meta().raw_bytes(), message);
+        assert_eq!(asn1.encode_to_vec().unwrap(), message);
+        offset += message.len();
+    }
+
+    // fields of the entered SEQUENCE are decoded one by one
+    let list = [
+        0x30, 0x80, // SEQUENCE with the indefinite length
+        0x30, 0x09, 0x02, 0x01, 0x01, 0x02, 0x01, 0x02, 0x02, 0x01, 0x03, // SEQUENCE OF INTEGER
+        0x01, 0x01, 0x00, // BOOLEAN
+        0x00, 0x00, // end-of-contents
+        0x05, 0x00, // NULL after the SEQUENCE
+    ];
+    let mut decoder = StreamDecoder::new(list.as_slice());
+    assert_eq!(decoder.enter().unwrap(), Some(Sequence::TAG));
+    assert_eq!(decoder.enter().unwrap(), Some(Sequence::TAG));
+    assert_eq!(decoder.depth(), 2);
+    for (i, offset) in [4, 7, 10].into_iter().enumerate() {
+        let integer = decoder.next_entity().unwrap().unwrap();
+        assert_eq!(integer.meta().tag_position(), offset);
+        assert!(matches!(integer.inner_asn1(), Asn1Type::Integer(integer) if integer.raw_data() == [i as u8 + 1]));
+    }
+    assert_eq!(decoder.next_entity().unwrap(), None);
+    assert_eq!(decoder.depth(), 1);
+    assert!(matches!(
+        decoder.next_entity().unwrap().unwrap().inner_asn1(),
+        Asn1Type::Bool(_)
+    ));
+    assert_eq!(decoder.next_entity().unwrap(), None);
+    assert_eq!(decoder.depth(), 0);
+    assert!(matches!(
+        decoder.next().unwrap().unwrap().inner_asn1(),
+        Asn1Type::Null(_)
+    ));
+    assert!(decoder.next().is_none());
+    assert_eq!(decoder.offset(), list.len());
+
+    let mut decoder = StreamDecoder::new([0x30, 0x05, 0x02, 0x01].as_slice());
+    let error = decoder.next().unwrap().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(error.offset(), Some(4));
+    assert!(decoder.next().is_none());
+
+    // fields can not exceed the entered entity
+    let mut decoder = StreamDecoder::new([0x30, 0x02, 0x02, 0x02, 0x01, 0x01].as_slice());
+    decoder.enter().unwrap();
+    assert_eq!(decoder.next_entity().unwrap_err().kind(), ErrorKind::InvalidLength);
+
+    let mut decoder = StreamDecoder::new([0x02, 0x01, 0x01].as_slice());
+    assert_eq!(decoder.enter().unwrap_err().kind(), ErrorKind::UnexpectedTag);
+
+    let mut decoder = StreamDecoder::new([0x04, 0x80, 0x00, 0x00].as_slice());
+    assert_eq!(decoder.next_entity().unwrap_err().kind(), ErrorKind::InvalidLength);
+
+    let mut decoder = StreamDecoder::new(FailingSource);
+    let error = decoder.next_entity().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Io);
+    assert_eq!(error.detail(), Some("Other: failure"));
+    assert_eq!(
+        error.to_string(),
+        "input/output error: Can not read from the stream (Other: failure) at offset 0"
+    );
+
+    // buffered sources are decoded in the same way
+    let decoded = StreamDecoder::new(io::BufReader::new(stream.as_slice()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(decoded.len(), messages.len());
+}
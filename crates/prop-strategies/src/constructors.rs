@@ -1,20 +1,68 @@
-use asn1_parser::{Asn1Type, OwnedApplicationTag, OwnedAsn1, OwnedAsn1Type, OwnedExplicitTag, OwnedSequence, OwnedSet};
+use asn1_parser::{
+    Asn1Type, OwnedApplicationTag, OwnedAsn1, OwnedAsn1Type, OwnedExplicitTag, OwnedImplicitTag, OwnedSequence,
+    OwnedSet, OwnedUnknown, Tag, TagClass, Unknown,
+};
 use proptest::collection::vec;
-use proptest::prop_oneof;
+use proptest::prelude::any;
 use proptest::strategy::{Just, Strategy};
+use proptest::{prop_compose, prop_oneof};
 
-use crate::{any_bit_string, any_bmp_string, any_bool, any_integer, any_null, any_octet_string, any_utf8_string};
+use crate::{
+    any_bit_string, any_bmp_string, any_bool, any_enumerated, any_general_string, any_generalized_time,
+    any_graphic_string, any_ia5_string, any_integer, any_null, any_numeric_string, any_object_descriptor,
+    any_object_identifier, any_octet_string, any_printable_string, any_real, any_relative_oid, any_teletex_string,
+    any_universal_string, any_utc_time, any_utf8_string, any_videotex_string, any_visible_string, bytes,
+};
 
-fn any_leaf_asn1_type() -> impl Strategy<Value = OwnedAsn1Type> {
+prop_compose! {
+    pub fn any_unknown()
+        (
+            tag_number in 0_u32..512,
+            constructed in any::<bool>(),
+            data in bytes(32),
+        ) -> OwnedUnknown {
+        Unknown::new_owned(Tag::new(TagClass::Private, constructed, tag_number), data)
+    }
+}
+
+prop_compose! {
+    pub fn any_implicit_tag()
+        (
+            tag_number in 0_u32..512,
+            data in bytes(32),
+        ) -> OwnedImplicitTag {
+        OwnedImplicitTag::new_owned(tag_number, data)
+    }
+}
+
+/// Generates every primitive asn1 type. `Unparsed` is excluded because only the lenient decoding produces it
+pub fn any_leaf_asn1_type() -> impl Strategy<Value = OwnedAsn1Type> {
     prop_oneof![
         any_octet_string().prop_map(Asn1Type::OctetString),
         any_utf8_string().prop_map(Asn1Type::Utf8String),
         any_bit_string().prop_map(Asn1Type::BitString),
         any_bmp_string().prop_map(Asn1Type::BmpString),
+        any_ia5_string().prop_map(Asn1Type::IA5String),
+        any_printable_string().prop_map(Asn1Type::PrintableString),
+        any_general_string().prop_map(Asn1Type::GeneralString),
+        any_numeric_string().prop_map(Asn1Type::NumericString),
+        any_visible_string().prop_map(Asn1Type::VisibleString),
+        any_universal_string().prop_map(Asn1Type::UniversalString),
+        any_teletex_string().prop_map(Asn1Type::TeletexString),
+        any_videotex_string().prop_map(Asn1Type::VideotexString),
+        any_graphic_string().prop_map(Asn1Type::GraphicString),
+        any_object_descriptor().prop_map(Asn1Type::ObjectDescriptor),
+        any_utc_time().prop_map(Asn1Type::UtcTime),
+        any_generalized_time().prop_map(Asn1Type::GeneralizedTime),
         any_bool().prop_map(Asn1Type::Bool),
         any_null().prop_map(Asn1Type::Null),
         any_integer().prop_map(Asn1Type::Integer),
-        // any_object_identifier().prop_map(Asn1Type::ObjectIdentifier),
+        any_enumerated().prop_map(Asn1Type::Enumerated),
+        any_real().prop_map(Asn1Type::Real),
+        any_object_identifier().prop_map(Asn1Type::ObjectIdentifier),
+        any_relative_oid().prop_map(Asn1Type::RelativeOid),
+        any_implicit_tag().prop_map(Asn1Type::ImplicitTag),
+        any_unknown().prop_map(Asn1Type::Unknown),
     ]
     .no_shrink()
 }
@@ -40,13 +88,13 @@ pub fn recursive_empty_asn1_type() -> impl Strategy<Value = OwnedAsn1Type> {
                         .collect::<Vec<_>>(),
                 ))
             }),
-            (0_u8..31)
+            (0_u32..512)
                 .prop_flat_map(move |tag| (Just(tag), explicit_tag_inner.clone()))
                 .prop_map(|(tag, inner)| Asn1Type::ExplicitTag(OwnedExplicitTag::new(
                     tag,
                     vec![OwnedAsn1::new(0, Default::default(), inner)]
                 ))),
-            (0_u8..31)
+            (0_u32..512)
                 .prop_flat_map(move |tag| (Just(tag), application_tag_inner.clone()))
                 .prop_map(|(tag, inner)| Asn1Type::ApplicationTag(OwnedApplicationTag::new(
                     tag,
@@ -55,3 +103,20 @@ pub fn recursive_empty_asn1_type() -> impl Strategy<Value = OwnedAsn1Type> {
         ]
     })
 }
+
+/// Wraps the leaf asn1 type into a long chain of single-field SEQUENCEs, SETs, and tagged nodes. Unlike
+/// [recursive_empty_asn1_type], it reaches the nesting depth of up to 128 nodes
+pub fn deeply_nested_asn1_type() -> impl Strategy<Value = OwnedAsn1Type> {
+    (any_leaf_asn1_type(), vec((0_u8..4, 0_u32..512), 16..64)).prop_map(|(leaf, wrappers)| {
+        wrappers.into_iter().fold(leaf, |inner, (kind, tag)| {
+            let fields = vec![OwnedAsn1::new(0, Default::default(), inner)];
+
+            match kind {
+                0 => Asn1Type::Sequence(OwnedSequence::new(fields)),
+                1 => Asn1Type::Set(OwnedSet::new(fields)),
+                2 => Asn1Type::ExplicitTag(OwnedExplicitTag::new(tag, fields)),
+                _ => Asn1Type::ApplicationTag(OwnedApplicationTag::new(tag, fields)),
+            }
+        })
+    })
+}
@@ -1,4 +1,4 @@
-use asn1_parser::{Bool, Null, ObjectIdentifier, OwnedInteger};
+use asn1_parser::{Bool, Null, ObjectIdentifier, OwnedEnumerated, OwnedInteger, OwnedReal, RelativeOid};
 use proptest::collection::vec;
 use proptest::prelude::any;
 use proptest::prop_compose;
@@ -18,6 +18,18 @@ pub fn any_integer() -> impl Strategy<Value = OwnedInteger> {
     bytes(1024).prop_map(|bytes| bytes.into())
 }
 
+pub fn any_enumerated() -> impl Strategy<Value = OwnedEnumerated> {
+    bytes(8).prop_map(|bytes| bytes.into())
+}
+
+pub fn any_real() -> impl Strategy<Value = OwnedReal> {
+    any::<f64>().prop_map(|value| value.into())
+}
+
+pub fn any_relative_oid() -> impl Strategy<Value = RelativeOid> {
+    vec(any::<u64>(), 1..11).prop_map(|arcs| arcs.into())
+}
+
 prop_compose! {
     pub fn any_object_identifier()
         (
@@ -34,6 +46,6 @@ prop_compose! {
         for node in nodes {
             formatted_oid.push_str(&format!(".{}", node));
         }
-        ObjectIdentifier::from(oid::ObjectIdentifier::try_from(dbg!(formatted_oid)).expect("Valid object identifier."))
+        ObjectIdentifier::try_from(formatted_oid.as_str()).expect("Valid object identifier.")
     }
 }
@@ -1,4 +1,8 @@
-use asn1_parser::{BitString, OwnedBitString, OwnedBmpString, OwnedOctetString, OwnedUtf8String};
+use asn1_parser::{
+    BitString, OwnedBitString, OwnedBmpString, OwnedGeneralString, OwnedGraphicString, OwnedIA5String,
+    OwnedNumericString, OwnedObjectDescriptor, OwnedOctetString, OwnedPrintableString, OwnedTeletexString,
+    OwnedUniversalString, OwnedUtf8String, OwnedVideotexString, OwnedVisibleString,
+};
 use proptest::prop_compose;
 
 use crate::{bytes, string};
@@ -19,6 +23,41 @@ prop_compose! {
     }
 }
 
+prop_compose! {
+    pub fn any_general_string()
+        (data in string(STRING_LEN)) -> OwnedGeneralString {
+        data.into()
+    }
+}
+
+prop_compose! {
+    pub fn any_ia5_string()
+        (data in "[\\x00-\\x7f]{0,32}") -> OwnedIA5String {
+        data.into()
+    }
+}
+
+prop_compose! {
+    pub fn any_printable_string()
+        (data in "[a-zA-Z0-9 '()+,./:=?-]{0,32}") -> OwnedPrintableString {
+        data.into()
+    }
+}
+
+prop_compose! {
+    pub fn any_numeric_string()
+        (data in "[0-9 ]{0,32}") -> OwnedNumericString {
+        data.into()
+    }
+}
+
+prop_compose! {
+    pub fn any_visible_string()
+        (data in "[\\x20-\\x7e]{0,32}") -> OwnedVisibleString {
+        data.into()
+    }
+}
+
 prop_compose! {
     pub fn any_bit_string()
         (
@@ -38,3 +77,38 @@ prop_compose! {
             data.as_str().into()
         }
 }
+
+prop_compose! {
+    pub fn any_universal_string()
+        (data in string(STRING_LEN)) -> OwnedUniversalString {
+            data.as_str().into()
+        }
+}
+
+prop_compose! {
+    pub fn any_teletex_string()
+        (data in bytes(STRING_LEN)) -> OwnedTeletexString {
+            data.into()
+        }
+}
+
+prop_compose! {
+    pub fn any_videotex_string()
+        (data in bytes(STRING_LEN)) -> OwnedVideotexString {
+            data.into()
+        }
+}
+
+prop_compose! {
+    pub fn any_graphic_string()
+        (data in bytes(STRING_LEN)) -> OwnedGraphicString {
+            data.into()
+        }
+}
+
+prop_compose! {
+    pub fn any_object_descriptor()
+        (data in bytes(STRING_LEN)) -> OwnedObjectDescriptor {
+            data.into()
+        }
+}
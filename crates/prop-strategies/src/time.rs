@@ -0,0 +1,66 @@
+use asn1_parser::{
+    Day, GeneralizedSecond, GeneralizedTime, GeneralizedYear, Hour, LocalTimeDiffFactor, LocalTimeDirection, Minute,
+    Month, Second, UtcTime, Year,
+};
+use proptest::prelude::any;
+use proptest::{option, prop_compose};
+
+prop_compose! {
+    pub fn any_utc_time()
+        (
+            year in 0_u8..100,
+            month in 1_u8..13,
+            day in 1_u8..29,
+            hour in 0_u8..24,
+            minute in 0_u8..60,
+            second in option::of(0_u8..60),
+        ) -> UtcTime {
+        UtcTime::new(
+            Year::try_from(year).unwrap(),
+            Month::try_from(month).unwrap(),
+            Day::try_from(day).unwrap(),
+            Hour::try_from(hour).unwrap(),
+            Minute::try_from(minute).unwrap(),
+            second.map(|second| Second::try_from(second).unwrap()),
+        )
+    }
+}
+
+prop_compose! {
+    pub fn any_local_time_diff_factor()
+        (
+            plus in any::<bool>(),
+            hour in 0_u8..24,
+            minute in 0_u8..60,
+        ) -> LocalTimeDiffFactor {
+        LocalTimeDiffFactor {
+            time_direction: if plus { LocalTimeDirection::Plus } else { LocalTimeDirection::Minus },
+            hour: Hour::try_from(hour).unwrap(),
+            minute: Minute::try_from(minute).unwrap(),
+        }
+    }
+}
+
+prop_compose! {
+    pub fn any_generalized_time()
+        (
+            year in 0_u16..10000,
+            month in 1_u8..13,
+            day in 1_u8..29,
+            hour in 0_u8..24,
+            minute in 0_u8..60,
+            // fractional seconds are encoded with the millisecond precision
+            millis in 0_u16..60000,
+            local_time in option::of(any_local_time_diff_factor()),
+        ) -> GeneralizedTime {
+        GeneralizedTime::new(
+            GeneralizedYear::new(year),
+            Month::try_from(month).unwrap(),
+            Day::try_from(day).unwrap(),
+            Hour::try_from(hour).unwrap(),
+            Minute::try_from(minute).unwrap(),
+            GeneralizedSecond::try_from(f32::from(millis) / 1000.0).unwrap(),
+            local_time,
+        )
+    }
+}
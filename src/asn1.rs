@@ -2,21 +2,42 @@
 mod macros;
 
 mod asn1_viewer;
+mod der_view;
+mod diff_view;
+mod edit_view;
+mod error_view;
 mod hex_view;
+mod json_view;
 mod node_options;
+mod schema_view;
 mod scheme;
+mod search_view;
 
+use std::collections::BTreeSet;
 use std::rc::Rc;
 
-use asn1_parser::{Asn1, Asn1Decoder, Asn1Encoder};
+use asn1_parser::{
+    check_der, diff_asn1, edit_node, set_node_value, text_dump, Asn1, Asn1Decoder, Asn1Encoder, Asn1Entity, Asn1Path,
+    Asn1Schema, OwnedAsn1, TextDumpStyle,
+};
 use web_sys::KeyboardEvent;
-use yew::{classes, function_component, html, use_effect_with_deps, use_reducer, use_state, Callback, Html, Reducible};
+use yew::{
+    classes, function_component, html, use_effect_with_deps, use_memo, use_reducer, use_state, Callback,
+    ContextProvider, Html, Reducible,
+};
 use yew_hooks::{use_clipboard, use_local_storage, use_location};
 use yew_notifications::{use_notification, Notification, NotificationType};
 
 use crate::asn1::asn1_viewer::Asn1Viewer;
+use crate::asn1::der_view::DerViolations;
+use crate::asn1::diff_view::{DiffChanges, DiffMarks};
+use crate::asn1::edit_view::{NodeEdit, NodeEditor};
+use crate::asn1::error_view::DecodeDiagnostics;
 use crate::asn1::hex_view::HexViewer;
-use crate::common::{encode_bytes, ByteInput, BytesFormat};
+use crate::asn1::json_view::JsonImport;
+use crate::asn1::schema_view::{SchemaAnnotations, SchemaInput};
+use crate::asn1::search_view::{Asn1Search, SearchMatches};
+use crate::common::{encode_bytes, ByteInput, BytesFormat, Checkbox};
 use crate::url_query_params;
 use crate::url_query_params::generate_asn1_link;
 
@@ -78,18 +99,24 @@ pub fn asn1_parser_page() -> Html {
 
     let raw_asn1 = use_state(|| TEST_ASN1.to_vec());
     let parsed_asn1 = use_state(|| Asn1::decode_buff(TEST_ASN1).unwrap());
+    // the second asn1 tree is compared with the first one in the diff mode
+    let changed_raw_asn1 = use_state(|| TEST_ASN1.to_vec());
+    let changed_asn1 = use_state(|| Asn1::decode_buff(TEST_ASN1).unwrap());
+    // errors encountered during the lenient decoding of the current asn1 tree
+    let diagnostics = use_state(Vec::new);
 
-    let notifications = use_notification::<Notification>();
     let asn1_setter = parsed_asn1.setter();
+    let diagnostics_setter = diagnostics.setter();
+    let changed_asn1_setter = changed_asn1.setter();
     let raw_data = (*raw_asn1).clone();
-    let parse_asn1 = Callback::from(move |_| match Asn1::decode_buff(&raw_data) {
-        Ok(asn1) => asn1_setter.set(asn1.to_owned_with_asn1(asn1.inner_asn1().to_owned())),
-        Err(error) => notifications.spawn(Notification::new(
-            NotificationType::Error,
-            "Invalid asn1 data",
-            error.message(),
-            Notification::NOTIFICATION_LIFETIME,
-        )),
+    let changed_raw_data = (*changed_raw_asn1).clone();
+    let parse_asn1 = Callback::from(move |_| {
+        let (asn1, errors) = Asn1::decode_buff_lenient(&raw_data);
+        asn1_setter.set(asn1.to_shared());
+        diagnostics_setter.set(errors);
+
+        let (asn1, _) = Asn1::decode_buff_lenient(&changed_raw_data);
+        changed_asn1_setter.set(asn1.to_shared());
     });
 
     let process = parse_asn1.clone();
@@ -107,6 +134,7 @@ pub fn asn1_parser_page() -> Html {
     let notifications = notification_manager.clone();
     let raw_asn1_setter = raw_asn1.setter();
     let asn1_setter = parsed_asn1.setter();
+    let diagnostics_setter = diagnostics.setter();
     let local_storage = use_local_storage::<String>(ASN1_LOCAL_STORAGE_KEY.to_owned());
     use_effect_with_deps(
         move |_: &[(); 0]| {
@@ -118,7 +146,7 @@ pub fn asn1_parser_page() -> Html {
                     if let Ok(bytes) = hex::decode(raw_asn1) {
                         match Asn1::decode_buff(&bytes) {
                             Ok(asn1) => {
-                                asn1_setter.set(asn1.to_owned_with_asn1(asn1.inner_asn1().to_owned()));
+                                asn1_setter.set(asn1.to_shared());
                             }
                             Err(err) => {
                                 error!("Can not decode asn1: {:?}", err);
@@ -133,17 +161,9 @@ pub fn asn1_parser_page() -> Html {
             match serde_qs::from_str(&query[1..]) {
                 Ok(asn1) => {
                     let url_query_params::Asn1 { asn1: asn1_data } = asn1;
-                    match Asn1::decode_buff(&asn1_data) {
-                        Ok(asn1) => {
-                            asn1_setter.set(asn1.to_owned_with_asn1(asn1.inner_asn1().to_owned()));
-                        }
-                        Err(error) => notifications.spawn(Notification::new(
-                            NotificationType::Error,
-                            "Invalid asn1 data",
-                            error.message(),
-                            Notification::NOTIFICATION_LIFETIME,
-                        )),
-                    };
+                    let (asn1, errors) = Asn1::decode_buff_lenient(&asn1_data);
+                    asn1_setter.set(asn1.to_shared());
+                    diagnostics_setter.set(errors);
                     raw_asn1_setter.set(asn1_data);
                 }
                 Err(err) => notifications.spawn(Notification::new(
@@ -160,13 +180,14 @@ pub fn asn1_parser_page() -> Html {
     let local_storage = use_local_storage::<String>(ASN1_LOCAL_STORAGE_KEY.to_owned());
     use_effect_with_deps(
         move |asn1| {
-            let mut encoded = vec![0; asn1.needed_buf_size()];
-            asn1.encode_buff(&mut encoded).expect("ASN1 encoding should not fail");
+            let encoded = asn1.encode_to_vec().expect("ASN1 encoding should not fail");
             local_storage.set(encode_bytes(encoded, BytesFormat::Hex));
         },
         parsed_asn1.clone(),
     );
 
+    let json_notifications = notification_manager.clone();
+    let dump_notifications = notification_manager.clone();
     let clipboard = use_clipboard();
     let raw_asn1_data = (*raw_asn1).clone();
     let share_by_link = Callback::from(move |_| {
@@ -178,11 +199,159 @@ pub fn asn1_parser_page() -> Html {
         ));
     });
 
+    let clipboard = use_clipboard();
+    let asn1 = (*parsed_asn1).clone();
+    let export_json = Callback::from(move |_| {
+        clipboard.write_text(serde_json::to_string_pretty(&asn1).expect("asn1 serialization should not fail"));
+
+        json_notifications.spawn(Notification::from_description_and_type(
+            NotificationType::Info,
+            "JSON copied",
+        ));
+    });
+
+    let dumpasn1_layout = use_state(|| false);
+    let dumpasn1_layout_setter = dumpasn1_layout.setter();
+    let set_dumpasn1_layout = Callback::from(move |checked| dumpasn1_layout_setter.set(checked));
+
+    let clipboard = use_clipboard();
+    let asn1 = (*parsed_asn1).clone();
+    let dump_style = if *dumpasn1_layout {
+        TextDumpStyle::DumpAsn1
+    } else {
+        TextDumpStyle::OpensslAsn1Parse
+    };
+    let copy_text_dump = Callback::from(move |_| {
+        clipboard.write_text(text_dump(&asn1, dump_style));
+
+        dump_notifications.spawn(Notification::from_description_and_type(
+            NotificationType::Info,
+            "text dump copied",
+        ));
+    });
+
+    let import_json = use_state(|| false);
+    let import_json_setter = import_json.setter();
+    let set_import_json = Callback::from(move |checked| import_json_setter.set(checked));
+
+    let raw_asn1_setter = raw_asn1.setter();
+    let asn1_setter = parsed_asn1.setter();
+    let diagnostics_setter = diagnostics.setter();
+    let set_imported_asn1 = Callback::from(move |asn1: OwnedAsn1| {
+        raw_asn1_setter.set(asn1.meta().raw_bytes().to_vec());
+        asn1_setter.set(asn1);
+        diagnostics_setter.set(Vec::new());
+    });
+
+    let edit_notifications = notification_manager.clone();
+    let raw_asn1_setter = raw_asn1.setter();
+    let asn1_setter = parsed_asn1.setter();
+    let diagnostics_setter = diagnostics.setter();
+    let asn1 = (*parsed_asn1).clone();
+    // the edited tree is encoded into the input data and decoded again to get valid offsets and node ids
+    let edit_asn1_node = NodeEditor(Callback::from(move |NodeEdit { node_id, value }| {
+        let mut asn1 = asn1.clone();
+        let encoded = match edit_node(&mut asn1, node_id, |node| set_node_value(node, &value)) {
+            Ok(true) => asn1.encode_to_vec().map_err(|err| err.to_string()),
+            Ok(false) => Err(format!("Node {} not found", node_id)),
+            Err(err) => Err(err.to_string()),
+        };
+
+        match encoded {
+            Ok(encoded) => {
+                let (asn1, errors) = Asn1::decode_buff_lenient(&encoded);
+                asn1_setter.set(asn1.to_shared());
+                diagnostics_setter.set(errors);
+                raw_asn1_setter.set(encoded);
+            }
+            Err(err) => edit_notifications.spawn(Notification::new(
+                NotificationType::Error,
+                "Can not edit the node",
+                err,
+                Notification::NOTIFICATION_LIFETIME,
+            )),
+        }
+    }));
+
     let raw_asn1_setter = raw_asn1.setter();
 
+    let strict_der = use_state(|| false);
+    let strict_der_setter = strict_der.setter();
+    let set_strict_der = Callback::from(move |checked| strict_der_setter.set(checked));
+
+    let use_schema = use_state(|| false);
+    let use_schema_setter = use_schema.setter();
+    let set_use_schema = Callback::from(move |checked| use_schema_setter.set(checked));
+
+    let schema_module = use_state(String::new);
+    let schema_module_setter = schema_module.setter();
+    let set_schema_module = Callback::from(move |module| schema_module_setter.set(module));
+
+    // empty root type means the first type of the module
+    let root_type = use_state(String::new);
+    let root_type_setter = root_type.setter();
+    let set_root_type = Callback::from(move |type_name| root_type_setter.set(type_name));
+
+    let schema = use_memo(|module| Asn1Schema::parse(module), (*schema_module).clone());
+    let (type_names, schema_error) = match schema.as_ref() {
+        Ok(schema) => (schema.type_names().map(ToOwned::to_owned).collect::<Vec<_>>(), None),
+        Err(err) => (Vec::new(), Some(err.to_string())),
+    };
+    let root_type_name = if type_names.contains(&*root_type) {
+        (*root_type).clone()
+    } else {
+        type_names.first().cloned().unwrap_or_default()
+    };
+    let annotations = match schema.as_ref() {
+        Ok(schema) if *use_schema => SchemaAnnotations(Rc::new(schema.annotate(&root_type_name, &parsed_asn1))),
+        _ => SchemaAnnotations::default(),
+    };
+
+    let diff_mode = use_state(|| false);
+    let diff_mode_setter = diff_mode.setter();
+    let set_diff_mode = Callback::from(move |checked| diff_mode_setter.set(checked));
+    let changed_raw_asn1_setter = changed_raw_asn1.setter();
+
+    let (changes, diff_error) = if *diff_mode {
+        match diff_asn1(&parsed_asn1, &changed_asn1) {
+            Ok(changes) => (changes, None),
+            Err(err) => (Vec::new(), Some(err.to_string())),
+        }
+    } else {
+        (Vec::new(), None)
+    };
+
+    let search_path = use_state(String::new);
+    let search_path_setter = search_path.setter();
+    let set_search_path = Callback::from(move |path| search_path_setter.set(path));
+
+    let (search_matches, search_result) = if search_path.trim().is_empty() {
+        (SearchMatches::default(), Ok(0))
+    } else {
+        match Asn1Path::parse(&search_path) {
+            Ok(path) => {
+                let ids = path
+                    .find(&parsed_asn1)
+                    .into_iter()
+                    .map(|node| node.id())
+                    .collect::<BTreeSet<_>>();
+                let count = ids.len();
+                (SearchMatches(Rc::new(ids)), Ok(count))
+            }
+            Err(err) => (SearchMatches::default(), Err(err.to_string())),
+        }
+    };
+
     let ctx = use_reducer(Highlight::default);
     let asn1_dispatcher = ctx.dispatcher();
     let hex_dispatcher = ctx.dispatcher();
+    let der_dispatcher = ctx.dispatcher();
+    let diff_dispatcher = ctx.dispatcher();
+
+    // node ids of the compared trees are independent, so the changed tree has its own highlight
+    let changed_ctx = use_reducer(Highlight::default);
+    let changed_asn1_dispatcher = changed_ctx.dispatcher();
+    let changed_diff_dispatcher = changed_ctx.dispatcher();
 
     html! {
         <div class={classes!("vertical", "asn1-page")} {onkeydown}>
@@ -200,19 +369,108 @@ pub fn asn1_parser_page() -> Html {
                 <button class="button-with-icon" onclick={share_by_link}>
                     <img src="/public/img/icons/share_by_link.png" />
                 </button>
+                <button class="action-button" onclick={export_json}>{"Export JSON"}</button>
+                <button class="action-button" onclick={copy_text_dump}>{"Copy as text dump"}</button>
+                <Checkbox id={"asn1-dumpasn1".to_owned()} name={"dumpasn1 layout".to_owned()} checked={*dumpasn1_layout} set_checked={set_dumpasn1_layout} />
+                <Checkbox id={"asn1-import-json".to_owned()} name={"import JSON".to_owned()} checked={*import_json} set_checked={set_import_json} />
+                <Checkbox id={"strict-der".to_owned()} name={"strict DER".to_owned()} checked={*strict_der} set_checked={set_strict_der} />
+                <Checkbox id={"asn1-schema".to_owned()} name={"schema".to_owned()} checked={*use_schema} set_checked={set_use_schema} />
+                <Checkbox id={"asn1-diff".to_owned()} name={"diff".to_owned()} checked={*diff_mode} set_checked={set_diff_mode} />
             </div>
-            <div class="asn1-viewers">
-                <Asn1Viewer
-                    structure={(*parsed_asn1).clone()}
-                    cur_node={(*ctx).current()}
-                    set_cur_node={move |action| asn1_dispatcher.dispatch(action)}
-                />
-                <HexViewer
-                    structure={(*parsed_asn1).clone()}
-                    cur_node={(*ctx).current()}
-                    set_cur_node={move |action| hex_dispatcher.dispatch(action)}
-                />
-            </div>
+            {if *diff_mode {
+                html! {
+                    <ByteInput bytes={(*changed_raw_asn1).clone()} setter={Callback::from(move |data| changed_raw_asn1_setter.set(data))} placeholder={"changed asn1 data".to_owned()} rows={10} />
+                }
+            } else {
+                html! {}
+            }}
+            {if *import_json {
+                html! {
+                    <JsonImport set_asn1={set_imported_asn1} />
+                }
+            } else {
+                html! {}
+            }}
+            {if *use_schema {
+                html! {
+                    <SchemaInput
+                        module={(*schema_module).clone()}
+                        set_module={set_schema_module}
+                        {type_names}
+                        root_type={root_type_name}
+                        {set_root_type}
+                        error={schema_error}
+                    />
+                }
+            } else {
+                html! {}
+            }}
+            <Asn1Search path={(*search_path).clone()} set_path={set_search_path} result={search_result} />
+            <DecodeDiagnostics diagnostics={(*diagnostics).clone()} />
+            {if *strict_der {
+                html! {
+                    <DerViolations
+                        violations={check_der(&parsed_asn1)}
+                        set_cur_node={move |action| der_dispatcher.dispatch(action)}
+                    />
+                }
+            } else {
+                html! {}
+            }}
+            {if *diff_mode {
+                html! {
+                    <>
+                        <DiffChanges
+                            changes={changes.clone()}
+                            error={diff_error}
+                            set_original_node={move |action| diff_dispatcher.dispatch(action)}
+                            set_changed_node={move |action| changed_diff_dispatcher.dispatch(action)}
+                        />
+                        <div class="asn1-diff-viewers">
+                            <ContextProvider<SchemaAnnotations> context={annotations.clone()}>
+                                <ContextProvider<SearchMatches> context={search_matches.clone()}>
+                                    <ContextProvider<DiffMarks> context={DiffMarks::original(&changes)}>
+                                        <Asn1Viewer
+                                            structure={(*parsed_asn1).clone()}
+                                            cur_node={(*ctx).current()}
+                                            set_cur_node={move |action| asn1_dispatcher.dispatch(action)}
+                                        />
+                                    </ContextProvider<DiffMarks>>
+                                </ContextProvider<SearchMatches>>
+                            </ContextProvider<SchemaAnnotations>>
+                            <ContextProvider<DiffMarks> context={DiffMarks::changed(&changes)}>
+                                <Asn1Viewer
+                                    structure={(*changed_asn1).clone()}
+                                    cur_node={(*changed_ctx).current()}
+                                    set_cur_node={move |action| changed_asn1_dispatcher.dispatch(action)}
+                                />
+                            </ContextProvider<DiffMarks>>
+                        </div>
+                    </>
+                }
+            } else {
+                html! {
+                    <div class="asn1-viewers">
+                        <ContextProvider<SchemaAnnotations> context={annotations}>
+                            <ContextProvider<SearchMatches> context={search_matches.clone()}>
+                                <ContextProvider<NodeEditor> context={edit_asn1_node}>
+                                    <Asn1Viewer
+                                        structure={(*parsed_asn1).clone()}
+                                        cur_node={(*ctx).current()}
+                                        set_cur_node={move |action| asn1_dispatcher.dispatch(action)}
+                                    />
+                                </ContextProvider<NodeEditor>>
+                            </ContextProvider<SearchMatches>>
+                        </ContextProvider<SchemaAnnotations>>
+                        <HexViewer
+                            structure={(*parsed_asn1).clone()}
+                            cur_node={(*ctx).current()}
+                            set_cur_node={move |action| hex_dispatcher.dispatch(action)}
+                            {search_matches}
+                        />
+                    </div>
+                }
+            }}
         </div>
     }
 }
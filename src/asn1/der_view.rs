@@ -0,0 +1,41 @@
+use asn1_parser::DerViolation;
+use web_sys::MouseEvent;
+use yew::{function_component, html, Callback, Html, Properties};
+
+use crate::asn1::HighlightAction;
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct DerViolationsProps {
+    pub violations: Vec<DerViolation>,
+    pub set_cur_node: Callback<HighlightAction>,
+}
+
+/// Renders DER rules violations. Hovering over the violation highlights the corresponding asn1 node
+#[function_component(DerViolations)]
+pub fn der_violations(props: &DerViolationsProps) -> Html {
+    if props.violations.is_empty() {
+        return html! {
+            <span class="total">{"The data is DER encoded"}</span>
+        };
+    }
+
+    html! {
+        <div class="vertical asn1-diagnostics">
+            <span class="total">{format!("The data is not DER encoded ({} violations):", props.violations.len())}</span>
+            {for props.violations.iter().map(|violation| {
+                let node_id = violation.node_id;
+
+                let set_cur_node = props.set_cur_node.clone();
+                let onmouseenter = Callback::from(move |_: MouseEvent| set_cur_node.emit(HighlightAction::Show(node_id)));
+                let set_cur_node = props.set_cur_node.clone();
+                let onmouseleave = Callback::from(move |_: MouseEvent| set_cur_node.emit(HighlightAction::Hide(node_id)));
+
+                html! {
+                    <span class="asn1-error-message asn1-der-violation" {onmouseenter} {onmouseleave}>
+                        {format!("{} at offset {}", violation.kind, violation.offset)}
+                    </span>
+                }
+            })}
+        </div>
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use asn1_parser::{Asn1Change, Asn1ChangeKind, Asn1ChangedNode};
+use web_sys::MouseEvent;
+use yew::{function_component, html, Callback, Html, Properties};
+
+use crate::asn1::HighlightAction;
+
+/// Changed nodes of one of the compared asn1 trees by node id
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DiffMarks(pub Rc<BTreeMap<u64, Asn1ChangeKind>>);
+
+impl DiffMarks {
+    pub fn original(changes: &[Asn1Change]) -> Self {
+        Self::collect(
+            changes
+                .iter()
+                .filter_map(|change| Some((change.original?, change.kind))),
+        )
+    }
+
+    pub fn changed(changes: &[Asn1Change]) -> Self {
+        Self::collect(changes.iter().filter_map(|change| Some((change.changed?, change.kind))))
+    }
+
+    fn collect(nodes: impl Iterator<Item = (Asn1ChangedNode, Asn1ChangeKind)>) -> Self {
+        Self(Rc::new(nodes.map(|(node, kind)| (node.node_id, kind)).collect()))
+    }
+
+    pub fn get(&self, id: u64) -> Option<Asn1ChangeKind> {
+        self.0.get(&id).copied()
+    }
+}
+
+pub fn diff_class(kind: Asn1ChangeKind) -> &'static str {
+    match kind {
+        Asn1ChangeKind::Inserted => "asn1-diff-inserted",
+        Asn1ChangeKind::Removed => "asn1-diff-removed",
+        Asn1ChangeKind::Changed => "asn1-diff-changed",
+    }
+}
+
+fn format_offset(node: Option<Asn1ChangedNode>) -> String {
+    node.map(|node| node.offset.to_string())
+        .unwrap_or_else(|| "-".to_owned())
+}
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct DiffChangesProps {
+    pub changes: Vec<Asn1Change>,
+    pub error: Option<String>,
+    pub set_original_node: Callback<HighlightAction>,
+    pub set_changed_node: Callback<HighlightAction>,
+}
+
+/// Renders the list of changes. Hovering over the change highlights the corresponding nodes in both trees
+#[function_component(DiffChanges)]
+pub fn diff_changes(props: &DiffChangesProps) -> Html {
+    if let Some(error) = &props.error {
+        return html! {
+            <span class="asn1-error-message">{format!("Can not compare the asn1 trees: {}", error)}</span>
+        };
+    }
+
+    if props.changes.is_empty() {
+        return html! {
+            <span class="total">{"The asn1 trees are equal"}</span>
+        };
+    }
+
+    html! {
+        <div class="vertical asn1-diagnostics">
+            <span class="total">{format!("{} changes:", props.changes.len())}</span>
+            {for props.changes.iter().map(|change| {
+                let original = change.original;
+                let changed = change.changed;
+
+                let set_original_node = props.set_original_node.clone();
+                let set_changed_node = props.set_changed_node.clone();
+                let onmouseenter = Callback::from(move |_: MouseEvent| {
+                    if let Some(node) = original {
+                        set_original_node.emit(HighlightAction::Show(node.node_id));
+                    }
+                    if let Some(node) = changed {
+                        set_changed_node.emit(HighlightAction::Show(node.node_id));
+                    }
+                });
+                let set_original_node = props.set_original_node.clone();
+                let set_changed_node = props.set_changed_node.clone();
+                let onmouseleave = Callback::from(move |_: MouseEvent| {
+                    if let Some(node) = original {
+                        set_original_node.emit(HighlightAction::Hide(node.node_id));
+                    }
+                    if let Some(node) = changed {
+                        set_changed_node.emit(HighlightAction::Hide(node.node_id));
+                    }
+                });
+
+                html! {
+                    <span class={format!("asn1-diff-change {}", diff_class(change.kind))} {onmouseenter} {onmouseleave}>
+                        {format!(
+                            "{}: original offset {}, changed offset {}",
+                            change.kind,
+                            format_offset(original),
+                            format_offset(changed),
+                        )}
+                    </span>
+                }
+            })}
+        </div>
+    }
+}
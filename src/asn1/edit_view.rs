@@ -0,0 +1,79 @@
+use asn1_parser::{node_value, Asn1, Asn1Type};
+use web_sys::{FocusEvent, HtmlInputElement, KeyboardEvent};
+use yew::{
+    function_component, html, use_effect_with_deps, use_node_ref, use_state, Callback, Html, Properties, TargetCast,
+};
+
+/// New value of the asn1 node entered by the user
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeEdit {
+    pub node_id: u64,
+    pub value: String,
+}
+
+/// Applies node edits to the asn1 tree. Nodes can be edited only when this context is provided
+#[derive(Clone, PartialEq)]
+pub struct NodeEditor(pub Callback<NodeEdit>);
+
+/// Returns the text value of the node if it can be edited
+pub fn editable_value(asn1: &Asn1<'_>) -> Option<String> {
+    match asn1.inner_asn1() {
+        Asn1Type::UtcTime(_) | Asn1Type::GeneralizedTime(_) => {
+            String::from_utf8(asn1.meta().data_bytes().to_vec()).ok()
+        }
+        _ => node_value(asn1),
+    }
+}
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct NodeValueInputProps {
+    pub value: String,
+    pub submit: Callback<String>,
+    pub cancel: Callback<()>,
+}
+
+/// Renders the node value input. Enter submits the new value, Escape or leaving the input cancels the editing
+#[function_component(NodeValueInput)]
+pub fn node_value_input(props: &NodeValueInputProps) -> Html {
+    let value = use_state(|| props.value.clone());
+
+    let input_ref = use_node_ref();
+    let focus_ref = input_ref.clone();
+    use_effect_with_deps(
+        move |_: &()| {
+            if let Some(input) = focus_ref.cast::<HtmlInputElement>() {
+                input.select();
+            }
+        },
+        (),
+    );
+
+    let value_setter = value.setter();
+    let oninput = Callback::from(move |event: html::oninput::Event| {
+        let input: HtmlInputElement = event.target_unchecked_into();
+        value_setter.set(input.value());
+    });
+
+    let new_value = (*value).clone();
+    let submit = props.submit.clone();
+    let cancel = props.cancel.clone();
+    let onkeydown = Callback::from(move |event: KeyboardEvent| match event.key().as_str() {
+        "Enter" => submit.emit(new_value.clone()),
+        "Escape" => cancel.emit(()),
+        _ => {}
+    });
+
+    let cancel = props.cancel.clone();
+    let onblur = Callback::from(move |_: FocusEvent| cancel.emit(()));
+
+    html! {
+        <input
+            ref={input_ref}
+            class="base-input asn1-node-editor"
+            value={(*value).clone()}
+            {oninput}
+            {onkeydown}
+            {onblur}
+        />
+    }
+}
@@ -0,0 +1,24 @@
+use asn1_parser::Error;
+use yew::{function_component, html, Html, Properties};
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct DecodeDiagnosticsProps {
+    pub diagnostics: Vec<Error>,
+}
+
+/// Renders errors that were encountered during the lenient asn1 decoding
+#[function_component(DecodeDiagnostics)]
+pub fn decode_diagnostics(props: &DecodeDiagnosticsProps) -> Html {
+    if props.diagnostics.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <div class="vertical asn1-diagnostics">
+            <span class="total">{format!("The data was decoded partially ({} errors):", props.diagnostics.len())}</span>
+            {for props.diagnostics.iter().map(|error| html! {
+                <span class="asn1-error-message">{error.to_string()}</span>
+            })}
+        </div>
+    }
+}
@@ -4,6 +4,7 @@ use yew::virtual_dom::VNode;
 use yew::{function_component, html, Callback, Classes, Html, Properties};
 
 use crate::asn1::node_options::NodeOptions;
+use crate::asn1::search_view::SearchMatches;
 use crate::asn1::{compare_ids, HighlightAction};
 use crate::common::{hex_format_byte, RcSlice};
 
@@ -13,6 +14,8 @@ pub struct HexViewerProps {
 
     pub cur_node: Option<u64>,
     pub set_cur_node: Callback<HighlightAction>,
+
+    pub search_matches: SearchMatches,
 }
 
 #[function_component(HexViewer)]
@@ -23,7 +26,14 @@ pub fn hex_viewer(props: &HexViewerProps) -> Html {
                 {{
                     let set_cur_node = props.set_cur_node.clone();
                     let mut bytes = Vec::with_capacity(props.structure.meta().raw_data.len());
-                    build_hex_bytes(&props.structure, &props.cur_node.clone(), set_cur_node, &mut bytes, false);
+                    build_hex_bytes(
+                        &props.structure,
+                        &props.cur_node.clone(),
+                        &props.search_matches,
+                        set_cur_node,
+                        &mut bytes,
+                        false,
+                    );
                     bytes
                 }}
             </div>
@@ -95,14 +105,15 @@ fn format_bytes(
 fn build_hex_bytes(
     asn1: &Asn1<'_>,
     cur_node: &Option<u64>,
+    search_matches: &SearchMatches,
     set_cur_node: Callback<HighlightAction>,
     bytes: &mut Vec<VNode>,
     select_all: bool,
 ) {
     let asn1_node_id = asn1.id();
     let if_selected = compare_ids(asn1_node_id, cur_node);
+    let is_search_match = search_matches.contains(asn1_node_id);
 
-    let tag: u8 = asn1.tag().into();
     let tag_set_cur_node = set_cur_node.clone();
     let onmouseenter = Callback::from(move |_: MouseEvent| tag_set_cur_node.emit(HighlightAction::Show(asn1_node_id)));
     let tag_set_cur_node = set_cur_node.clone();
@@ -115,20 +126,25 @@ fn build_hex_bytes(
     let length_len = meta.length_range().len();
     let data_len = meta.data_range().len();
 
-    bytes.push(html! {
-        <span
-            class={if select_all {
-                "asn1-hex-byte asn1-hex-byte-data-selected"
-            } else if if_selected {
-                "asn1-hex-byte asn1-hex-byte-tag-selected"
-            } else {
-                "asn1-hex-byte asn1-hex-byte-tag"
-            }}
-            {onmouseenter}
-            {onmouseleave}
-        >
-            <NodeOptions node_bytes={raw_bytes.clone()} {offset} {length_len} {data_len} name={hex_format_byte(tag)}/>
-        </span>
+    // the tag can take more than one byte (high-tag-number form)
+    meta.tag_bytes().iter().for_each(|tag| {
+        bytes.push(html! {
+            <span
+                class={if select_all {
+                    "asn1-hex-byte asn1-hex-byte-data-selected"
+                } else if if_selected {
+                    "asn1-hex-byte asn1-hex-byte-tag-selected"
+                } else if is_search_match {
+                    "asn1-hex-byte asn1-hex-byte-tag asn1-hex-byte-search-match"
+                } else {
+                    "asn1-hex-byte asn1-hex-byte-tag"
+                }}
+                onmouseenter={onmouseenter.clone()}
+                onmouseleave={onmouseleave.clone()}
+            >
+                <NodeOptions node_bytes={raw_bytes.clone()} {offset} {length_len} {data_len} name={hex_format_byte(*tag)}/>
+            </span>
+        })
     });
 
     format_bytes(
@@ -149,13 +165,31 @@ fn build_hex_bytes(
 
     build_data_bytes(
         asn1,
-        raw_bytes,
+        raw_bytes.clone(),
         asn1_node_id,
         cur_node,
-        set_cur_node,
+        search_matches,
+        set_cur_node.clone(),
         bytes,
         if_selected || select_all,
     );
+
+    // end-of-contents octets of the indefinite length form
+    format_bytes(
+        meta,
+        raw_bytes,
+        meta.end_of_contents_bytes(),
+        asn1_node_id,
+        if select_all {
+            "asn1-hex-byte-data-selected"
+        } else if if_selected {
+            "asn1-hex-byte-len-selected"
+        } else {
+            "asn1-hex-byte-len"
+        },
+        set_cur_node,
+        bytes,
+    );
 }
 
 fn build_data_bytes(
@@ -163,6 +197,7 @@ fn build_data_bytes(
     raw_bytes: RcSlice,
     asn1_node_id: u64,
     cur_node: &Option<u64>,
+    search_matches: &SearchMatches,
     set_cur_node: Callback<HighlightAction>,
     bytes: &mut Vec<VNode>,
     select_all: bool,
@@ -196,19 +231,18 @@ fn build_data_bytes(
     match asn1.inner_asn1() {
         Asn1Type::Sequence(sequence) => {
             let set_cur_node = set_cur_node.clone();
-            sequence
-                .fields()
-                .iter()
-                .for_each(move |asn1| build_hex_bytes(asn1, cur_node, set_cur_node.clone(), bytes, select_all));
+            sequence.fields().iter().for_each(move |asn1| {
+                build_hex_bytes(asn1, cur_node, search_matches, set_cur_node.clone(), bytes, select_all)
+            });
         }
         Asn1Type::Set(set) => {
             let set_cur_node = set_cur_node.clone();
-            set.fields()
-                .iter()
-                .for_each(move |asn1| build_hex_bytes(asn1, cur_node, set_cur_node.clone(), bytes, select_all));
+            set.fields().iter().for_each(move |asn1| {
+                build_hex_bytes(asn1, cur_node, search_matches, set_cur_node.clone(), bytes, select_all)
+            });
         }
         Asn1Type::OctetString(octet) => match octet.inner() {
-            Some(asn1) => build_hex_bytes(asn1, cur_node, set_cur_node.clone(), bytes, select_all),
+            Some(asn1) => build_hex_bytes(asn1, cur_node, search_matches, set_cur_node.clone(), bytes, select_all),
             None => default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all),
         },
         Asn1Type::Utf8String(_) => {
@@ -229,12 +263,34 @@ fn build_data_bytes(
         Asn1Type::VisibleString(_) => {
             default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all)
         }
+        Asn1Type::UniversalString(_) => {
+            default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all)
+        }
+        Asn1Type::TeletexString(_) => {
+            default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all)
+        }
+        Asn1Type::VideotexString(_) => {
+            default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all)
+        }
+        Asn1Type::GraphicString(_) => {
+            default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all)
+        }
+        Asn1Type::ObjectDescriptor(_) => {
+            default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all)
+        }
+        Asn1Type::Enumerated(_) => {
+            default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all)
+        }
+        Asn1Type::Real(_) => default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all),
+        Asn1Type::RelativeOid(_) => {
+            default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all)
+        }
         Asn1Type::UtcTime(_) => default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all),
         Asn1Type::GeneralizedTime(_) => {
             default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all)
         }
         Asn1Type::BitString(bit) => match bit.inner() {
-            Some(asn1) => build_hex_bytes(asn1, cur_node, set_cur_node.clone(), bytes, select_all),
+            Some(asn1) => build_hex_bytes(asn1, cur_node, search_matches, set_cur_node.clone(), bytes, select_all),
             None => default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all),
         },
         Asn1Type::BmpString(_) => {
@@ -248,21 +304,41 @@ fn build_data_bytes(
         }
         Asn1Type::ExplicitTag(explicit) => {
             let set_cur_node = set_cur_node.clone();
-            explicit
-                .inner()
-                .iter()
-                .for_each(move |asn1| build_hex_bytes(asn1, cur_node, set_cur_node.clone(), bytes, select_all));
+            explicit.inner().iter().for_each(move |asn1| {
+                build_hex_bytes(asn1, cur_node, search_matches, set_cur_node.clone(), bytes, select_all)
+            });
         }
         Asn1Type::ImplicitTag(implicit) => match implicit.inner_asn1() {
-            Some(asn1) => build_hex_bytes(asn1, cur_node, set_cur_node.clone(), bytes, select_all),
+            Some(asn1) => build_hex_bytes(asn1, cur_node, search_matches, set_cur_node.clone(), bytes, select_all),
             None => default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all),
         },
         Asn1Type::ApplicationTag(application) => {
             let set_cur_node = set_cur_node.clone();
-            application
-                .inner()
-                .iter()
-                .for_each(move |asn1| build_hex_bytes(asn1, cur_node, set_cur_node.clone(), bytes, select_all));
+            application.inner().iter().for_each(move |asn1| {
+                build_hex_bytes(asn1, cur_node, search_matches, set_cur_node.clone(), bytes, select_all)
+            });
         }
+        Asn1Type::Unknown(unknown) => match unknown.fields() {
+            Some(fields) => {
+                let set_cur_node = set_cur_node.clone();
+                fields.iter().for_each(move |asn1| {
+                    build_hex_bytes(asn1, cur_node, search_matches, set_cur_node.clone(), bytes, select_all)
+                });
+            }
+            None => default_bytes(asn1_node_id, cur_node, set_cur_node, asn1, raw_bytes, bytes, select_all),
+        },
+        Asn1Type::Unparsed(_) => format_bytes(
+            asn1.meta(),
+            raw_bytes,
+            asn1.meta().data_bytes(),
+            asn1_node_id,
+            if compare_ids(asn1_node_id, cur_node) || select_all {
+                "asn1-hex-byte-data-selected"
+            } else {
+                "asn1-hex-byte-error"
+            },
+            set_cur_node,
+            bytes,
+        ),
     }
 }
@@ -0,0 +1,54 @@
+use asn1_parser::OwnedAsn1;
+use web_sys::HtmlInputElement;
+use yew::{function_component, html, use_state, Callback, Html, Properties, TargetCast};
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct JsonImportProps {
+    pub set_asn1: Callback<OwnedAsn1>,
+}
+
+/// Renders the JSON input. The imported asn1 tree replaces the current one
+#[function_component(JsonImport)]
+pub fn json_import(props: &JsonImportProps) -> Html {
+    let json = use_state(String::new);
+    let error = use_state(|| None::<String>);
+
+    let json_setter = json.setter();
+    let oninput = Callback::from(move |event: html::oninput::Event| {
+        let input: HtmlInputElement = event.target_unchecked_into();
+        json_setter.set(input.value());
+    });
+
+    let set_asn1 = props.set_asn1.clone();
+    let raw_json = (*json).clone();
+    let error_setter = error.setter();
+    let onclick = Callback::from(move |_| match serde_json::from_str::<OwnedAsn1>(&raw_json) {
+        Ok(asn1) => {
+            error_setter.set(None);
+            set_asn1.emit(asn1);
+        }
+        Err(err) => error_setter.set(Some(err.to_string())),
+    });
+
+    html! {
+        <div class="vertical asn1-json">
+            <textarea
+                rows="8"
+                placeholder={"asn1 tree in JSON, e.g. exported by the \"export JSON\" button"}
+                class="base-input"
+                value={(*json).clone()}
+                {oninput}
+            />
+            <div class="horizontal">
+                <button class="action-button" {onclick}>{"Import"}</button>
+                {if let Some(error) = error.as_ref() {
+                    html! {
+                        <span class="asn1-error-message">{format!("Can not import the JSON: {}", error)}</span>
+                    }
+                } else {
+                    html! {}
+                }}
+            </div>
+        </div>
+    }
+}
@@ -1,4 +1,5 @@
-use yew::{function_component, html, use_state, Callback, Html, Properties};
+use asn1_parser::SchemaAnnotation;
+use yew::{function_component, html, use_context, use_state, Callback, Html, Properties};
 use yew_hooks::use_clipboard;
 use yew_notifications::{use_notification, Notification, NotificationType};
 
@@ -30,8 +31,15 @@ pub fn node_options(props: &NodeOptionsProps) -> Html {
 
     let clipboard = use_clipboard();
     let notifications = use_notification::<Notification>();
-    let node_bytes_len = props.node_bytes.len();
-    let value_raw = props.node_bytes.with_range(props.length_len + 1, node_bytes_len);
+    // the value can be followed by the end-of-contents octets (indefinite length form),
+    // so we calculate its position from the beginning of the node.
+    // unparsed bytes have neither tag nor length octets
+    let value_start = if props.length_len == 0 {
+        0
+    } else {
+        tag_len(props.node_bytes.data()) + props.length_len
+    };
+    let value_raw = props.node_bytes.with_range(value_start, value_start + props.data_len);
     let copy_value = Callback::from(move |_| {
         clipboard.write_text(hex::encode(value_raw.data()));
 
@@ -53,6 +61,18 @@ pub fn node_options(props: &NodeOptionsProps) -> Html {
         ));
     });
 
+    // nodes matched by the user-provided schema are labeled with their schema path
+    let name = match use_context::<Option<SchemaAnnotation>>().flatten() {
+        Some(SchemaAnnotation { path, type_name }) => html! {
+            <span class="asn1-node-options-name asn1-node-schema-name" {onclick} title={format!("{}: {}", type_name, props.name)}>
+                {if path.is_empty() { type_name } else { path }}
+            </span>
+        },
+        None => html! {
+            <span class="asn1-node-options-name" {onclick}>{props.name.clone()}</span>
+        },
+    };
+
     html! {
         <div class="asn1-node-options-container">
             {if *show_options {html! {
@@ -67,7 +87,17 @@ pub fn node_options(props: &NodeOptionsProps) -> Html {
                     </div>
                 </div>
             }} else {html! {}}}
-            <span class="asn1-node-options-name" {onclick}>{props.name.clone()}</span>
+            {name}
         </div>
     }
 }
+
+/// Returns how many bytes the tag takes. The tag can take more than one byte (high-tag-number form).
+fn tag_len(node_bytes: &[u8]) -> usize {
+    match node_bytes {
+        [first, subsequent @ ..] if first & 0x1f == 0x1f => {
+            2 + subsequent.iter().take_while(|byte| *byte & 0x80 != 0).count()
+        }
+        _ => 1,
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use asn1_parser::SchemaAnnotation;
+use web_sys::HtmlInputElement;
+use yew::html::onchange::Event;
+use yew::virtual_dom::VNode;
+use yew::{function_component, html, Callback, Html, Properties, TargetCast};
+
+/// Schema annotations of the current asn1 tree by node id
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SchemaAnnotations(pub Rc<BTreeMap<u64, SchemaAnnotation>>);
+
+impl SchemaAnnotations {
+    pub fn get(&self, id: u64) -> Option<SchemaAnnotation> {
+        self.0.get(&id).cloned()
+    }
+}
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct SchemaInputProps {
+    pub module: String,
+    pub set_module: Callback<String>,
+    pub type_names: Vec<String>,
+    pub root_type: String,
+    pub set_root_type: Callback<String>,
+    pub error: Option<String>,
+}
+
+fn render_type_options(type_names: &[String], root_type: &str) -> Vec<VNode> {
+    type_names
+        .iter()
+        .map(|type_name| {
+            html! {
+                <option selected={type_name == root_type} value={type_name.clone()}>{type_name}</option>
+            }
+        })
+        .collect()
+}
+
+/// Renders the asn1 module input and the root type selection
+#[function_component(SchemaInput)]
+pub fn schema_input(props: &SchemaInputProps) -> Html {
+    let set_module = props.set_module.clone();
+    let on_module_input = Callback::from(move |event: html::oninput::Event| {
+        let input: HtmlInputElement = event.target_unchecked_into();
+        set_module.emit(input.value());
+    });
+
+    let set_root_type = props.set_root_type.clone();
+    let on_root_type_change = Callback::from(move |event: Event| {
+        let input: HtmlInputElement = event.target_unchecked_into();
+        set_root_type.emit(input.value());
+    });
+
+    html! {
+        <div class="vertical asn1-schema">
+            <textarea
+                rows="8"
+                placeholder={"asn1 module, e.g. Certificate ::= SEQUENCE { ... }"}
+                class="base-input"
+                value={props.module.clone()}
+                oninput={on_module_input}
+            />
+            {if let Some(error) = &props.error {
+                html! {
+                    <span class="asn1-error-message">{format!("Can not parse the schema: {}", error)}</span>
+                }
+            } else {
+                html! {
+                    <div class="horizontal">
+                        <span>{"Root type:"}</span>
+                        <div>
+                            <select class="base-input" onchange={on_root_type_change}>
+                                {render_type_options(&props.type_names, &props.root_type)}
+                            </select>
+                        </div>
+                    </div>
+                }
+            }}
+        </div>
+    }
+}
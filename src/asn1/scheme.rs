@@ -5,22 +5,32 @@ mod set;
 mod strings;
 mod tag;
 mod time;
+mod unparsed;
 
-use asn1_parser::{Asn1, Asn1Entity, Asn1Type};
+use asn1_parser::{Asn1, Asn1Entity, Asn1Type, SchemaAnnotation};
 use web_sys::MouseEvent;
 use yew::virtual_dom::VNode;
-use yew::{classes, function_component, html, Callback, Children, Classes, Html, Properties};
+use yew::{
+    classes, function_component, html, use_context, use_state, Callback, Children, Classes, ContextProvider, Html,
+    Properties,
+};
 
-use self::oid::ObjectIdentifierNode;
-use self::primitive::{BoolNode, IntegerNode, NullNode};
+use self::oid::{ObjectIdentifierNode, RelativeOidNode};
+use self::primitive::{BoolNode, EnumeratedNode, IntegerNode, NullNode, RealNode};
 use self::sequence::SequenceNode;
 use self::strings::{
-    BitStringNode, BmpStringNode, GeneralStringNode, IA5StringNode, NumericStringNode, OctetStringNode,
-    PrintableStringNode, Utf8StringNode, VisibleStringNode,
+    BitStringNode, BmpStringNode, GeneralStringNode, GraphicStringNode, IA5StringNode, NumericStringNode,
+    ObjectDescriptorNode, OctetStringNode, PrintableStringNode, TeletexStringNode, UniversalStringNode, Utf8StringNode,
+    VideotexStringNode, VisibleStringNode,
 };
-use self::tag::{ApplicationTagNode, ExplicitTagNode, ImplicitTagNode};
+use self::tag::{ApplicationTagNode, ExplicitTagNode, ImplicitTagNode, UnknownNode};
 use self::time::{GeneralizedTimeNode, UtcTimeNode};
+use self::unparsed::UnparsedNode;
+use crate::asn1::diff_view::{diff_class, DiffMarks};
+use crate::asn1::edit_view::{editable_value, NodeEdit, NodeEditor, NodeValueInput};
+use crate::asn1::schema_view::SchemaAnnotations;
 use crate::asn1::scheme::set::SetNode;
+use crate::asn1::search_view::SearchMatches;
 use crate::asn1::HighlightAction;
 
 #[derive(PartialEq, Properties, Clone)]
@@ -28,19 +38,32 @@ pub struct Asn1NodeProps {
     pub id: u64,
     pub cur_id: Option<u64>,
     pub set_cur_node: Callback<HighlightAction>,
+    /// Text value of the node. The node can be edited by double-click if it's present
+    #[prop_or_default]
+    pub value: Option<String>,
 
     pub children: Children,
 }
 
 #[function_component(Asn1Node)]
 pub fn asn1_node(props: &Asn1NodeProps) -> Html {
-    fn get_node_class(id: u64, cur_id: &Option<u64>) -> Classes {
-        match cur_id {
+    fn get_node_class(
+        id: u64,
+        cur_id: &Option<u64>,
+        is_search_match: bool,
+        diff_mark: Option<&'static str>,
+    ) -> Classes {
+        let mut class = match cur_id {
             Some(cur_id) if *cur_id == id => {
                 classes!("hover_node", "asn1-node-container")
             }
             _ => classes!("asn1-node-container"),
+        };
+        if is_search_match {
+            class.push("asn1-search-match");
         }
+        class.push(diff_mark);
+        class
     }
 
     let asn1_node_id = props.id;
@@ -53,114 +76,153 @@ pub fn asn1_node(props: &Asn1NodeProps) -> Html {
         set_cur_node.emit(HighlightAction::Hide(asn1_node_id));
     });
 
+    // the annotation is provided for the node options of this node only: nested nodes provide their own ones
+    let annotation = use_context::<SchemaAnnotations>().and_then(|annotations| annotations.get(props.id));
+
+    let is_search_match = use_context::<SearchMatches>().map_or(false, |matches| matches.contains(props.id));
+    let diff_mark = use_context::<DiffMarks>()
+        .and_then(|marks| marks.get(props.id))
+        .map(diff_class);
+
+    let editor = use_context::<NodeEditor>();
+    let editing = use_state(|| false);
+    let editable = editor.is_some() && props.value.is_some();
+    let editing_setter = editing.setter();
+    let ondblclick = Callback::from(move |event: MouseEvent| {
+        // parent nodes should not handle the double-click of the edited node
+        if editable {
+            event.stop_propagation();
+            editing_setter.set(true);
+        }
+    });
+
+    let value_input = match (editor, props.value.clone()) {
+        (Some(NodeEditor(edit_node)), Some(value)) if *editing => {
+            let editing_setter = editing.setter();
+            let submit = Callback::from(move |value| {
+                edit_node.emit(NodeEdit {
+                    node_id: asn1_node_id,
+                    value,
+                });
+                editing_setter.set(false);
+            });
+            let editing_setter = editing.setter();
+            let cancel = Callback::from(move |_| editing_setter.set(false));
+
+            html! {
+                <NodeValueInput {value} {submit} {cancel} />
+            }
+        }
+        _ => html! {},
+    };
+
     html! {
-        <div class={get_node_class(props.id, &props.cur_id)} {onmouseenter} {onmouseleave}>
-            {props.children.clone()}
+        <div class={get_node_class(props.id, &props.cur_id, is_search_match, diff_mark)} {onmouseenter} {onmouseleave} {ondblclick}>
+            <ContextProvider<Option<SchemaAnnotation>> context={annotation}>
+                {props.children.clone()}
+            </ContextProvider<Option<SchemaAnnotation>>>
+            {value_input}
         </div>
     }
 }
 
 pub fn build_asn1_schema(asn1: &Asn1<'_>, cur_id: &Option<u64>, set_cur_node: &Callback<HighlightAction>) -> VNode {
-    match asn1.inner_asn1() {
+    let node = match asn1.inner_asn1() {
         Asn1Type::OctetString(octet) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <OctetStringNode node={octet.to_owned()} meta={asn1.meta().to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} />
-            </Asn1Node>
+            <OctetStringNode node={octet.to_owned()} meta={asn1.meta().to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} />
         },
         Asn1Type::Utf8String(utf8) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <Utf8StringNode node={utf8.to_owned()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <Utf8StringNode node={utf8.to_owned()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::IA5String(ia5) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <IA5StringNode node={ia5.to_owned()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <IA5StringNode node={ia5.to_owned()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::PrintableString(printable) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <PrintableStringNode node={printable.to_owned()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <PrintableStringNode node={printable.to_owned()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::GeneralString(general) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <GeneralStringNode node={general.to_owned()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <GeneralStringNode node={general.to_owned()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::NumericString(numeric) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <NumericStringNode node={numeric.to_owned()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <NumericStringNode node={numeric.to_owned()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::VisibleString(visible) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <VisibleStringNode node={visible.to_owned()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <VisibleStringNode node={visible.to_owned()} meta={asn1.meta().to_owned()} />
+        },
+        Asn1Type::UniversalString(universal) => html! {
+            <UniversalStringNode node={universal.to_owned()} meta={asn1.meta().to_owned()} />
+        },
+        Asn1Type::TeletexString(teletex) => html! {
+            <TeletexStringNode node={teletex.to_owned()} meta={asn1.meta().to_owned()} />
+        },
+        Asn1Type::VideotexString(videotex) => html! {
+            <VideotexStringNode node={videotex.to_owned()} meta={asn1.meta().to_owned()} />
+        },
+        Asn1Type::GraphicString(graphic) => html! {
+            <GraphicStringNode node={graphic.to_owned()} meta={asn1.meta().to_owned()} />
+        },
+        Asn1Type::ObjectDescriptor(descriptor) => html! {
+            <ObjectDescriptorNode node={descriptor.to_owned()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::Sequence(sequence) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <SequenceNode node={sequence.to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <SequenceNode node={sequence.to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::Set(set) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <SetNode node={set.to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <SetNode node={set.to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::BitString(bit) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <BitStringNode node={bit.to_owned()} meta={asn1.meta().to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} />
-            </Asn1Node>
+            <BitStringNode node={bit.to_owned()} meta={asn1.meta().to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} />
         },
         Asn1Type::Bool(boolean) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <BoolNode node={boolean.to_owned()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <BoolNode node={boolean.to_owned()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::BmpString(bmp) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <BmpStringNode node={bmp.to_owned()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <BmpStringNode node={bmp.to_owned()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::Null(_) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <NullNode meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <NullNode meta={asn1.meta().to_owned()} />
         },
         Asn1Type::Integer(integer) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <IntegerNode node={integer.to_owned()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <IntegerNode node={integer.to_owned()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::ObjectIdentifier(object_identifier) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <ObjectIdentifierNode node={object_identifier.clone()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <ObjectIdentifierNode node={object_identifier.clone()} meta={asn1.meta().to_owned()} />
+        },
+        Asn1Type::Enumerated(enumerated) => html! {
+            <EnumeratedNode node={enumerated.to_owned()} meta={asn1.meta().to_owned()} />
+        },
+        Asn1Type::Real(real) => html! {
+            <RealNode node={real.to_owned()} meta={asn1.meta().to_owned()} />
+        },
+        Asn1Type::RelativeOid(relative_oid) => html! {
+            <RelativeOidNode node={relative_oid.clone()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::ExplicitTag(explicit) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <ExplicitTagNode node={explicit.to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <ExplicitTagNode node={explicit.to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::ImplicitTag(implicit) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <ImplicitTagNode node={implicit.to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <ImplicitTagNode node={implicit.to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::ApplicationTag(application) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <ApplicationTagNode node={application.to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <ApplicationTagNode node={application.to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} meta={asn1.meta().to_owned()} />
+        },
+        Asn1Type::Unknown(unknown) => html! {
+            <UnknownNode node={unknown.to_owned()} cur_node={cur_id} set_cur_node={set_cur_node.clone()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::UtcTime(utc_time) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <UtcTimeNode node={utc_time.to_owned()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <UtcTimeNode node={utc_time.to_owned()} meta={asn1.meta().to_owned()} />
         },
         Asn1Type::GeneralizedTime(generalized_time) => html! {
-            <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()}>
-                <GeneralizedTimeNode node={generalized_time.to_owned()} meta={asn1.meta().to_owned()} />
-            </Asn1Node>
+            <GeneralizedTimeNode node={generalized_time.to_owned()} meta={asn1.meta().to_owned()} />
+        },
+        Asn1Type::Unparsed(unparsed) => html! {
+            <UnparsedNode node={unparsed.to_owned()} meta={asn1.meta().to_owned()} />
         },
+    };
+
+    html! {
+        <Asn1Node id={asn1.id()} {cur_id} set_cur_node={set_cur_node.clone()} value={editable_value(asn1)}>
+            {node}
+        </Asn1Node>
     }
 }
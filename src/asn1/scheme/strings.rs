@@ -1,6 +1,7 @@
 use asn1_parser::{
-    OwnedBitString, OwnedBmpString, OwnedGeneralString, OwnedIA5String, OwnedNumericString, OwnedOctetString,
-    OwnedPrintableString, OwnedRawAsn1EntityData, OwnedUtf8String, OwnedVisibleString,
+    OwnedBitString, OwnedBmpString, OwnedGeneralString, OwnedGraphicString, OwnedIA5String, OwnedNumericString,
+    OwnedObjectDescriptor, OwnedOctetString, OwnedPrintableString, OwnedRawAsn1EntityData, OwnedTeletexString,
+    OwnedUniversalString, OwnedUtf8String, OwnedVideotexString, OwnedVisibleString,
 };
 use yew::{function_component, html, Callback, Html, Properties};
 
@@ -128,9 +129,33 @@ pub fn bmp_string(props: &BmpStringNodeProps) -> Html {
     }
 }
 
+#[derive(PartialEq, Properties, Clone)]
+pub struct UniversalStringNodeProps {
+    pub node: OwnedUniversalString,
+    pub meta: OwnedRawAsn1EntityData,
+}
+
+#[function_component(UniversalStringNode)]
+pub fn universal_string(props: &UniversalStringNodeProps) -> Html {
+    let offset = props.meta.tag_position();
+    let length_len = props.meta.length_range().len();
+    let data_len = props.meta.data_range().len();
+
+    html! {
+        <div class="terminal-asn1-node">
+            <NodeOptions node_bytes={RcSlice::from(props.meta.raw_bytes())} {offset} {length_len} {data_len} name={String::from("UniversalString")} />
+            <span class="asn-simple-value">{props.node.string()}</span>
+        </div>
+    }
+}
+
 define_string_node!(GeneralString);
 define_string_node!(IA5String);
 define_string_node!(PrintableString);
 define_string_node!(Utf8String);
 define_string_node!(NumericString);
 define_string_node!(VisibleString);
+define_string_node!(TeletexString);
+define_string_node!(VideotexString);
+define_string_node!(GraphicString);
+define_string_node!(ObjectDescriptor);
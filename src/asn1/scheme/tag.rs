@@ -1,6 +1,8 @@
 use std::str::from_utf8;
 
-use asn1_parser::{OwnedApplicationTag, OwnedExplicitTag, OwnedImplicitTag, OwnedRawAsn1EntityData};
+use asn1_parser::{
+    OwnedApplicationTag, OwnedExplicitTag, OwnedImplicitTag, OwnedRawAsn1EntityData, OwnedUnknown, Taggable,
+};
 use yew::{function_component, html, Callback, Html, Properties};
 
 use crate::asn1::node_options::NodeOptions;
@@ -114,3 +116,40 @@ pub fn implicit_tag(props: &ImplicitTagProps) -> Html {
         },
     }
 }
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct UnknownProps {
+    pub node: OwnedUnknown,
+    pub cur_node: Option<u64>,
+    pub set_cur_node: Callback<HighlightAction>,
+    pub meta: OwnedRawAsn1EntityData,
+}
+
+#[function_component(UnknownNode)]
+pub fn unknown(props: &UnknownProps) -> Html {
+    let offset = props.meta.tag_position();
+    let length_len = props.meta.length_range().len();
+    let data_len = props.meta.data_range().len();
+    let octets = props.node.octets();
+    let name = props.node.tag().to_string();
+
+    match props.node.fields() {
+        Some(fields) => html! {
+            <div style="cursor: crosshair; width: 100%">
+                <div class="asn1-constructor-header">
+                    <NodeOptions node_bytes={RcSlice::from(props.meta.raw_bytes())} {offset} {length_len} {data_len} {name}/>
+                </div>
+                <div class="asn1-constructor-body">
+                    {fields.iter().map(|f| build_asn1_schema(f, &props.cur_node, &props.set_cur_node)).collect::<Vec<_>>()}
+                </div>
+            </div>
+        },
+        None => html! {
+            <div class="terminal-asn1-node">
+                <NodeOptions node_bytes={RcSlice::from(props.meta.raw_bytes())} {offset} {length_len} {data_len} {name} />
+                <span class="asn1-node-info-label">{format!("({} bytes)", octets.len())}</span>
+                <span class="asn-simple-value">{hex::encode(octets)}</span>
+            </div>
+        },
+    }
+}
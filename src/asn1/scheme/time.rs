@@ -1,4 +1,5 @@
-use asn1_parser::{GeneralizedTime, OwnedRawAsn1EntityData, UtcTime};
+use asn1_parser::{Asn1Result, GeneralizedTime, OwnedRawAsn1EntityData, UtcTime};
+use time::{Duration, OffsetDateTime, UtcOffset};
 use yew::{function_component, html, Html, Properties};
 
 use crate::asn1::node_options::NodeOptions;
@@ -20,6 +21,7 @@ pub fn utc_time_string(props: &UtcTimeNodeProps) -> Html {
         <div class="terminal-asn1-node">
             <NodeOptions node_bytes={RcSlice::from(props.meta.raw_bytes())} {offset} {length_len} {data_len} name={String::from("UtcTime")}/>
             <span class="asn-simple-value">{format_utc_time(&props.node)}</span>
+            {timestamp_details(OffsetDateTime::try_from(&props.node), false)}
         </div>
     }
 }
@@ -40,22 +42,91 @@ pub fn general_time_string(props: &GeneralizedTimeNodeProps) -> Html {
         <div class="terminal-asn1-node">
             <NodeOptions node_bytes={RcSlice::from(props.meta.raw_bytes())} {offset} {length_len} {data_len} name={String::from("GeneralizedTime")}/>
             <span class="asn-simple-value">{format_generalized_time(&props.node)}</span>
+            {timestamp_details(OffsetDateTime::try_from(&props.node), props.node.local_time.is_some())}
         </div>
     }
 }
 
-fn format_utc_time(utc_time: &UtcTime) -> String {
-    use time::OffsetDateTime;
+/// Renders the local and relative time. The UTC time is rendered only if the node value is not in UTC
+fn timestamp_details(timestamp: Asn1Result<OffsetDateTime>, with_utc: bool) -> Html {
+    let timestamp = match timestamp {
+        Ok(timestamp) => timestamp,
+        Err(err) => {
+            return html! {
+                <span class="asn1-node-info-label asn1-unparsed-value">{format!("invalid date: {}", err)}</span>
+            }
+        }
+    };
+    let local_offset = UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC);
 
-    let mut formatted = String::new();
+    html! {
+        <>
+            {if with_utc {
+                html! { <span class="asn1-node-info-label">{format!("UTC: {}", format_timestamp(timestamp))}</span> }
+            } else {
+                html! {}
+            }}
+            <span class="asn1-node-info-label">{format!("local: {}", format_timestamp(timestamp.to_offset(local_offset)))}</span>
+            <span class="asn1-node-info-label">{format_relative_time(timestamp - OffsetDateTime::now_utc())}</span>
+        </>
+    }
+}
 
-    let current_year = (OffsetDateTime::now_utc().year() % 100) as u8;
-    if current_year < *utc_time.year.as_ref() {
-        formatted.push_str("19");
+fn format_timestamp(timestamp: OffsetDateTime) -> String {
+    let mut formatted = format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        timestamp.year(),
+        u8::from(timestamp.month()),
+        timestamp.day(),
+        timestamp.hour(),
+        timestamp.minute(),
+        timestamp.second(),
+    );
+
+    let offset = timestamp.offset();
+    if offset.is_utc() {
+        formatted.push_str(" UTC");
     } else {
-        formatted.push_str("20");
+        formatted.push_str(&format!(
+            " {}{:02}:{:02}",
+            if offset.is_negative() { '-' } else { '+' },
+            offset.whole_hours().unsigned_abs(),
+            offset.minutes_past_hour().unsigned_abs(),
+        ));
     }
-    formatted.push_str(&format!("{:02}", utc_time.year.as_ref()));
+
+    formatted
+}
+
+/// Formats the difference between the timestamp and now using the largest unit, e.g. `in 3 days` or `2 hours ago`
+fn format_relative_time(difference: Duration) -> String {
+    const UNITS: [(&str, i64); 5] = [
+        ("year", 365 * 24 * 60 * 60),
+        ("day", 24 * 60 * 60),
+        ("hour", 60 * 60),
+        ("minute", 60),
+        ("second", 1),
+    ];
+
+    let seconds = difference.whole_seconds().unsigned_abs();
+    let (unit, amount) = UNITS
+        .iter()
+        .map(|(unit, unit_seconds)| (unit, seconds / *unit_seconds as u64))
+        .find(|(_, amount)| *amount > 0)
+        .unwrap_or((&"second", 0));
+    let amount = format!("{} {}{}", amount, unit, if amount == 1 { "" } else { "s" });
+
+    if difference.is_negative() {
+        format!("{} ago", amount)
+    } else {
+        format!("in {}", amount)
+    }
+}
+
+fn format_utc_time(utc_time: &UtcTime) -> String {
+    let mut formatted = String::new();
+
+    formatted.push_str(&format!("{:04}", utc_time.full_year()));
 
     formatted.push('-');
     formatted.push_str(&format!("{:02}", utc_time.month.as_ref()));
@@ -0,0 +1,28 @@
+use asn1_parser::{OwnedRawAsn1EntityData, OwnedUnparsed};
+use yew::{function_component, html, Html, Properties};
+
+use crate::asn1::node_options::NodeOptions;
+use crate::common::RcSlice;
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct UnparsedNodeProps {
+    pub node: OwnedUnparsed,
+    pub meta: OwnedRawAsn1EntityData,
+}
+
+#[function_component(UnparsedNode)]
+pub fn unparsed(props: &UnparsedNodeProps) -> Html {
+    let data = props.node.data();
+
+    let offset = props.meta.tag_position();
+    let length_len = props.meta.length_range().len();
+    let data_len = props.meta.data_range().len();
+
+    html! {
+        <div class="terminal-asn1-node">
+            <NodeOptions node_bytes={RcSlice::from(props.meta.raw_bytes())} {offset} {length_len} {data_len} name={String::from("Unparsed")} />
+            <span class="asn1-node-info-label">{format!("({} bytes)", data.len())}</span>
+            <span class="asn-simple-value asn1-unparsed-value">{hex::encode(data)}</span>
+        </div>
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+use web_sys::HtmlInputElement;
+use yew::{function_component, html, Callback, Html, Properties, TargetCast};
+
+/// Ids of the asn1 nodes matched by the search path
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SearchMatches(pub Rc<BTreeSet<u64>>);
+
+impl SearchMatches {
+    pub fn contains(&self, id: u64) -> bool {
+        self.0.contains(&id)
+    }
+}
+
+#[derive(PartialEq, Properties, Clone)]
+pub struct Asn1SearchProps {
+    pub path: String,
+    pub set_path: Callback<String>,
+    /// Amount of the matched nodes or the path parsing error
+    pub result: Result<usize, String>,
+}
+
+/// Renders the path input and the search result
+#[function_component(Asn1Search)]
+pub fn asn1_search(props: &Asn1SearchProps) -> Html {
+    let set_path = props.set_path.clone();
+    let oninput = Callback::from(move |event: html::oninput::Event| {
+        let input: HtmlInputElement = event.target_unchecked_into();
+        set_path.emit(input.value());
+    });
+
+    html! {
+        <div class="horizontal asn1-search">
+            <input
+                class="base-input"
+                placeholder={"path, e.g. seq/ctx[3]/seq/int or **/oid=2.5.4.3"}
+                value={props.path.clone()}
+                {oninput}
+            />
+            {match &props.result {
+                _ if props.path.trim().is_empty() => html! {},
+                Ok(count) => html! {
+                    <span class="total">{format!("{} matches", count)}</span>
+                },
+                Err(error) => html! {
+                    <span class="asn1-error-message">{format!("Invalid path: {}", error)}</span>
+                },
+            }}
+        </div>
+    }
+}